@@ -0,0 +1,78 @@
+//! Benchmarks of the hot paths of LinearPL: evaluating tracks, emitting
+//! particles and stepping several systems together. Run with
+//! `cargo bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use linearpl::curves::{Track, TrackMode};
+use linearpl::linear_particles::LinearParticles;
+use linearpl::math::{Color, Vec3};
+use linearpl::particle_sys::{LoopMode, ParticleSys};
+
+// seconds simulated by each step of the benchmarked systems
+const DT: f32 = 1. / 60.;
+
+fn track_interpolation(c: &mut Criterion) {
+    let densities = Track::new(vec![0., 0.5, 1., 0.25, 0.75]);
+    let colors = Track::new(vec![
+        Color::new(1., 0., 0., 1.),
+        Color::new(0., 1., 0., 1.),
+        Color::new(0., 0., 1., 1.),
+    ]);
+    let mut smooth = densities.clone();
+    smooth.options.mode = TrackMode::CatmullRom;
+
+    c.bench_function("track_f32", |b| {
+        b.iter(|| densities.value_at(black_box(0.37), 1.))
+    });
+    c.bench_function("track_color", |b| {
+        b.iter(|| colors.value_at(black_box(0.37), 1.))
+    });
+    c.bench_function("track_catmull_rom", |b| {
+        b.iter(|| smooth.value_at(black_box(0.37), 1.))
+    });
+}
+
+// looping system emitting `rate` particles per second
+fn emitter(rate: f32) -> LinearParticles {
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_rates(&[rate])
+        .unwrap()
+        .with_decay(1.)
+        .unwrap()
+        .with_velocity(Vec3::Y, 0.5)
+        .unwrap()
+        .with_gravity(Vec3::new(0., -9.8, 0.));
+    lp.setup(LoopMode::Loop, None).unwrap();
+    lp
+}
+
+fn emission(c: &mut Criterion) {
+    let mut lp = emitter(10_000.);
+    // settle to a steady particle count before measuring
+    for _ in 0..60 {
+        lp.step(DT).unwrap();
+    }
+    c.bench_function("emission_10k_per_sec", |b| {
+        b.iter(|| {
+            if !lp.step(DT).unwrap() {
+                lp.setup(LoopMode::Loop, None).unwrap();
+            }
+        })
+    });
+}
+
+fn group_update(c: &mut Criterion) {
+    let mut systems: Vec<LinearParticles> = (0..16).map(|_| emitter(1_000.)).collect();
+    c.bench_function("update_16_systems", |b| {
+        b.iter(|| {
+            for lp in systems.iter_mut() {
+                if !lp.step(DT).unwrap() {
+                    lp.setup(LoopMode::Loop, None).unwrap();
+                }
+            }
+        })
+    });
+}
+
+criterion_group!(benches, track_interpolation, emission, group_update);
+criterion_main!(benches);
@@ -0,0 +1,162 @@
+//! # LinearPL Preview
+//!
+//! Previewer for effect files. Renders the system or group described by
+//! a RON or JSON effect file, reloading it each time the file is saved:
+//!
+//! ```text
+//! cargo run --features ron,json --bin linearpl-preview -- effects/seq.ron
+//! ```
+//!
+//! Drag the mouse to orbit the camera and scroll to zoom. `F` frames the
+//! live particles, `G` toggles the gizmos of `draw_debug()`, `R` restarts
+//! the effect, `Space` pauses it and `Escape` quits. Effects that run
+//! once are replayed when they finish.
+
+use macroquad::prelude::*;
+
+use linearpl::error::LinearPLError;
+use linearpl::gizmos::Gizmos;
+use linearpl::particle_sys::ParticleSysDyn;
+use linearpl::render::register_camera;
+use linearpl::watch::EffectWatcher;
+
+const ORBIT_SPEED: f32 = 0.8;
+const ZOOM_SPEED: f32 = 0.1;
+const MIN_DISTANCE: f32 = 0.5;
+const MAX_DISTANCE: f32 = 100.;
+
+#[macroquad::main("LinearPL Preview")]
+async fn main() -> Result<(), LinearPLError> {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: linearpl-preview <effect.ron|effect.json>");
+        std::process::exit(2);
+    };
+    let mut watcher = EffectWatcher::new(&path)?;
+    let mut error: Option<String> = None;
+
+    let mut target = vec3(0., 0., 0.);
+    let mut distance: f32 = 6.;
+    let mut yaw: f32 = 1.57;
+    let mut pitch: f32 = 0.3;
+    let mut mouse_pressed = false;
+    let mut gizmos = false;
+
+    loop {
+        if is_key_pressed(KeyCode::Escape) {
+            break;
+        }
+
+        // pick up saves of the effect file, keeping the last good effect
+        // on screen while the file doesn't load
+        match watcher.poll() {
+            Ok(true) => error = None,
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                error = Some(e.to_string());
+            }
+        }
+
+        if is_key_pressed(KeyCode::Space) {
+            match watcher.clock().is_paused() {
+                true => watcher.clock().resume(),
+                false => watcher.clock().pause(),
+            }
+        }
+        if is_key_pressed(KeyCode::R) {
+            let sys = watcher.system();
+            let looping = sys.is_looping();
+            sys.stop();
+            match looping {
+                true => sys.start_loop()?,
+                false => sys.start()?,
+            }
+        }
+        if is_key_pressed(KeyCode::G) {
+            gizmos = !gizmos;
+        }
+        if is_key_pressed(KeyCode::F) {
+            if let Some(center) = particle_center(watcher.system().as_ref()) {
+                target = center;
+            }
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            mouse_pressed = true;
+        }
+        if is_mouse_button_released(MouseButton::Left) {
+            mouse_pressed = false;
+        }
+        if mouse_pressed {
+            let d_mouse = mouse_delta_position();
+            yaw += d_mouse.x * ORBIT_SPEED;
+            pitch = (pitch + d_mouse.y * -ORBIT_SPEED).clamp(-1.5, 1.5);
+        }
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0. {
+            distance =
+                (distance * (1. - wheel.signum() * ZOOM_SPEED)).clamp(MIN_DISTANCE, MAX_DISTANCE);
+        }
+
+        clear_background(BLACK);
+        let offset = vec3(
+            yaw.cos() * pitch.cos(),
+            pitch.sin(),
+            yaw.sin() * pitch.cos(),
+        );
+        let camera = Camera3D {
+            position: target + offset * distance,
+            up: vec3(0., 1., 0.),
+            target,
+            ..Default::default()
+        };
+        set_camera(&camera);
+        register_camera(&camera);
+        draw_grid(20, 1., DARKGRAY, Color::new(0.2, 0.2, 0.2, 1.));
+
+        let sys = watcher.system();
+        if !sys.is_active() {
+            sys.start()?;
+        }
+        sys.run()?;
+        if gizmos {
+            sys.draw_debug(&Gizmos::new());
+        }
+
+        set_default_camera();
+        draw_text(&path, 10., 20., 20., WHITE);
+        draw_text(
+            &format!("{} particles", watcher.system().particle_count()),
+            10.,
+            40.,
+            20.,
+            WHITE,
+        );
+        if watcher.clock().is_paused() {
+            draw_text("paused", 10., 60., 20., YELLOW);
+        }
+        if let Some(e) = &error {
+            draw_text(e, 10., screen_height() - 30., 20., RED);
+        }
+        draw_text(
+            "drag to orbit, scroll to zoom, F to frame, G for gizmos, R to restart, space to pause",
+            10.,
+            screen_height() - 10.,
+            20.,
+            GRAY,
+        );
+
+        next_frame().await;
+    }
+    Ok(())
+}
+
+// average location of the live particles of `sys`, if any
+fn particle_center(sys: &dyn ParticleSysDyn) -> Option<Vec3> {
+    let (mut sum, mut count) = (Vec3::ZERO, 0);
+    sys.for_each_particle(&mut |p| {
+        sum += p.location();
+        count += 1;
+    });
+    (count > 0).then(|| sum / count as f32)
+}
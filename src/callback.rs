@@ -0,0 +1,204 @@
+//! # Callbacks
+//!
+//! User registered callbacks that are invoked by particle systems
+//! when certain events occur, such as a period finishing or a loop
+//! wrapping back around to the start.
+//!
+//! Callbacks are reference counted so that cloning a particle system
+//! (e.g. with the `clone_with_*` methods) shares the same callback
+//! instead of requiring it to be `Clone` itself.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::math::Vec3;
+use crate::particle::Particle;
+
+/// Boxed `FnMut` callback shared between clones of a particle system.
+#[derive(Clone)]
+pub struct Callback(Rc<RefCell<Box<dyn FnMut()>>>);
+
+impl Callback {
+    /// Wrap the closure `f` as a new Callback.
+    pub fn new(f: impl FnMut() + 'static) -> Self {
+        Callback(Rc::new(RefCell::new(Box::new(f))))
+    }
+
+    /// Invoke the wrapped closure.
+    pub fn call(&self) {
+        (self.0.borrow_mut())()
+    }
+}
+
+impl fmt::Debug for Callback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
+type SpawnFn = dyn FnMut(&mut Particle);
+
+/// Boxed `FnMut` callback invoked with each newly spawned Particle.
+#[derive(Clone)]
+pub struct SpawnCallback(Rc<RefCell<Box<SpawnFn>>>);
+
+impl SpawnCallback {
+    /// Wrap the closure `f` as a new SpawnCallback.
+    pub fn new(f: impl FnMut(&mut Particle) + 'static) -> Self {
+        SpawnCallback(Rc::new(RefCell::new(Box::new(f))))
+    }
+
+    /// Invoke the wrapped closure on Particle `p`.
+    pub fn call(&self, p: &mut Particle) {
+        (self.0.borrow_mut())(p)
+    }
+}
+
+impl fmt::Debug for SpawnCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SpawnCallback")
+    }
+}
+
+type UpdateFn = dyn FnMut(&mut Particle, f32, f32);
+
+/// Boxed `FnMut` callback invoked with each live Particle every frame.
+#[derive(Clone)]
+pub struct UpdateCallback(Rc<RefCell<Box<UpdateFn>>>);
+
+impl UpdateCallback {
+    /// Wrap the closure `f` as a new UpdateCallback.
+    pub fn new(f: impl FnMut(&mut Particle, f32, f32) + 'static) -> Self {
+        UpdateCallback(Rc::new(RefCell::new(Box::new(f))))
+    }
+
+    /// Invoke the wrapped closure on Particle `p` stepped by `dt` seconds
+    /// to `age` seconds.
+    pub fn call(&self, p: &mut Particle, dt: f32, age: f32) {
+        (self.0.borrow_mut())(p, dt, age)
+    }
+
+    // whether `other` is a clone of the same callback
+    pub(crate) fn ptr_eq(&self, other: &UpdateCallback) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for UpdateCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UpdateCallback")
+    }
+}
+
+/// Boxed `FnMut` callback sampling a signal, such as the amplitude of
+/// the music playing, once each frame.
+#[derive(Clone)]
+pub struct SignalCallback(Rc<RefCell<Box<dyn FnMut() -> f32>>>);
+
+impl SignalCallback {
+    /// Wrap the closure `f` as a new SignalCallback.
+    pub fn new(f: impl FnMut() -> f32 + 'static) -> Self {
+        SignalCallback(Rc::new(RefCell::new(Box::new(f))))
+    }
+
+    /// Invoke the wrapped closure, returning the current value of the
+    /// signal.
+    pub fn call(&self) -> f32 {
+        (self.0.borrow_mut())()
+    }
+}
+
+impl fmt::Debug for SignalCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SignalCallback")
+    }
+}
+
+/// Boxed `FnMut` callback returning the location of a moving object a
+/// system follows, sampled once each frame.
+#[derive(Clone)]
+pub struct FollowCallback(Rc<RefCell<Box<dyn FnMut() -> Vec3>>>);
+
+impl FollowCallback {
+    /// Wrap the closure `f` as a new FollowCallback.
+    pub fn new(f: impl FnMut() -> Vec3 + 'static) -> Self {
+        FollowCallback(Rc::new(RefCell::new(Box::new(f))))
+    }
+
+    /// Invoke the wrapped closure, returning the current location of
+    /// the object followed.
+    pub fn call(&self) -> Vec3 {
+        (self.0.borrow_mut())()
+    }
+}
+
+impl fmt::Debug for FollowCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FollowCallback")
+    }
+}
+
+/// Completion and loop callbacks held by a particle system.
+///
+/// `on_complete` is only invoked once per `start()`, even though
+/// `run()` continues returning `Ok(false)` afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct Hooks {
+    on_complete: Option<Callback>,
+    on_loop: Option<Callback>,
+    completed: bool,
+}
+
+impl Hooks {
+    /// Set the callback invoked when the period finishes.
+    pub fn set_on_complete(&mut self, f: impl FnMut() + 'static) {
+        self.on_complete = Some(Callback::new(f));
+    }
+
+    /// Set the callback invoked when a loop wraps around.
+    pub fn set_on_loop(&mut self, f: impl FnMut() + 'static) {
+        self.on_loop = Some(Callback::new(f));
+    }
+
+    /// Invoke `on_complete` if it hasn't been since the last `reset()`.
+    pub fn complete(&mut self) {
+        if self.completed {
+            return;
+        }
+        self.completed = true;
+        if let Some(f) = &self.on_complete {
+            f.call();
+        }
+    }
+
+    /// Invoke `on_loop`.
+    pub fn looped(&self) {
+        if let Some(f) = &self.on_loop {
+            f.call();
+        }
+    }
+
+    /// Allow `on_complete` to be invoked again.
+    pub fn reset(&mut self) {
+        self.completed = false;
+    }
+}
+
+#[test]
+fn hooks_complete_once_test() {
+    use std::cell::Cell;
+
+    let count = Rc::new(Cell::new(0));
+    let c = count.clone();
+    let mut hooks = Hooks::default();
+    hooks.set_on_complete(move || c.set(c.get() + 1));
+
+    hooks.complete();
+    hooks.complete();
+    assert_eq!(count.get(), 1);
+
+    hooks.reset();
+    hooks.complete();
+    assert_eq!(count.get(), 2);
+}
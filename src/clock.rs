@@ -0,0 +1,135 @@
+//! # Clock
+//!
+//! Time shared between systems. A `Clock` is a handle that any number of
+//! systems and groups can follow in place of their own wall time, so that
+//! pausing or slowing it down affects all of them at once:
+//!
+//! ```ignore
+//! let clock = Clock::new();
+//! let mut sparks = LinearParticles::new(start, end).with_clock(&clock);
+//! let mut smoke = SyncGrp::new(2., &parts).with_clock(&clock);
+//!
+//! clock.pause();
+//! clock.set_scale(0.25)?;
+//! ```
+//!
+//! Clones of a `Clock` share the same time.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::LinearPLError;
+use crate::time::now;
+use crate::util::check_time_scale;
+
+/// Handle to a clock shared by every system following it.
+#[derive(Debug, Clone, Default)]
+pub struct Clock(Rc<RefCell<ClockState>>);
+
+#[derive(Debug)]
+struct ClockState {
+    // seconds on the clock when the wall clock read `anchor`
+    time: f32,
+    anchor: f64,
+    scale: f32,
+    paused: bool,
+}
+
+impl Default for ClockState {
+    fn default() -> Self {
+        ClockState {
+            time: 0.,
+            anchor: now(),
+            scale: 1.,
+            paused: false,
+        }
+    }
+}
+
+impl ClockState {
+    // seconds on the clock by the wall clock reading `wall`
+    fn time_at(&self, wall: f64) -> f32 {
+        match self.paused {
+            true => self.time,
+            false => self.time + (wall - self.anchor) as f32 * self.scale,
+        }
+    }
+
+    // fold the time passed so far into `time`, so that a change of
+    // speed only applies from now on
+    fn rebase(&mut self) {
+        let wall = now();
+        self.time = self.time_at(wall);
+        self.anchor = wall;
+    }
+}
+
+impl Clock {
+    /// Create a new Clock running at normal speed from `0.`.
+    pub fn new() -> Self {
+        Clock::default()
+    }
+
+    /// Return the seconds passed on the Clock since it was created.
+    pub fn time(&self) -> f32 {
+        self.0.borrow().time_at(now())
+    }
+
+    /// Stop the Clock, freezing every system following it.
+    pub fn pause(&self) {
+        let mut state = self.0.borrow_mut();
+        state.rebase();
+        state.paused = true;
+    }
+
+    /// Start the Clock again after `pause()`.
+    pub fn resume(&self) {
+        let mut state = self.0.borrow_mut();
+        state.rebase();
+        state.paused = false;
+    }
+
+    /// Return `true` if the Clock is paused.
+    pub fn is_paused(&self) -> bool {
+        self.0.borrow().paused
+    }
+
+    /// Run the Clock at `scale` times the speed of wall time, such as
+    /// `0.5` for slow motion. Returns an error if `scale` is negative.
+    pub fn set_scale(&self, scale: f32) -> Result<(), LinearPLError> {
+        check_time_scale(scale)?;
+        let mut state = self.0.borrow_mut();
+        state.rebase();
+        state.scale = scale;
+        Ok(())
+    }
+
+    /// Return the speed of the Clock relative to wall time.
+    pub fn scale(&self) -> f32 {
+        self.0.borrow().scale
+    }
+
+    /// Move the Clock `dt` seconds forward, such as to step through an
+    /// effect frame by frame while paused.
+    pub fn advance(&self, dt: f32) {
+        self.0.borrow_mut().time += dt;
+    }
+}
+
+#[test]
+fn clock_test() {
+    let clock = Clock::new();
+    let shared = clock.clone();
+    clock.pause();
+    assert!(shared.is_paused());
+    let t = shared.time();
+    assert_eq!(shared.time(), t);
+
+    clock.advance(0.5);
+    assert_eq!(shared.time(), t + 0.5);
+    assert!(clock.set_scale(-1.).is_err());
+    clock.set_scale(0.25).unwrap();
+    assert_eq!(shared.scale(), 0.25);
+    clock.resume();
+    assert!(shared.time() >= t + 0.5);
+}
@@ -0,0 +1,177 @@
+//! # Collision
+//!
+//! Solid volumes that moving particles collide with. Each `Collider`
+//! is a plane or an axis-aligned box together with the `Response` of
+//! particles hitting it. `Colliders` gathers them so the same scene
+//! geometry, such as a floor and a table, can be shared by many systems:
+//!
+//! ```ignore
+//! let scene = Colliders::new()
+//!     .with(Collider::plane(Vec3::ZERO, Vec3::Y, Response::Bounce(0.5)))
+//!     .with(Collider::aabb(vec3(-1., 0., -1.), vec3(1., 1., 1.), Response::Kill));
+//! let lp = LinearParticles::new(start, end).with_colliders(scene.clone());
+//! ```
+
+use crate::math::Vec3;
+use crate::particle::Particle;
+
+/// What happens to a particle once it hits a `Collider`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Response {
+    /// The particle is removed.
+    #[default]
+    Kill,
+    /// The particle is pushed out and its velocity reflected, keeping
+    /// the given fraction of its speed into the surface.
+    Bounce(f32),
+    /// The particle is pushed out and moves on along the surface.
+    Slide,
+}
+
+/// Solid volume particles collide with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Collider {
+    /// Everything behind the plane through `point` facing `normal`.
+    Plane {
+        point: Vec3,
+        normal: Vec3,
+        response: Response,
+    },
+    /// Everything inside the box from corner `min` to corner `max`.
+    Aabb {
+        min: Vec3,
+        max: Vec3,
+        response: Response,
+    },
+}
+
+impl Collider {
+    /// Create a new Collider filling everything behind the plane through
+    /// `point` facing `normal`.
+    pub fn plane(point: Vec3, normal: Vec3, response: Response) -> Self {
+        Collider::Plane {
+            point,
+            normal: normal.try_normalize().unwrap_or(Vec3::Y),
+            response,
+        }
+    }
+
+    /// Create a new Collider filling the box between corners `a` and `b`.
+    pub fn aabb(a: Vec3, b: Vec3, response: Response) -> Self {
+        Collider::Aabb {
+            min: a.min(b),
+            max: a.max(b),
+            response,
+        }
+    }
+
+    // outward normal of the closest surface and how deep `location` is
+    // beneath it, if inside the volume
+    fn penetration(&self, location: Vec3) -> Option<(Vec3, f32)> {
+        match *self {
+            Collider::Plane { point, normal, .. } => {
+                let depth = (point - location).dot(normal);
+                (depth > 0.).then_some((normal, depth))
+            }
+            Collider::Aabb { min, max, .. } => {
+                let inside = location.cmpgt(min).all() && location.cmplt(max).all();
+                if !inside {
+                    return None;
+                }
+                let faces = [
+                    (Vec3::NEG_X, location.x - min.x),
+                    (Vec3::X, max.x - location.x),
+                    (Vec3::NEG_Y, location.y - min.y),
+                    (Vec3::Y, max.y - location.y),
+                    (Vec3::NEG_Z, location.z - min.z),
+                    (Vec3::Z, max.z - location.z),
+                ];
+                faces.into_iter().min_by(|a, b| a.1.total_cmp(&b.1))
+            }
+        }
+    }
+
+    fn response(&self) -> Response {
+        match *self {
+            Collider::Plane { response, .. } | Collider::Aabb { response, .. } => response,
+        }
+    }
+
+    /// Collide `p` with the volume. Returns `false` if the particle
+    /// was killed, else `true`.
+    pub fn resolve(&self, p: &mut Particle) -> bool {
+        let Some((normal, depth)) = self.penetration(p.location()) else {
+            return true;
+        };
+        let into = p.velocity().dot(normal).min(0.);
+        match self.response() {
+            Response::Kill => return false,
+            Response::Bounce(restitution) => {
+                p.set_velocity(p.velocity() - normal * into * (1. + restitution));
+            }
+            Response::Slide => p.set_velocity(p.velocity() - normal * into),
+        }
+        p.translate(normal * depth);
+        true
+    }
+}
+
+/// Collection of colliders shared by particle systems.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Colliders(Vec<Collider>);
+
+impl Colliders {
+    /// Create a new empty Colliders.
+    pub fn new() -> Self {
+        Colliders(Vec::new())
+    }
+
+    /// Add collider `c`.
+    pub fn push(&mut self, c: Collider) {
+        self.0.push(c);
+    }
+
+    /// Return self (consuming it) with collider `c` added.
+    pub fn with(mut self, c: Collider) -> Self {
+        self.push(c);
+        self
+    }
+
+    /// Return the number of colliders.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return `true` if there are no colliders.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Collide `p` with every collider in turn. Returns `false` if the
+    /// particle was killed, else `true`.
+    pub fn resolve(&self, p: &mut Particle) -> bool {
+        self.0.iter().all(|c| c.resolve(p))
+    }
+}
+
+#[test]
+fn collision_response_test() {
+    let floor = Collider::plane(Vec3::ZERO, Vec3::Y, Response::Bounce(0.5));
+    let mut p = Particle::default().with_velocity(Vec3::new(1., -2., 0.));
+    p.translate(Vec3::new(0., -0.5, 0.));
+    assert!(floor.resolve(&mut p));
+    assert_eq!(p.location(), Vec3::ZERO);
+    assert_eq!(p.velocity(), Vec3::new(1., 1., 0.));
+
+    let slide = Collider::plane(Vec3::ZERO, Vec3::Y, Response::Slide);
+    let mut p = Particle::default().with_velocity(Vec3::new(1., -2., 0.));
+    p.translate(Vec3::new(0., -0.5, 0.));
+    assert!(slide.resolve(&mut p));
+    assert_eq!(p.velocity(), Vec3::X);
+
+    let scene = Colliders::new().with(Collider::aabb(Vec3::ONE, -Vec3::ONE, Response::Kill));
+    assert!(!scene.resolve(&mut Particle::default()));
+    let mut outside = Particle::default();
+    outside.translate(Vec3::splat(2.));
+    assert!(scene.resolve(&mut outside));
+}
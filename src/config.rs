@@ -0,0 +1,386 @@
+//! # Config
+//!
+//! Plain data describing systems and groups, to be stored and shared as
+//! effect files rather than written out in code. A `LinearParticlesConfig`
+//! holds the endpoints, tracks, decay, period and loop mode of a system,
+//! and a `GroupConfig` nests systems and other groups:
+//!
+//! ```ignore
+//! let config = SysConfig::Group(GroupConfig {
+//!     kind: GroupKind::Seq,
+//!     period: 2.,
+//!     mode: Some(LoopMode::Loop),
+//!     parts: vec![SysConfig::Particles(sparks), SysConfig::Particles(smoke)],
+//! });
+//! let mut effect = config.build()?;
+//! ```
+//!
+//! With the `serde` feature, every config implements `Serialize` and
+//! `Deserialize`. Fields left out of a serialized config keep their
+//! defaults.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::LinearPLError;
+use crate::groups::{CrossfadeGrp, RandomGrp, SeqGrp, StaggerGrp, SyncGrp};
+use crate::linear_particles::{LinearParticles, LinearParticlesBuilder};
+use crate::math::{Color, Vec3};
+use crate::particle_sys::{LoopMode, ParticleSysDyn};
+use crate::util::{
+    check_bursts, check_colors, check_crossfade, check_decay, check_decays, check_densities,
+    check_exclusive, check_fade, check_locations, check_offsets, check_period, check_rates,
+    check_sizes, check_warmup,
+};
+
+/// Plain data settings of a LinearParticles system. Settings left as
+/// `None` keep the defaults of `LinearParticles::new`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LinearParticlesConfig {
+    /// Starting location of the line.
+    pub start: [f32; 3],
+    /// Ending location of the line.
+    pub end: [f32; 3],
+    /// Track of spawn locations along the line, from `0` to `1`.
+    pub locations: Option<Vec<f32>>,
+    /// Track of spawn densities.
+    pub densities: Option<Vec<f32>>,
+    /// Track of spawn rates in particles per second.
+    pub rates: Option<Vec<f32>>,
+    /// Track of particle colors as `[r, g, b, a]`.
+    pub colors: Option<Vec<[f32; 4]>>,
+    /// Track of particle sizes.
+    pub sizes: Option<Vec<f32>>,
+    /// Track of particle decays.
+    pub decays: Option<Vec<f32>>,
+    /// Seconds particles take to decay.
+    pub decay: Option<f32>,
+    /// Seconds in the period of the system.
+    pub period: Option<f32>,
+    /// Bursts as times within the period and particle counts.
+    pub bursts: Option<Vec<(f32, usize)>>,
+    /// Seconds particles take to fade in.
+    pub fade_in: Option<f32>,
+    /// Seconds the system is simulated ahead when started.
+    pub warmup: Option<f32>,
+    /// Seed of the system's random spawn decisions.
+    pub seed: Option<u64>,
+    /// LoopMode the built system is started in, if any.
+    pub mode: Option<LoopMode>,
+}
+
+impl LinearParticlesConfig {
+    /// Check every setting of the config, returning an `InvalidField`
+    /// error naming the first one rejected.
+    pub fn validate(&self) -> Result<(), LinearPLError> {
+        let at = |field| move |e: LinearPLError| e.in_field(field);
+        if let Some(l) = &self.locations {
+            check_locations(l).map_err(at("locations"))?;
+        }
+        if let Some(d) = &self.densities {
+            check_densities(d).map_err(at("densities"))?;
+        }
+        if let Some(r) = &self.rates {
+            check_rates(r).map_err(at("rates"))?;
+        }
+        if let Some(c) = &self.colors {
+            let colors = c.iter().map(|&c| Color::from(c)).collect::<Vec<_>>();
+            check_colors(&colors).map_err(at("colors"))?;
+        }
+        if let Some(s) = &self.sizes {
+            check_sizes(s).map_err(at("sizes"))?;
+        }
+        if let Some(d) = &self.decays {
+            check_decays(d).map_err(at("decays"))?;
+        }
+        if let Some(d) = self.decay {
+            check_decay(d).map_err(at("decay"))?;
+        }
+        check_exclusive(
+            ("densities", self.densities.is_some()),
+            ("rates", self.rates.is_some()),
+        )
+        .map_err(at("rates"))?;
+        check_exclusive(
+            ("decay", self.decay.is_some()),
+            ("decays", self.decays.is_some()),
+        )
+        .map_err(at("decays"))?;
+        if let Some(p) = self.period {
+            check_period(p).map_err(at("period"))?;
+        }
+        if let Some(b) = &self.bursts {
+            check_bursts(b).map_err(at("bursts"))?;
+        }
+        if let Some(f) = self.fade_in {
+            check_fade(f).map_err(at("fade_in"))?;
+        }
+        if let Some(w) = self.warmup {
+            check_warmup(w).map_err(at("warmup"))?;
+        }
+        Ok(())
+    }
+
+    /// Return a LinearParticlesBuilder holding the settings of the config,
+    /// to be extended with settings the config doesn't cover.
+    pub fn builder(&self) -> LinearParticlesBuilder {
+        let mut b = LinearParticlesBuilder::new(Vec3::from(self.start), Vec3::from(self.end));
+        if let Some(l) = &self.locations {
+            b = b.locations(l);
+        }
+        if let Some(d) = &self.densities {
+            b = b.densities(d);
+        }
+        if let Some(r) = &self.rates {
+            b = b.rates(r);
+        }
+        if let Some(c) = &self.colors {
+            b = b.colors(&c.iter().map(|&c| Color::from(c)).collect::<Vec<_>>());
+        }
+        if let Some(s) = &self.sizes {
+            b = b.sizes(s);
+        }
+        if let Some(d) = &self.decays {
+            b = b.decays(d);
+        }
+        if let Some(d) = self.decay {
+            b = b.decay(d);
+        }
+        if let Some(p) = self.period {
+            b = b.period(p);
+        }
+        if let Some(bursts) = &self.bursts {
+            b = b.bursts(bursts);
+        }
+        if let Some(f) = self.fade_in {
+            b = b.fade_in(f);
+        }
+        if let Some(w) = self.warmup {
+            b = b.warmup(w);
+        }
+        if let Some(seed) = self.seed {
+            b = b.seed(seed);
+        }
+        if let Some(mode) = self.mode {
+            b = b.start_with_mode(mode);
+        }
+        b
+    }
+}
+
+/// Kind of group a GroupConfig builds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GroupKind {
+    /// A SyncGrp running all parts together.
+    #[default]
+    Sync,
+    /// A SeqGrp running the parts one after another.
+    Seq,
+    /// A RandomGrp running a randomly chosen part each cycle.
+    Random,
+    /// A StaggerGrp starting each part `offset` seconds after the last.
+    Stagger { offset: f32 },
+    /// A CrossfadeGrp blending over `crossfade` seconds between parts.
+    Crossfade { crossfade: f32 },
+}
+
+/// Plain data settings of a group and the systems within it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GroupConfig {
+    /// Kind of the group.
+    pub kind: GroupKind,
+    /// Seconds in the period of the group.
+    pub period: f32,
+    /// LoopMode the built group is started in, if any.
+    pub mode: Option<LoopMode>,
+    /// Systems and groups within the group.
+    pub parts: Vec<SysConfig>,
+}
+
+impl Default for GroupConfig {
+    fn default() -> Self {
+        GroupConfig {
+            kind: GroupKind::Sync,
+            period: 1.,
+            mode: None,
+            parts: Vec::new(),
+        }
+    }
+}
+
+impl GroupConfig {
+    /// Check every setting of the config and of the systems within it,
+    /// returning an `InvalidField` error naming the first one rejected.
+    pub fn validate(&self) -> Result<(), LinearPLError> {
+        self.check_settings()?;
+        for (i, part) in self.parts.iter().enumerate() {
+            part.validate().map_err(|e| e.in_field(&part_field(i)))?;
+        }
+        Ok(())
+    }
+
+    // check the settings of the group itself, leaving its parts
+    fn check_settings(&self) -> Result<(), LinearPLError> {
+        check_period(self.period).map_err(|e| e.in_field("period"))?;
+        match self.kind {
+            GroupKind::Stagger { offset } => {
+                check_offsets(&[offset], 1).map_err(|e| e.in_field("kind.offset"))
+            }
+            GroupKind::Crossfade { crossfade } => {
+                check_crossfade(crossfade).map_err(|e| e.in_field("kind.crossfade"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate the config and return the group it describes, with all
+    /// parts built.
+    pub fn build(&self) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+        self.check_settings()?;
+        let parts = self
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| part.build().map_err(|e| e.in_field(&part_field(i))))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut grp: Box<dyn ParticleSysDyn> = match self.kind {
+            GroupKind::Sync => Box::new(SyncGrp::new(self.period, &parts)),
+            GroupKind::Seq => Box::new(SeqGrp::new(self.period, &parts)),
+            GroupKind::Random => Box::new(RandomGrp::new(self.period, &parts)),
+            GroupKind::Stagger { offset } => {
+                Box::new(StaggerGrp::new(self.period, offset, &parts)?)
+            }
+            GroupKind::Crossfade { crossfade } => {
+                Box::new(CrossfadeGrp::new(self.period, crossfade, &parts)?)
+            }
+        };
+        if let Some(mode) = self.mode {
+            grp.start_with_mode(mode)?;
+        }
+        Ok(grp)
+    }
+}
+
+/// Plain data settings of either a single system or a group.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SysConfig {
+    /// A LinearParticles system.
+    Particles(LinearParticlesConfig),
+    /// A group of systems.
+    Group(GroupConfig),
+}
+
+// name of the field holding part `i` of a group
+fn part_field(i: usize) -> String {
+    format!("parts[{}]", i)
+}
+
+impl SysConfig {
+    /// Check every setting of the config, returning an `InvalidField`
+    /// error naming the first one rejected.
+    pub fn validate(&self) -> Result<(), LinearPLError> {
+        match self {
+            SysConfig::Particles(c) => c.validate(),
+            SysConfig::Group(c) => c.validate(),
+        }
+    }
+
+    /// Validate the config and return the system it describes.
+    pub fn build(&self) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+        Ok(match self {
+            SysConfig::Particles(c) => Box::new(LinearParticles::from_config(c)?),
+            SysConfig::Group(c) => c.build()?,
+        })
+    }
+}
+
+// swap `next` in place of the running system `current`, carrying on
+// from its elapsed time and following `clock`. A `next` left unstarted
+// is started in the way of `current`.
+#[cfg(any(feature = "ron", feature = "json", feature = "editor"))]
+pub(crate) fn replace_system(
+    current: &mut Box<dyn ParticleSysDyn>,
+    mut next: Box<dyn ParticleSysDyn>,
+    clock: &crate::clock::Clock,
+) -> Result<(), LinearPLError> {
+    if current.is_active() {
+        if !next.is_active() {
+            next.start_with_mode(match current.is_looping() {
+                true => LoopMode::Loop,
+                false => LoopMode::Once,
+            })?;
+        }
+        if let Some(t) = current.elapsed_time() {
+            next.advance_time(t);
+        }
+    }
+    next.set_clock(clock);
+    *current = next;
+    Ok(())
+}
+
+#[test]
+fn config_build_test() {
+    let sparks = LinearParticlesConfig {
+        end: [1., 0., 0.],
+        densities: Some(vec![0.5, 1.]),
+        colors: Some(vec![[1., 0.5, 0., 1.]]),
+        decay: Some(0.5),
+        period: Some(2.),
+        mode: Some(LoopMode::Loop),
+        ..Default::default()
+    };
+    let lp = LinearParticles::from_config(&sparks).unwrap();
+    let info = ParticleSysDyn::describe(&lp);
+    assert_eq!(info.period, Some(2.));
+    assert!(info.looping);
+
+    let config = SysConfig::Group(GroupConfig {
+        kind: GroupKind::Seq,
+        period: 3.,
+        mode: None,
+        parts: vec![
+            SysConfig::Particles(sparks.clone()),
+            SysConfig::Group(GroupConfig {
+                parts: vec![SysConfig::Particles(LinearParticlesConfig::default())],
+                ..Default::default()
+            }),
+        ],
+    });
+    assert!(config.build().is_ok());
+
+    let broken = LinearParticlesConfig {
+        densities: Some(vec![-1.]),
+        ..sparks
+    };
+    let e = SysConfig::Particles(broken.clone()).build().err().unwrap();
+    assert_eq!(e.root(), &LinearPLError::InvalidDensity(-1.));
+    let group = GroupConfig {
+        period: -1.,
+        ..Default::default()
+    };
+    assert!(group.build().is_err());
+
+    let nested = GroupConfig {
+        parts: vec![
+            SysConfig::Particles(LinearParticlesConfig::default()),
+            SysConfig::Group(GroupConfig {
+                parts: vec![SysConfig::Particles(LinearParticlesConfig {
+                    colors: Some(Vec::new()),
+                    ..broken
+                })],
+                ..Default::default()
+            }),
+        ],
+        ..Default::default()
+    };
+    let e = nested.validate().err().unwrap();
+    assert!(e.to_string().starts_with("parts[1].parts[0].densities: "));
+    assert_eq!(nested.build().err(), Some(e));
+}
@@ -0,0 +1,275 @@
+//! # Culling and Level of Detail
+//!
+//! Skipping systems that can't be seen. A `Culling` attached to a system
+//! tests its `Bounds`, the box around its line and live particles, against
+//! the current camera each frame, skipping the draw when the box is off
+//! screen or further than a configurable distance away:
+//!
+//! ```ignore
+//! let lp = LinearParticles::new(start, end)
+//!     .with_culling(Culling::new().with_max_distance(50.).with_simulation(false));
+//! ```
+//!
+//! Culled systems keep simulating by default, so effects are already in
+//! full swing when they come back into view.
+//!
+//! Systems that can be seen but are far away can spend less on detail
+//! no one will notice. A `Lod` scales the density and size of spawned
+//! particles down as the emitter moves from its near to its far distance
+//! from the camera given to `run_with_camera()`:
+//!
+//! ```ignore
+//! let mut lp = LinearParticles::new(start, end).with_lod(Lod::new(10., 60.))?;
+//! lp.run_with_camera(&camera)?;
+//! ```
+
+use crate::math::{Mat4, Vec3, Vec4, Vec4Swizzles};
+
+// smallest magnitude of the homogeneous coordinate of the camera position
+// recovered from a view projection, below which the camera is taken to be
+// orthographic and without a position
+const MIN_EYE_W: f32 = 1e-6;
+
+/// Axis-aligned box bounding a system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Bounds {
+    /// Create new Bounds around the corners `a` and `b`.
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Bounds {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Return the Bounds grown to include `p`.
+    pub fn including(self, p: Vec3) -> Self {
+        Bounds {
+            min: self.min.min(p),
+            max: self.max.max(p),
+        }
+    }
+
+    /// Return the Bounds grown by `margin` on every side.
+    pub fn expanded(self, margin: f32) -> Self {
+        Bounds {
+            min: self.min - Vec3::splat(margin),
+            max: self.max + Vec3::splat(margin),
+        }
+    }
+
+    /// Return the eight corners of the box.
+    pub fn corners(&self) -> [Vec3; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            Vec3::new(a.x, a.y, a.z),
+            Vec3::new(b.x, a.y, a.z),
+            Vec3::new(a.x, b.y, a.z),
+            Vec3::new(b.x, b.y, a.z),
+            Vec3::new(a.x, a.y, b.z),
+            Vec3::new(b.x, a.y, b.z),
+            Vec3::new(a.x, b.y, b.z),
+            Vec3::new(b.x, b.y, b.z),
+        ]
+    }
+
+    /// Return the Bounds around the box transformed by `m`.
+    pub fn transformed(&self, m: &Mat4) -> Self {
+        let [first, rest @ ..] = self.corners().map(|c| m.transform_point3(c));
+        rest.into_iter()
+            .fold(Bounds::new(first, first), Bounds::including)
+    }
+
+    /// Return the distance from `p` to the closest point of the box,
+    /// `0.` if inside it.
+    pub fn distance_to(&self, p: Vec3) -> f32 {
+        p.clamp(self.min, self.max).distance(p)
+    }
+
+    /// Return `true` if any part of the box may be within the view of
+    /// the camera with the combined view and projection `view_projection`.
+    /// Boxes are only reported outside when all of their corners are
+    /// beyond the same side of the view.
+    pub fn in_view(&self, view_projection: &Mat4) -> bool {
+        let clip = self
+            .corners()
+            .map(|c| *view_projection * Vec4::new(c.x, c.y, c.z, 1.));
+        let outside = |beyond: fn(&Vec4) -> bool| clip.iter().all(beyond);
+        !(outside(|c| c.x < -c.w)
+            || outside(|c| c.x > c.w)
+            || outside(|c| c.y < -c.w)
+            || outside(|c| c.y > c.w)
+            || outside(|c| c.z < -c.w)
+            || outside(|c| c.z > c.w))
+    }
+}
+
+/// Settings deciding when a system is culled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Culling {
+    /// Whether systems outside of the camera's view are culled.
+    pub frustum: bool,
+    /// Distance from the camera beyond which systems are culled.
+    /// Orthographic cameras have no position and never cull by distance.
+    pub max_distance: Option<f32>,
+    /// Distance the bounds of a system are grown by, so that particles
+    /// reaching just past them, such as the far end of a line or the
+    /// halo of a glow, aren't cut off at the edge of the screen.
+    pub margin: f32,
+    /// Whether culled systems keep spawning and aging particles. If
+    /// not, their live particles are dropped while culled.
+    pub simulate: bool,
+}
+
+impl Default for Culling {
+    fn default() -> Self {
+        Culling {
+            frustum: true,
+            max_distance: None,
+            margin: 1.,
+            simulate: true,
+        }
+    }
+}
+
+impl Culling {
+    /// Create a new Culling culling systems outside of the camera's view.
+    pub fn new() -> Self {
+        Culling::default()
+    }
+
+    /// Return self (consuming it) culling systems further than `d` from
+    /// the camera.
+    pub fn with_max_distance(mut self, d: f32) -> Self {
+        self.max_distance = Some(d);
+        self
+    }
+
+    /// Return self (consuming it) with bounds grown by `margin`.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Return self (consuming it) with culled systems simulated if
+    /// `simulate`.
+    pub fn with_simulation(mut self, simulate: bool) -> Self {
+        self.simulate = simulate;
+        self
+    }
+
+    /// Return `true` if a system within `bounds` should be drawn by the
+    /// camera with the combined view and projection `view_projection`.
+    pub fn is_visible(&self, bounds: &Bounds, view_projection: &Mat4) -> bool {
+        let bounds = bounds.expanded(self.margin);
+        if self.frustum && !bounds.in_view(view_projection) {
+            return false;
+        }
+        match (self.max_distance, camera_position(view_projection)) {
+            (Some(d), Some(eye)) => bounds.distance_to(eye) <= d,
+            _ => true,
+        }
+    }
+}
+
+/// Level of detail of a system by its distance from the camera. Closer
+/// than `near`, particles are spawned as usual. From there, density and
+/// size scale down linearly until reaching the `density` and `size`
+/// scales at `far` and beyond.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lod {
+    /// Distance up to which particles are spawned at full detail.
+    pub near: f32,
+    /// Distance from which particles are spawned at the lowest detail.
+    pub far: f32,
+    /// Scale of the density, rate and bursts at `far`, from `0` to `1`.
+    pub density: f32,
+    /// Scale of the particle sizes at `far`, from `0` to `1`.
+    pub size: f32,
+}
+
+impl Lod {
+    /// Create a new Lod scaling from full detail at `near` down to a
+    /// quarter of the density and half the size at `far`.
+    pub fn new(near: f32, far: f32) -> Self {
+        Lod {
+            near,
+            far,
+            density: 0.25,
+            size: 0.5,
+        }
+    }
+
+    /// Return self (consuming it) with density scaled to `scale` at `far`.
+    pub fn with_density(mut self, scale: f32) -> Self {
+        self.density = scale;
+        self
+    }
+
+    /// Return self (consuming it) with sizes scaled to `scale` at `far`.
+    pub fn with_size(mut self, scale: f32) -> Self {
+        self.size = scale;
+        self
+    }
+
+    /// Return the scales of density and size for an emitter `distance`
+    /// from the camera.
+    pub fn scales(&self, distance: f32) -> (f32, f32) {
+        let ratio = ((distance - self.near) / (self.far - self.near)).clamp(0., 1.);
+        (
+            1. + (self.density - 1.) * ratio,
+            1. + (self.size - 1.) * ratio,
+        )
+    }
+}
+
+// world space position of the perspective camera with the combined view
+// and projection `view_projection`, the point it maps onto the centre of
+// the view with a homogeneous coordinate of zero
+fn camera_position(view_projection: &Mat4) -> Option<Vec3> {
+    let eye = view_projection.inverse() * Vec4::Z;
+    (eye.w.abs() > MIN_EYE_W).then(|| eye.xyz() / eye.w)
+}
+
+#[test]
+fn culling_test() {
+    let eye = Vec3::new(0., 0., 10.);
+    let view = Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y);
+    let projection = Mat4::perspective_rh_gl(1., 1., 0.1, 100.);
+    let view_projection = projection * view;
+    assert!(camera_position(&view_projection).unwrap().distance(eye) < 1e-3);
+
+    let culling = Culling::new().with_margin(0.);
+    let ahead = Bounds::new(Vec3::splat(-1.), Vec3::ONE);
+    let behind = Bounds::new(Vec3::new(-1., -1., 20.), Vec3::new(1., 1., 22.));
+    let aside = Bounds::new(Vec3::new(50., 0., 0.), Vec3::new(51., 1., 1.));
+    assert!(culling.is_visible(&ahead, &view_projection));
+    assert!(!culling.is_visible(&behind, &view_projection));
+    assert!(!culling.is_visible(&aside, &view_projection));
+    assert!(culling
+        .with_margin(50.)
+        .is_visible(&aside, &view_projection));
+
+    let near = culling.with_max_distance(5.);
+    assert!(!near.is_visible(&ahead, &view_projection));
+    assert!(near
+        .with_max_distance(9.5)
+        .is_visible(&ahead, &view_projection));
+
+    let lod = Lod::new(10., 20.).with_density(0.).with_size(0.5);
+    assert_eq!(lod.scales(5.), (1., 1.));
+    assert_eq!(lod.scales(15.), (0.5, 0.75));
+    assert_eq!(lod.scales(40.), (0., 0.5));
+
+    let ortho = Mat4::orthographic_rh_gl(-1., 1., -1., 1., -100., 100.);
+    assert!(camera_position(&ortho).is_none());
+    assert!(near.is_visible(&ahead, &ortho));
+    assert_eq!(
+        ahead.transformed(&Mat4::from_scale(Vec3::splat(2.))).max,
+        Vec3::splat(2.)
+    );
+}
@@ -0,0 +1,977 @@
+//! # Curves
+//!
+//! Interpolation machinery behind the tracks of particle systems, public
+//! so that other `ParticleSys` implementations can reuse it.
+//!
+//! A `Track` holds values spread over a period, evenly or keyed at
+//! explicit normalized times, and how they are blended: an `Easing`
+//! remaps the progress between each pair of neighbouring values so that
+//! motion can speed up, slow down, overshoot or bounce, and a `TrackMode`
+//! picks between straight blends, smooth splines and held steps. `Noise`
+//! can be overlaid on top, and the values can be replaced outright by
+//! any `Interpolator`, such as a closure taking the ratio through the
+//! period:
+//!
+//! ```ignore
+//! let lp = LinearParticles::new(start, end)
+//!     .with_density_interpolator(|r: f32| (r * 20.).sin().abs())
+//!     .with_size_interpolator(Curve::step(vec![0.01, 0.05]));
+//! ```
+//!
+//! The `map_*` functions evaluate plain slices of values the same way
+//! without building a Track.
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use std::fmt;
+use std::rc::Rc;
+
+use crate::error::LinearPLError;
+use crate::math::{Color, Position, Vec3};
+
+/// Curve applied to the progress between two neighbouring track values.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    /// Constant rate of change.
+    #[default]
+    Linear,
+    /// Starts slow, quadratically.
+    QuadIn,
+    /// Ends slow, quadratically.
+    QuadOut,
+    /// Starts and ends slow, quadratically.
+    QuadInOut,
+    /// Starts slow, cubically.
+    CubicIn,
+    /// Ends slow, cubically.
+    CubicOut,
+    /// Starts and ends slow, cubically.
+    CubicInOut,
+    /// Starts slow along a sine curve.
+    SineIn,
+    /// Ends slow along a sine curve.
+    SineOut,
+    /// Starts and ends slow along a sine curve.
+    SineInOut,
+    /// Overshoots the next value and springs back to it.
+    Elastic,
+    /// Reaches the next value and bounces back from it a few times.
+    Bounce,
+    /// Cubic Bezier from `(0, 0)` to `(1, 1)` through the handles
+    /// `(x1, y1)` and `(x2, y2)`, as with CSS `cubic-bezier()`. The `x`
+    /// of each handle is clamped to `0` to `1`.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+/// What a track gives for times outside of its period, such as while
+/// particles spawned at its end are still being placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Edge {
+    /// Holds the first or last value.
+    #[default]
+    Clamp,
+    /// Carries on along the first or last segment at the same rate.
+    Extrapolate,
+    /// Starts over from the other end of the period.
+    Wrap,
+}
+
+/// How a track moves from one value to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrackMode {
+    /// Straight blend between neighbouring values.
+    #[default]
+    Linear,
+    /// Catmull-Rom spline passing through every value, without visible
+    /// corners where the direction of a sweep changes.
+    CatmullRom,
+    /// Holds each value until the next one is reached, for discrete
+    /// flips such as color changes or switching density on and off.
+    Step,
+}
+
+impl TrackMode {
+    /// Return the value progress `t` from `0` to `1` between `values[low]`
+    /// and `values[high]`, using the values either side of them to shape
+    /// smooth modes.
+    pub fn interpolate(&self, values: &[f32], low: usize, high: usize, t: f32) -> f32 {
+        let (a, b) = (values[low], values[high]);
+        match self {
+            TrackMode::Linear => (a * (1.0 - t)) + (b * t),
+            TrackMode::CatmullRom => {
+                let before = values[low.saturating_sub(1)];
+                let after = values[(high + 1).min(values.len() - 1)];
+                catmull_rom(before, a, b, after, t)
+            }
+            TrackMode::Step => a,
+        }
+    }
+}
+
+// point `t` of the way along the Catmull-Rom segment from `p1` to `p2`
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * ((2. * p1)
+        + (p2 - p0) * t
+        + (2. * p0 - 5. * p1 + 4. * p2 - p3) * t2
+        + (3. * p1 - p0 - 3. * p2 + p3) * t3)
+}
+
+/// Source of the value of a track at any point through its period.
+pub trait Interpolator<T> {
+    /// Return the value `ratio` of the way through the period, from `0`
+    /// to `1`.
+    fn value_at(&self, ratio: f32) -> T;
+}
+
+impl<T, F: Fn(f32) -> T> Interpolator<T> for F {
+    fn value_at(&self, ratio: f32) -> T {
+        self(ratio)
+    }
+}
+
+/// Evenly spaced values moved between by a TrackMode, the built-in
+/// Interpolator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve<T> {
+    pub values: Vec<T>,
+    pub mode: TrackMode,
+}
+
+impl<T> Curve<T> {
+    /// Create a new Curve moving between `values` by `mode`.
+    pub fn new(values: Vec<T>, mode: TrackMode) -> Self {
+        Curve { values, mode }
+    }
+
+    /// Create a new Curve blending linearly between `values`.
+    pub fn linear(values: Vec<T>) -> Self {
+        Curve::new(values, TrackMode::Linear)
+    }
+
+    /// Create a new Curve sweeping smoothly through `values`.
+    pub fn spline(values: Vec<T>) -> Self {
+        Curve::new(values, TrackMode::CatmullRom)
+    }
+
+    /// Create a new Curve holding each of `values` until the next.
+    pub fn step(values: Vec<T>) -> Self {
+        Curve::new(values, TrackMode::Step)
+    }
+}
+
+impl Interpolator<f32> for Curve<f32> {
+    fn value_at(&self, ratio: f32) -> f32 {
+        if self.values.is_empty() {
+            return 0.;
+        }
+        let ratio = ratio.clamp(0., 1.);
+        map_float_value(&self.values, ratio, 1., &[Easing::Linear], self.mode).unwrap_or(0.)
+    }
+}
+
+impl Interpolator<Color> for Curve<Color> {
+    fn value_at(&self, ratio: f32) -> Color {
+        if self.values.is_empty() {
+            return Color::default();
+        }
+        let ratio = ratio.clamp(0., 1.);
+        map_color_value(&self.values, ratio, 1., &[Easing::Linear], self.mode)
+            .map(|(r, g, b, a)| Color::new(r, g, b, a))
+            .unwrap_or_default()
+    }
+}
+
+/// Seeded 1D noise overlaid on a track, offsetting its value by up to
+/// `amplitude` either way and changing course `frequency` times a second.
+/// The same seed and elapsed time always give the same offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Noise {
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub seed: u32,
+}
+
+impl Noise {
+    /// Create a new Noise of `amplitude` and `frequency` with seed `0`.
+    pub fn new(amplitude: f32, frequency: f32) -> Self {
+        Noise {
+            amplitude,
+            frequency,
+            seed: 0,
+        }
+    }
+
+    /// Return self (consuming it) with seed `seed`.
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Return the offset `elapsed` seconds into the period.
+    pub fn value_at(&self, elapsed: f32) -> f32 {
+        let x = elapsed * self.frequency;
+        let i = x.floor();
+        let f = x - i;
+        let s = f * f * (3. - 2. * f);
+        let (a, b) = (
+            lattice(i as i32, self.seed),
+            lattice(i as i32 + 1, self.seed),
+        );
+        self.amplitude * (a * (1. - s) + b * s)
+    }
+}
+
+// pseudo-random value from -1 to 1 at integer point `i` of the noise
+fn lattice(i: i32, seed: u32) -> f32 {
+    let mut h = (i as u32).wrapping_mul(0x9e37_79b1) ^ seed.wrapping_mul(0x85eb_ca77);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297a_2d39);
+    h ^= h >> 15;
+    (h as f32 / u32::MAX as f32) * 2. - 1.
+}
+
+impl Easing {
+    /// Return the eased progress for progress `t` from `0` to `1`.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1. - (1. - t) * (1. - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2. * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(2) / 2.
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1. - (1. - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+            Easing::SineIn => 1. - (t * FRAC_PI_2).cos(),
+            Easing::SineOut => (t * FRAC_PI_2).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.) / 2.,
+            Easing::Elastic => {
+                if t == 0. || t == 1. {
+                    t
+                } else {
+                    2f32.powf(-10. * t) * ((t * 10. - 0.75) * TAU / 3.).sin() + 1.
+                }
+            }
+            Easing::Bounce => bounce(t),
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(x1.clamp(0., 1.), *y1, x2.clamp(0., 1.), *y2, t)
+            }
+        }
+    }
+}
+
+// one coordinate of the cubic Bezier from 0 to 1 with handles `a` and `b`
+// at parameter `u`
+fn bezier_axis(a: f32, b: f32, u: f32) -> f32 {
+    let v = 1. - u;
+    3. * v * v * u * a + 3. * v * u * u * b + u * u * u
+}
+
+// y of the cubic Bezier with handles (x1, y1) and (x2, y2) where x is `t`,
+// found by bisection as x grows steadily with the parameter
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    let (mut low, mut high) = (0., 1.);
+    let mut u = t;
+    for _ in 0..24 {
+        let x = bezier_axis(x1, x2, u);
+        if (x - t).abs() < 1e-6 {
+            break;
+        }
+        if x < t {
+            low = u;
+        } else {
+            high = u;
+        }
+        u = (low + high) / 2.;
+    }
+    bezier_axis(y1, y2, u)
+}
+
+// bounce-out curve made of four parabolic arcs of shrinking height
+fn bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[test]
+fn easing_endpoints_test() {
+    let easings = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::Elastic,
+        Easing::Bounce,
+        Easing::CubicBezier(0.42, 0., 0.58, 1.),
+    ];
+    for e in easings.iter() {
+        assert!(e.apply(0.).abs() < 1e-5, "{:?}", e);
+        assert!((e.apply(1.) - 1.).abs() < 1e-5, "{:?}", e);
+    }
+    assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+    let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.);
+    assert!(ease.apply(0.5) > 0.75);
+    let linear = Easing::CubicBezier(0., 0., 1., 1.);
+    assert!((linear.apply(0.3) - 0.3).abs() < 1e-4);
+    assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+}
+
+#[test]
+fn track_mode_test() {
+    let values = [0., 1., 1., 0.];
+    assert_eq!(TrackMode::Linear.interpolate(&values, 0, 1, 0.5), 0.5);
+    assert_eq!(TrackMode::CatmullRom.interpolate(&values, 1, 2, 0.), 1.);
+    assert_eq!(TrackMode::CatmullRom.interpolate(&values, 1, 2, 1.), 1.);
+    assert!(TrackMode::CatmullRom.interpolate(&values, 1, 2, 0.5) > 1.);
+    assert_eq!(TrackMode::Step.interpolate(&values, 0, 1, 0.99), 0.);
+}
+
+#[test]
+fn curve_interpolator_test() {
+    assert_eq!(Curve::linear(vec![0., 2.]).value_at(0.25), 0.5);
+    assert_eq!(Curve::step(vec![0., 2.]).value_at(0.75), 0.);
+    assert_eq!(Curve::spline(vec![0., 2.]).value_at(1.), 2.);
+    assert_eq!(Curve::<f32>::linear(Vec::new()).value_at(0.5), 0.);
+    let log = |r: f32| (1. + r * 9.).log10();
+    assert_eq!(log.value_at(1.), 1.);
+}
+
+#[test]
+fn noise_test() {
+    let noise = Noise::new(0.5, 4.).with_seed(7);
+    for i in 0..100 {
+        let v = noise.value_at(i as f32 * 0.013);
+        assert!(v.abs() <= 0.5);
+        assert_eq!(v, noise.value_at(i as f32 * 0.013));
+    }
+    assert_ne!(noise.value_at(0.3), noise.with_seed(8).value_at(0.3));
+}
+
+/// Value held by a Track, interpolated one channel at a time.
+pub trait TrackValue: Clone {
+    /// Number of channels of the value.
+    const CHANNELS: usize;
+
+    /// Return channel `i` of the value.
+    fn channel(&self, i: usize) -> f32;
+
+    /// Create a value from its `channels`.
+    fn from_channels(channels: &[f32]) -> Self;
+
+    /// Return the value offset by noise `n`.
+    fn add_noise(&self, n: f32) -> Self;
+}
+
+impl TrackValue for f32 {
+    const CHANNELS: usize = 1;
+
+    fn channel(&self, _i: usize) -> f32 {
+        *self
+    }
+
+    fn from_channels(channels: &[f32]) -> Self {
+        channels[0]
+    }
+
+    fn add_noise(&self, n: f32) -> Self {
+        self + n
+    }
+}
+
+/// Colors are offset by noise in brightness, leaving their opacity.
+impl TrackValue for Color {
+    const CHANNELS: usize = 4;
+
+    fn channel(&self, i: usize) -> f32 {
+        [self.r, self.g, self.b, self.a][i]
+    }
+
+    fn from_channels(channels: &[f32]) -> Self {
+        Color::new(channels[0], channels[1], channels[2], channels[3])
+    }
+
+    fn add_noise(&self, n: f32) -> Self {
+        Color::new(self.r + n, self.g + n, self.b + n, self.a)
+    }
+}
+
+impl TrackValue for Vec3 {
+    const CHANNELS: usize = 3;
+
+    fn channel(&self, i: usize) -> f32 {
+        self[i]
+    }
+
+    fn from_channels(channels: &[f32]) -> Self {
+        Vec3::new(channels[0], channels[1], channels[2])
+    }
+
+    fn add_noise(&self, n: f32) -> Self {
+        *self + Vec3::splat(n)
+    }
+}
+
+/// How a Track blends its values, whatever their type. Each segment
+/// between neighbouring values is blended along its entry in `easings`
+/// by `mode`, the last easing carrying on for any remaining segments.
+/// Any `noise` is added on top of the interpolated value. A `seamless`
+/// track blends from its last value back to its first by the end of the
+/// period, so a looping system does not snap at each loop boundary.
+/// Times outside of the period are handled according to `edge`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackOptions {
+    pub easings: Vec<Easing>,
+    pub mode: TrackMode,
+    pub noise: Option<Noise>,
+    pub seamless: bool,
+    pub edge: Edge,
+}
+
+impl Default for TrackOptions {
+    fn default() -> Self {
+        TrackOptions {
+            easings: vec![Easing::Linear],
+            mode: TrackMode::Linear,
+            noise: None,
+            seamless: false,
+            edge: Edge::Clamp,
+        }
+    }
+}
+
+/// Values spread over a period, blended according to `options`. Values
+/// are evenly spaced unless keyed at explicit normalized `times`, and
+/// are ignored altogether while a custom `interpolator` is set.
+#[derive(Clone)]
+pub struct Track<T> {
+    pub values: Vec<T>,
+    pub times: Option<Vec<f32>>,
+    pub interpolator: Option<Rc<dyn Interpolator<T>>>,
+    pub options: TrackOptions,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Track<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Track")
+            .field("values", &self.values)
+            .field("times", &self.times)
+            .field("interpolator", &self.interpolator.is_some())
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl<T: Clone + 'static> Track<T> {
+    /// Create a new linearly interpolated Track of `values`.
+    pub fn new(values: Vec<T>) -> Self {
+        Track {
+            values,
+            times: None,
+            interpolator: None,
+            options: TrackOptions::default(),
+        }
+    }
+
+    /// Replace the values of the Track with evenly spaced `values`,
+    /// keeping its options.
+    pub fn set_values(&mut self, values: &[T]) {
+        self.values = values.into();
+        self.times = None;
+        self.interpolator = None;
+    }
+
+    /// Replace the values of the Track with `keys` of normalized times
+    /// and values, keeping its options. Times should be from `0` to `1`
+    /// in increasing order.
+    pub fn set_keys(&mut self, keys: &[(f32, T)]) {
+        self.times = Some(keys.iter().map(|(t, _)| *t).collect());
+        self.values = keys.iter().map(|(_, v)| v.clone()).collect();
+        self.interpolator = None;
+    }
+
+    /// Replace the values of the Track with the custom Interpolator `i`.
+    pub fn set_interpolator(&mut self, i: Rc<dyn Interpolator<T>>) {
+        self.interpolator = Some(i);
+    }
+
+    /// Reverse the order of the values of the Track.
+    pub fn reverse(&mut self) {
+        self.values.reverse();
+        if let Some(times) = self.times.as_mut() {
+            times.reverse();
+            times.iter_mut().for_each(|t| *t = 1. - *t);
+        }
+        if self.options.easings.len() + 1 == self.values.len() {
+            self.options.easings.reverse();
+        }
+        if let Some(i) = self.interpolator.take() {
+            self.interpolator = Some(Rc::new(move |r: f32| i.value_at(1. - r)));
+        }
+    }
+}
+
+impl<T: TrackValue> Track<T> {
+    /// Return the value `elapsed` seconds into a period of `total` seconds.
+    pub fn value_at(&self, elapsed: f32, total: f32) -> Result<T, LinearPLError> {
+        let elapsed = self.edge_elapsed(elapsed, total);
+        let value = match &self.interpolator {
+            Some(i) => i.value_at((elapsed / total).clamp(0., 1.)),
+            None => {
+                let mut channels = Vec::with_capacity(T::CHANNELS);
+                for c in 0..T::CHANNELS {
+                    let values: Vec<f32> = self.values.iter().map(|v| v.channel(c)).collect();
+                    channels.push(self.channel_at(&values, elapsed, total)?);
+                }
+                T::from_channels(&channels)
+            }
+        };
+        Ok(match self.options.noise {
+            Some(n) => value.add_noise(n.value_at(elapsed)),
+            None => value,
+        })
+    }
+
+    // value of the channel of the track holding `values`
+    fn channel_at(&self, values: &[f32], elapsed: f32, total: f32) -> Result<f32, LinearPLError> {
+        let o = &self.options;
+        if o.seamless {
+            let (values, times) = self.seam_keys(values);
+            return map_float_keys(&values, &times, elapsed, total, &o.easings, o.mode);
+        }
+        let value = match &self.times {
+            Some(times) => map_float_keys(values, times, elapsed, total, &o.easings, o.mode)?,
+            None => map_float_value(values, elapsed, total, &o.easings, o.mode)?,
+        };
+        Ok(match self.overshoot(elapsed / total) {
+            Some((edge, inner, k)) => value + (values[edge] - values[inner]) * k,
+            None => value,
+        })
+    }
+
+    // `elapsed` brought back within the period if the track wraps
+    fn edge_elapsed(&self, elapsed: f32, total: f32) -> f32 {
+        if self.options.edge == Edge::Wrap && total > 0. {
+            elapsed.rem_euclid(total)
+        } else {
+            elapsed
+        }
+    }
+
+    // normalized time of key `i`
+    fn time(&self, i: usize) -> f32 {
+        match &self.times {
+            Some(times) => times[i],
+            None => i as f32 / (self.values.len() - 1) as f32,
+        }
+    }
+
+    // index of the key at the edge `ratio` lies beyond, the key next to
+    // it and how many times the segment between them `ratio` lies past
+    // the edge, if the track extrapolates
+    fn overshoot(&self, ratio: f32) -> Option<(usize, usize, f32)> {
+        let n = self.values.len();
+        if self.options.edge != Edge::Extrapolate || n < 2 {
+            return None;
+        }
+        let (first, last) = (self.time(0), self.time(n - 1));
+        if ratio > last {
+            let span = last - self.time(n - 2);
+            (span > 0.).then(|| (n - 1, n - 2, (ratio - last) / span))
+        } else if ratio < first {
+            let span = self.time(1) - first;
+            (span > 0.).then(|| (0, 1, (first - ratio) / span))
+        } else {
+            None
+        }
+    }
+
+    // channel `values` and key times of a seamless track, with the first
+    // value repeated a period after it and the last a period before it
+    // so that blending carries across the seam
+    fn seam_keys(&self, values: &[f32]) -> (Vec<f32>, Vec<f32>) {
+        let times = match &self.times {
+            Some(times) => times.clone(),
+            None => {
+                let n = values.len() as f32;
+                (0..values.len()).map(|i| i as f32 / n).collect()
+            }
+        };
+        let (Some(first), Some(last)) = (values.first(), values.last()) else {
+            return (values.into(), times);
+        };
+        let mut seam_values = vec![*last];
+        seam_values.extend(values);
+        seam_values.push(*first);
+        let mut seam_times = vec![times[times.len() - 1] - 1.];
+        seam_times.extend(times.iter());
+        seam_times.push(times[0] + 1.);
+        (seam_values, seam_times)
+    }
+}
+
+// easing of segment `i` of a track, the last easing carrying on past
+// the end of `easings`
+fn segment_easing(easings: &[Easing], i: usize) -> Easing {
+    easings
+        .get(i)
+        .or(easings.last())
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Return the value `elapsed` seconds into a period of `total` seconds
+/// of `values` evenly spaced over it, with `easings` shaping the blend
+/// of each segment between neighbouring values in turn along `mode`.
+/// Times outside of the period clamp to the first or last value.
+pub fn map_float_value(
+    values: &[f32],
+    elapsed: f32,
+    total: f32,
+    easings: &[Easing],
+    mode: TrackMode,
+) -> Result<f32, LinearPLError> {
+    if values.is_empty() {
+        return Err(LinearPLError::EmptyTrack("values"));
+    }
+    let ratio = elapsed / total;
+    let len = values.len() - 1;
+    let vratio = len as f32 * ratio;
+    let low = (vratio.floor()) as usize;
+    let high = (vratio.ceil()) as usize;
+
+    let low = if low > len { len } else { low };
+    let high = if high > len { len } else { high };
+
+    let first_value = match values.get(low) {
+        Some(val) => val,
+        None => {
+            return Err(LinearPLError::IndexOutOfBounds {
+                context: "map_float_values",
+                index: low,
+                len,
+            });
+        }
+    };
+
+    if low == high {
+        Ok(*first_value)
+    } else {
+        match values.get(high) {
+            Some(_) => {
+                let t = segment_easing(easings, low).apply(vratio - low as f32);
+                Ok(mode.interpolate(values, low, high, t))
+            }
+            None => Err(LinearPLError::IndexOutOfBounds {
+                context: "map_float_values",
+                index: high,
+                len,
+            }),
+        }
+    }
+}
+
+#[test]
+fn map_float_value_test() {
+    let values = vec![0.0, 1.0];
+    assert_eq!(
+        map_float_value(&values, 0.0, 1.0, &[Easing::Linear], TrackMode::Linear).unwrap_or(-1.0),
+        0.0
+    );
+    assert_eq!(
+        map_float_value(
+            &values,
+            2.0 / 3.0,
+            1.0,
+            &[Easing::Linear],
+            TrackMode::Linear
+        )
+        .unwrap_or(-1.0),
+        2.0 / 3.0
+    );
+    assert_eq!(
+        map_float_value(&values, 0.5, 1.0, &[Easing::QuadIn], TrackMode::Linear).unwrap_or(-1.0),
+        0.25
+    );
+
+    let values = vec![1.0, 0.0, 0.5, 0.0];
+    assert_eq!(
+        map_float_value(&values, 0.5, 1.0, &[Easing::Linear], TrackMode::Linear).unwrap_or(-1.0),
+        0.25
+    );
+
+    assert_eq!(
+        map_float_value(&[], 0.5, 1.0, &[Easing::Linear], TrackMode::Linear),
+        Err(LinearPLError::EmptyTrack("values"))
+    );
+}
+
+// interpolate each channel of `colors` progress `t` from index `low` to `high`
+fn interpolate_color(
+    colors: &[Color],
+    low: usize,
+    high: usize,
+    t: f32,
+    mode: TrackMode,
+) -> (f32, f32, f32, f32) {
+    let channel = |f: fn(&Color) -> f32| {
+        let values: Vec<f32> = colors.iter().map(f).collect();
+        mode.interpolate(&values, low, high, t)
+    };
+    (
+        channel(|c| c.r),
+        channel(|c| c.g),
+        channel(|c| c.b),
+        channel(|c| c.a),
+    )
+}
+
+/// Return the color `elapsed` seconds into a period of `total` seconds
+/// of `colors` evenly spaced over it, as with `map_float_value()`.
+pub fn map_color_value(
+    colors: &[Color],
+    elapsed: f32,
+    total: f32,
+    easings: &[Easing],
+    mode: TrackMode,
+) -> Result<(f32, f32, f32, f32), LinearPLError> {
+    if colors.is_empty() {
+        return Err(LinearPLError::EmptyTrack("colors"));
+    }
+    let ratio = elapsed / total;
+    let len = colors.len() - 1;
+    let vratio = len as f32 * ratio;
+    let low = ((len as f32 * ratio).floor()) as usize;
+    let high = ((len as f32 * ratio).ceil()) as usize;
+
+    let low = if low > len { len } else { low };
+    let high = if high > len { len } else { high };
+
+    let first_value = match colors.get(low) {
+        Some(val) => val,
+        None => {
+            return Err(LinearPLError::IndexOutOfBounds {
+                context: "map_color_value",
+                index: low,
+                len,
+            });
+        }
+    };
+
+    if low == high {
+        Ok((first_value.r, first_value.g, first_value.b, first_value.a))
+    } else {
+        match colors.get(high) {
+            Some(_) => {
+                let t = segment_easing(easings, low).apply(vratio - low as f32);
+                Ok(interpolate_color(colors, low, high, t, mode))
+            }
+            None => Err(LinearPLError::IndexOutOfBounds {
+                context: "map_color_value",
+                index: high,
+                len,
+            }),
+        }
+    }
+}
+
+#[test]
+fn map_color_value_test() {
+    let values = vec![
+        Color::new(1.0, 1.0, 1.0, 1.0),
+        Color::new(0.0, 0.0, 0.0, 1.0),
+    ];
+    assert_eq!(
+        map_color_value(&values, 0.5, 1.0, &[Easing::Linear], TrackMode::Linear).unwrap(),
+        (0.5, 0.5, 0.5, 1.0)
+    );
+    assert_eq!(
+        map_color_value(&values, 0.2, 1.0, &[Easing::Linear], TrackMode::Linear).unwrap(),
+        (0.8, 0.8, 0.8, 1.0)
+    );
+}
+
+/// Return the indices of the pair of keys surrounding the ratio
+/// `elapsed` / `total` in the increasing key `times`, along with the
+/// progress from the first to the second.
+pub fn find_keys(times: &[f32], elapsed: f32, total: f32) -> (usize, usize, f32) {
+    let ratio = elapsed / total;
+    let last = times.len().saturating_sub(1);
+    let high = times.partition_point(|t| *t <= ratio);
+    if high == 0 {
+        return (0, 0, 0.);
+    }
+    if high > last {
+        return (last, last, 0.);
+    }
+    let low = high - 1;
+    let span = times[high] - times[low];
+    let t = if span > 0. {
+        (ratio - times[low]) / span
+    } else {
+        1.
+    };
+    (low, high, t)
+}
+
+#[test]
+fn find_keys_test() {
+    let times = [0., 0.25, 1.];
+    assert_eq!(find_keys(&times, 0., 1.), (0, 1, 0.));
+    assert_eq!(find_keys(&times, 0.125, 1.), (0, 1, 0.5));
+    assert_eq!(find_keys(&times, 0.625, 1.), (1, 2, 0.5));
+    assert_eq!(find_keys(&times, 1., 1.), (2, 2, 0.));
+}
+
+/// Return the value `elapsed` seconds into a period of `total` seconds
+/// of `values` keyed at the normalized `times`, with `easings` shaping
+/// the blend of each segment between neighbouring keys in turn along `mode`.
+/// Times before the first key or after the last clamp to its value.
+pub fn map_float_keys(
+    values: &[f32],
+    times: &[f32],
+    elapsed: f32,
+    total: f32,
+    easings: &[Easing],
+    mode: TrackMode,
+) -> Result<f32, LinearPLError> {
+    let (low, high, t) = find_keys(times, elapsed, total);
+    match (values.get(low), values.get(high)) {
+        (Some(_), Some(_)) => {
+            Ok(mode.interpolate(values, low, high, segment_easing(easings, low).apply(t)))
+        }
+        _ => Err(LinearPLError::IndexOutOfBounds {
+            context: "map_float_keys",
+            index: high,
+            len: values.len(),
+        }),
+    }
+}
+
+/// Return the color `elapsed` seconds into a period of `total` seconds
+/// of `colors` keyed at the normalized `times`, as with `map_float_keys()`.
+pub fn map_color_keys(
+    colors: &[Color],
+    times: &[f32],
+    elapsed: f32,
+    total: f32,
+    easings: &[Easing],
+    mode: TrackMode,
+) -> Result<(f32, f32, f32, f32), LinearPLError> {
+    let (low, high, t) = find_keys(times, elapsed, total);
+    match (colors.get(low), colors.get(high)) {
+        (Some(_), Some(_)) => Ok(interpolate_color(
+            colors,
+            low,
+            high,
+            segment_easing(easings, low).apply(t),
+            mode,
+        )),
+        _ => Err(LinearPLError::IndexOutOfBounds {
+            context: "map_color_keys",
+            index: high,
+            len: colors.len(),
+        }),
+    }
+}
+
+#[test]
+fn map_keys_test() {
+    let values = [0.0, 1.0, 1.0];
+    let times = [0.0, 0.2, 1.0];
+    assert_eq!(
+        map_float_keys(
+            &values,
+            &times,
+            0.1,
+            1.0,
+            &[Easing::Linear],
+            TrackMode::Linear
+        ),
+        Ok(0.5)
+    );
+    assert_eq!(
+        map_float_keys(
+            &values,
+            &times,
+            0.6,
+            1.0,
+            &[Easing::Linear],
+            TrackMode::Linear
+        ),
+        Ok(1.0)
+    );
+
+    let colors = [
+        Color::new(1.0, 1.0, 1.0, 1.0),
+        Color::new(0.0, 0.0, 0.0, 1.0),
+    ];
+    assert_eq!(
+        map_color_keys(
+            &colors,
+            &[0.5, 1.0],
+            0.25,
+            1.0,
+            &[Easing::Linear],
+            TrackMode::Linear
+        ),
+        Ok((1.0, 1.0, 1.0, 1.0))
+    );
+    assert_eq!(
+        map_color_keys(
+            &colors,
+            &[0.5, 1.0],
+            0.75,
+            1.0,
+            &[Easing::Linear],
+            TrackMode::Linear
+        ),
+        Ok((0.5, 0.5, 0.5, 1.0))
+    );
+}
+
+/// Return the location `elapsed` seconds into a period of `period`
+/// seconds along the line from `start_location` to `end_location`,
+/// following the `locations` track.
+pub fn map_location<P: Position>(
+    locations: &Track<f32>,
+    start_location: P,
+    end_location: P,
+    elapsed: f32,
+    period: f32,
+) -> Result<P, LinearPLError> {
+    let ratio = locations.value_at(elapsed, period)?;
+    Ok(end_location.lerp(start_location, ratio))
+}
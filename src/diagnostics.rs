@@ -0,0 +1,198 @@
+//! # Diagnostics
+//!
+//! On-screen stats of a system or group tree. `draw_overlay()` draws, in
+//! the corner of the screen, the live and peak particle counts, how many
+//! of the systems in the tree are active, and for each system its state
+//! and particles along with the update and draw time and reallocations
+//! of its last frame:
+//!
+//! ```ignore
+//! let lp = LinearParticles::new(start, end).with_perf_counters(true);
+//! let mut scene = SyncGrp::new(4., &[lp.clone(), lp]);
+//!
+//! loop {
+//!     scene.run()?;
+//!     draw_overlay(&scene, 10., 20.);
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! Timings and reallocations are only shown for systems gathering
+//! `PerfCounters`, such as LinearParticles given `with_perf_counters(true)`;
+//! groups show the totals of the systems nested within them. Nothing is drawn
+//! with the `headless` feature enabled, and `draw_overlay()` needs the
+//! `macroquad` feature, but `overlay_text()` returns the same lines for
+//! logging.
+
+#[cfg(feature = "macroquad")]
+use macroquad::shapes::draw_rectangle;
+#[cfg(feature = "macroquad")]
+use macroquad::text::{draw_text, measure_text};
+
+#[cfg(feature = "macroquad")]
+use crate::math::{Color, WHITE};
+use crate::particle_sys::{ParticleSysDyn, PerfCounters, SysInfo};
+#[cfg(feature = "macroquad")]
+use crate::util::HEADLESS;
+
+// most systems listed, past which the rest are counted on a single line
+// so deep trees don't run off the screen
+const MAX_ROWS: usize = 24;
+
+/// Counts of a system or group tree, totalled over every system in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    /// Number of live particles.
+    pub particles: usize,
+    /// Highest number of live particles since the tree was set up.
+    pub peak_particles: usize,
+    /// Number of systems in the tree, the outermost included.
+    pub systems: usize,
+    /// Number of those systems that are active.
+    pub active_systems: usize,
+    /// PerfCounters of the last frame, if any system gathers them.
+    pub perf: Option<PerfCounters>,
+}
+
+impl Stats {
+    /// Return the Stats of `sys` and every system nested within it.
+    pub fn of(sys: &dyn ParticleSysDyn) -> Self {
+        let info = sys.describe();
+        let (systems, active_systems) = count_systems(&info);
+        Stats {
+            particles: info.particles,
+            peak_particles: sys.peak_particle_count(),
+            systems,
+            active_systems,
+            perf: info.perf,
+        }
+    }
+}
+
+// number of systems in the tree of `info`, and of those active
+fn count_systems(info: &SysInfo) -> (usize, usize) {
+    info.children
+        .iter()
+        .map(count_systems)
+        .fold((1, info.active as usize), |(n, a), (cn, ca)| {
+            (n + cn, a + ca)
+        })
+}
+
+// timings of `perf` in milliseconds along with its reallocations
+fn perf_text(perf: &PerfCounters) -> String {
+    format!(
+        "update {:.2}ms draw {:.2}ms alloc {}",
+        perf.update_secs * 1000.,
+        perf.draw_secs * 1000.,
+        perf.allocations
+    )
+}
+
+// push a line for `info` and each system nested within it onto `lines`,
+// indented by `depth`, until `rows` runs out
+fn push_rows(info: &SysInfo, depth: usize, rows: &mut usize, lines: &mut Vec<String>) {
+    if *rows == 0 {
+        return;
+    }
+    *rows -= 1;
+    let state = match (info.active, info.looping) {
+        (true, true) => "looping",
+        (true, false) => "active",
+        _ => "stopped",
+    };
+    let mut line = format!(
+        "{}{} {} {}",
+        "  ".repeat(depth),
+        info.name,
+        state,
+        info.particles
+    );
+    if let Some(perf) = &info.perf {
+        line = format!("{} {}", line, perf_text(perf));
+    }
+    lines.push(line);
+    for c in info.children.iter() {
+        push_rows(c, depth + 1, rows, lines);
+    }
+}
+
+/// Return the lines `draw_overlay()` shows for `sys`: the totals of the
+/// tree followed by one line for each system in it.
+pub fn overlay_text(sys: &dyn ParticleSysDyn) -> Vec<String> {
+    let stats = Stats::of(sys);
+    let mut lines = vec![
+        format!(
+            "particles {} (peak {})",
+            stats.particles, stats.peak_particles
+        ),
+        format!("systems {}/{} active", stats.active_systems, stats.systems),
+    ];
+    if let Some(perf) = &stats.perf {
+        lines.push(format!("frame {}", perf_text(perf)));
+    }
+    let mut rows = MAX_ROWS;
+    push_rows(&sys.describe(), 0, &mut rows, &mut lines);
+    if stats.systems > MAX_ROWS {
+        lines.push(format!("... {} more", stats.systems - MAX_ROWS));
+    }
+    lines
+}
+
+/// Draw the stats of `sys` and every system nested within it over what
+/// is already on screen, with the top left corner at `x`, `y` in screen
+/// pixels. Call after `set_default_camera()` so it is drawn in 2D.
+#[cfg(feature = "macroquad")]
+pub fn draw_overlay(sys: &dyn ParticleSysDyn, x: f32, y: f32) {
+    // font size and spacing of the lines of the overlay
+    const FONT_SIZE: u16 = 16;
+    const LINE_HEIGHT: f32 = 18.;
+    const PADDING: f32 = 6.;
+    const BACKGROUND: Color = Color::new(0., 0., 0., 0.6);
+
+    if HEADLESS {
+        return;
+    }
+    let lines = overlay_text(sys);
+    let width = lines
+        .iter()
+        .map(|l| measure_text(l, None, FONT_SIZE, 1.).width)
+        .fold(0., f32::max);
+    draw_rectangle(
+        x,
+        y,
+        width + 2. * PADDING,
+        lines.len() as f32 * LINE_HEIGHT + 2. * PADDING,
+        BACKGROUND,
+    );
+    for (i, line) in lines.iter().enumerate() {
+        let baseline = y + PADDING + (i + 1) as f32 * LINE_HEIGHT - 4.;
+        draw_text(line, x + PADDING, baseline, FONT_SIZE as f32, WHITE);
+    }
+}
+
+#[test]
+fn overlay_text_test() {
+    use crate::groups::SyncGrp;
+    use crate::linear_particles::LinearParticles;
+    use crate::math::Vec3;
+
+    let lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_bursts(&[(0., 10)])
+        .unwrap()
+        .with_perf_counters(true);
+    let mut grp = SyncGrp::new(1., &[lp.clone(), lp]);
+    grp.start().unwrap();
+    grp.advance(0.1).unwrap();
+
+    let stats = Stats::of(&grp);
+    assert_eq!((stats.systems, stats.active_systems), (3, 3));
+    assert!(stats.particles >= 20);
+    assert!(stats.perf.unwrap().allocations > 0);
+
+    let lines = overlay_text(&grp);
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[1], "systems 3/3 active");
+    assert!(lines[3].starts_with("SyncGrp active"));
+    assert!(lines[4].starts_with("  LinearParticles active"));
+}
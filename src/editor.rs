@@ -0,0 +1,294 @@
+//! # Editor
+//!
+//! Live tuning of systems with egui. An `Editor` holds the `SysConfig`
+//! of a system or group tree along with the system built from it, and
+//! shows sliders and color pickers for the period, decay, densities,
+//! colors and endpoints of every system in the tree. Each change is
+//! validated and the rebuilt system swapped in place of the running one:
+//!
+//! ```ignore
+//! let mut editor = Editor::new(config)?;
+//!
+//! loop {
+//!     editor.system().run()?;
+//!     egui_macroquad::ui(|ctx| editor.window(ctx));
+//!     egui_macroquad::draw();
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! The panel only draws into the `egui::Context` or `egui::Ui` it is
+//! given, so it works with whichever egui integration the program uses.
+//! `config()` returns the tuned settings, ready to be saved as an effect
+//! file.
+
+use egui::{CollapsingHeader, DragValue, Slider, Ui};
+
+use crate::clock::Clock;
+use crate::config::{replace_system, GroupConfig, GroupKind, LinearParticlesConfig, SysConfig};
+use crate::error::LinearPLError;
+use crate::particle_sys::ParticleSysDyn;
+
+// ranges of the sliders for seconds and for decays
+const MAX_PERIOD: f32 = 20.;
+const MAX_DECAY: f32 = 5.;
+
+// settings shown for systems leaving them unset, matching the defaults
+// of `LinearParticles::new`
+const DEFAULT_PERIOD: f32 = 1.;
+const DEFAULT_DECAY: f32 = 0.09;
+const DEFAULT_COLOR: [f32; 4] = [1., 1., 1., 1.];
+
+/// egui panel tuning a system or group tree through its `SysConfig`.
+pub struct Editor {
+    config: SysConfig,
+    sys: Box<dyn ParticleSysDyn>,
+    clock: Clock,
+    error: Option<LinearPLError>,
+}
+
+impl Editor {
+    /// Create a new Editor for the system described by `config`. Returns
+    /// an error if `config` doesn't describe a valid system.
+    pub fn new(config: SysConfig) -> Result<Self, LinearPLError> {
+        let clock = Clock::new();
+        let mut sys = config.build()?;
+        sys.set_clock(&clock);
+        Ok(Editor {
+            config,
+            sys,
+            clock,
+            error: None,
+        })
+    }
+
+    /// Return self (consuming it) with the system, and every system
+    /// rebuilt in its place, following the shared `clock`.
+    pub fn with_clock(mut self, clock: &Clock) -> Self {
+        self.clock = clock.clone();
+        self.sys.set_clock(clock);
+        self
+    }
+
+    /// Return the settings of the system as last applied.
+    pub fn config(&self) -> &SysConfig {
+        &self.config
+    }
+
+    /// Return the system built from the settings.
+    pub fn system(&mut self) -> &mut Box<dyn ParticleSysDyn> {
+        &mut self.sys
+    }
+
+    /// Return the error the last rejected change was reported with.
+    pub fn error(&self) -> Option<&LinearPLError> {
+        self.error.as_ref()
+    }
+
+    /// Rebuild the system from `config` and swap it in place of the
+    /// running one, carrying on from its elapsed time. If `config` is
+    /// invalid, the error is returned and the current system kept.
+    pub fn set_config(&mut self, config: SysConfig) -> Result<(), LinearPLError> {
+        let sys = config.build()?;
+        replace_system(&mut self.sys, sys, &self.clock)?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Show the panel in a window of its own within `ctx`.
+    pub fn window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("LinearPL").show(ctx, |ui| {
+            self.ui(ui);
+        });
+    }
+
+    /// Show the panel within `ui`, applying any change made through it.
+    /// Returns `true` if a change was applied.
+    pub fn ui(&mut self, ui: &mut Ui) -> bool {
+        let mut config = self.config.clone();
+        let changed = sys_ui(ui, &mut config, "system");
+        if let Some(e) = &self.error {
+            ui.colored_label(egui::Color32::RED, e.to_string());
+        }
+        if !changed {
+            return false;
+        }
+        match self.set_config(config) {
+            Ok(()) => {
+                self.error = None;
+                true
+            }
+            Err(e) => {
+                self.error = Some(e);
+                false
+            }
+        }
+    }
+}
+
+// controls of a system or group titled `title`, returning `true` if
+// any setting changed
+fn sys_ui(ui: &mut Ui, config: &mut SysConfig, title: &str) -> bool {
+    match config {
+        SysConfig::Particles(c) => CollapsingHeader::new(format!("{} (particles)", title))
+            .id_salt(title)
+            .show(ui, |ui| particles_ui(ui, c))
+            .body_returned
+            .unwrap_or(false),
+        SysConfig::Group(c) => CollapsingHeader::new(format!("{} ({})", title, kind_name(&c.kind)))
+            .id_salt(title)
+            .default_open(true)
+            .show(ui, |ui| group_ui(ui, c, title))
+            .body_returned
+            .unwrap_or(false),
+    }
+}
+
+fn kind_name(kind: &GroupKind) -> &'static str {
+    match kind {
+        GroupKind::Sync => "SyncGrp",
+        GroupKind::Seq => "SeqGrp",
+        GroupKind::Random => "RandomGrp",
+        GroupKind::Stagger { .. } => "StaggerGrp",
+        GroupKind::Crossfade { .. } => "CrossfadeGrp",
+    }
+}
+
+fn group_ui(ui: &mut Ui, config: &mut GroupConfig, title: &str) -> bool {
+    let mut changed = ui
+        .add(Slider::new(&mut config.period, 0. ..=MAX_PERIOD).text("period"))
+        .changed();
+    match &mut config.kind {
+        GroupKind::Stagger { offset } => {
+            changed |= ui
+                .add(Slider::new(offset, 0. ..=MAX_PERIOD).text("offset"))
+                .changed();
+        }
+        GroupKind::Crossfade { crossfade } => {
+            changed |= ui
+                .add(Slider::new(crossfade, 0. ..=MAX_PERIOD).text("crossfade"))
+                .changed();
+        }
+        _ => {}
+    }
+    for (i, part) in config.parts.iter_mut().enumerate() {
+        changed |= sys_ui(ui, part, &format!("{}.parts[{}]", title, i));
+    }
+    changed
+}
+
+fn particles_ui(ui: &mut Ui, config: &mut LinearParticlesConfig) -> bool {
+    let mut changed = false;
+    changed |= point_ui(ui, &mut config.start, "start");
+    changed |= point_ui(ui, &mut config.end, "end");
+
+    let mut period = config.period.unwrap_or(DEFAULT_PERIOD);
+    if ui
+        .add(Slider::new(&mut period, 0. ..=MAX_PERIOD).text("period"))
+        .changed()
+    {
+        config.period = Some(period);
+        changed = true;
+    }
+    let mut decay = config.decay.unwrap_or(DEFAULT_DECAY);
+    if ui
+        .add(Slider::new(&mut decay, 0. ..=MAX_DECAY).text("decay"))
+        .changed()
+    {
+        config.decay = Some(decay);
+        config.decays = None;
+        changed = true;
+    }
+
+    let mut densities = config.densities.clone().unwrap_or_else(|| vec![1.]);
+    ui.label("densities");
+    if list_ui(ui, &mut densities, 1., |ui, d| {
+        ui.add(Slider::new(d, 0. ..=1.)).changed()
+    }) {
+        config.densities = Some(densities);
+        changed = true;
+    }
+
+    let mut colors = config.colors.clone().unwrap_or_else(|| vec![DEFAULT_COLOR]);
+    ui.label("colors");
+    if list_ui(ui, &mut colors, DEFAULT_COLOR, |ui, c| {
+        ui.color_edit_button_rgba_unmultiplied(c).changed()
+    }) {
+        config.colors = Some(colors);
+        changed = true;
+    }
+    changed
+}
+
+// drag values for the coordinates of `point`
+fn point_ui(ui: &mut Ui, point: &mut [f32; 3], label: &str) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        point.iter_mut().fold(false, |changed, v| {
+            ui.add(DragValue::new(v).speed(0.01)).changed() | changed
+        })
+    })
+    .inner
+}
+
+// the values of a track in a row, each shown by `item`, along with
+// buttons adding a copy of the last value, or `new` if empty, and
+// removing the last value
+fn list_ui<T: Clone>(
+    ui: &mut Ui,
+    values: &mut Vec<T>,
+    new: T,
+    mut item: impl FnMut(&mut Ui, &mut T) -> bool,
+) -> bool {
+    ui.horizontal_wrapped(|ui| {
+        let mut changed = false;
+        for v in values.iter_mut() {
+            changed |= item(ui, v);
+        }
+        if ui.small_button("+").clicked() {
+            values.push(values.last().cloned().unwrap_or(new));
+            changed = true;
+        }
+        if values.len() > 1 && ui.small_button("-").clicked() {
+            values.pop();
+            changed = true;
+        }
+        changed
+    })
+    .inner
+}
+
+#[test]
+fn editor_test() {
+    let config = SysConfig::Group(GroupConfig {
+        kind: GroupKind::Stagger { offset: 0.25 },
+        parts: vec![
+            SysConfig::Particles(LinearParticlesConfig::default()),
+            SysConfig::Group(GroupConfig {
+                parts: vec![SysConfig::Particles(LinearParticlesConfig::default())],
+                ..Default::default()
+            }),
+        ],
+        ..Default::default()
+    });
+    let mut editor = Editor::new(config.clone()).unwrap();
+    let ctx = egui::Context::default();
+    for _ in 0..2 {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| editor.window(ctx));
+    }
+    assert_eq!(editor.config(), &config);
+
+    let mut tuned = config.clone();
+    if let SysConfig::Group(g) = &mut tuned {
+        g.period = 3.;
+    }
+    editor.set_config(tuned.clone()).unwrap();
+    assert_eq!(editor.config(), &tuned);
+
+    let mut broken = tuned.clone();
+    if let SysConfig::Group(g) = &mut broken {
+        g.period = -1.;
+    }
+    assert!(editor.set_config(broken).is_err());
+    assert_eq!(editor.config(), &tuned);
+}
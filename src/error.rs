@@ -0,0 +1,323 @@
+//! # Errors
+//!
+//! Error type returned by the fallible operations of LinearPL.
+//! Each variant corresponds to a distinct failure kind so that
+//! users can match on them rather than inspecting messages.
+//! Under `Validation::Clamp`, values that would have been rejected
+//! are clamped instead and reported as a `Diagnostic`.
+
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// Errors that can occur while configuring or running a particle system.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinearPLError {
+    /// A period was given a negative value.
+    InvalidPeriod(f32),
+    /// A decay was given a negative value.
+    InvalidDecay(f32),
+    /// A warm-up duration was given a negative value.
+    InvalidWarmup(f32),
+    /// A location interpolation value was outside of `0` to `1`.
+    InvalidLocation(f32),
+    /// A density value was outside of `0` to `1`.
+    InvalidDensity(f32),
+    /// A start offset was given a negative value.
+    InvalidOffset(f32),
+    /// A sequence weight was given a negative value.
+    InvalidWeight(f32),
+    /// A crossfade duration was given a negative value.
+    InvalidCrossfade(f32),
+    /// A particle size was given a negative value.
+    InvalidSize(f32),
+    /// A random spread was given a negative value.
+    InvalidSpread(f32),
+    /// A drag coefficient was given a negative value.
+    InvalidDrag(f32),
+    /// A fade-in duration was given a negative value.
+    InvalidFade(f32),
+    /// A glow opacity was outside of `0` to `1`.
+    InvalidGlow(f32),
+    /// A velocity stretch was given a negative value.
+    InvalidStretch(f32),
+    /// A mass was not a positive value, or a mass range was reversed.
+    InvalidMass(f32),
+    /// A keyframe time was outside of `0` to `1` or before the key preceding it.
+    InvalidKeyTime(f32),
+    /// A noise amplitude or frequency was given a negative value.
+    InvalidNoise(f32),
+    /// An emission rate was given a negative value.
+    InvalidRate(f32),
+    /// A burst was given a negative time.
+    InvalidBurst(f32),
+    /// A direction had a NaN or infinite component.
+    InvalidDirection(f32),
+    /// A level of detail distance was negative or before the near distance,
+    /// or a scale was outside of `0` to `1`.
+    InvalidLod(f32),
+    /// A simulation timestep was not a positive value.
+    InvalidTimestep(f32),
+    /// A clock was given a negative speed.
+    InvalidTimeScale(f32),
+    /// A recording was given a negative duration.
+    InvalidDuration(f32),
+    /// A part of a sequence was given zero repetitions.
+    InvalidRepeats(u32),
+    /// A list of per-child settings did not match the number of children.
+    LengthMismatch {
+        context: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// The named track was given no values.
+    EmptyTrack(&'static str),
+    /// `run()` was called before `start()` or `start_loop()`.
+    NotStarted,
+    /// An internal lookup fell outside of a collection.
+    IndexOutOfBounds {
+        context: &'static str,
+        index: usize,
+        len: usize,
+    },
+    /// A file could not be read or written.
+    Io { path: String, reason: String },
+    /// An effect document did not describe a valid system or group.
+    Parse(String),
+    /// Two settings overriding each other, such as `decay` and `decays`,
+    /// were both given.
+    ConflictingSettings(&'static str, &'static str),
+    /// The part at `index` of a LinearGrp with a shared buffer has a
+    /// `setting` that the shared buffer can't draw it with.
+    SharedBuffer { index: usize, setting: &'static str },
+    /// The setting at `field` of a config, such as
+    /// `parts[1].densities`, was rejected with `source`.
+    InvalidField {
+        field: String,
+        source: Box<LinearPLError>,
+    },
+}
+
+impl LinearPLError {
+    /// Return the error as raised by the setting `field`, prefixing the
+    /// field of an `InvalidField` error raised within it.
+    pub fn in_field(self, field: &str) -> Self {
+        match self {
+            LinearPLError::InvalidField {
+                field: inner,
+                source,
+            } => LinearPLError::InvalidField {
+                field: format!("{}.{}", field, inner),
+                source,
+            },
+            e => LinearPLError::InvalidField {
+                field: field.to_string(),
+                source: Box::new(e),
+            },
+        }
+    }
+
+    // error of the file at `path` failing to be read, created or written
+    // with `reason`
+    pub(crate) fn io(path: &Path, reason: impl fmt::Display) -> Self {
+        LinearPLError::Io {
+            path: path.display().to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Return the error underneath any `InvalidField` wrapping it.
+    pub fn root(&self) -> &LinearPLError {
+        match self {
+            LinearPLError::InvalidField { source, .. } => source.root(),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for LinearPLError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinearPLError::InvalidPeriod(p) => {
+                write!(f, "value error: {} period should be positive value", p)
+            }
+            LinearPLError::InvalidDecay(d) => {
+                write!(f, "value error: {} decay should be positive value", d)
+            }
+            LinearPLError::InvalidWarmup(w) => {
+                write!(f, "value error: {} warmup should be positive value", w)
+            }
+            LinearPLError::InvalidLocation(l) => write!(
+                f,
+                "value error: {} location interpolation should be between 0 and 1 inclusive",
+                l
+            ),
+            LinearPLError::InvalidDensity(d) => write!(
+                f,
+                "value error: {} density value should be between 0 and 1 inclusive",
+                d
+            ),
+            LinearPLError::InvalidOffset(o) => {
+                write!(f, "value error: {} offset should be positive value", o)
+            }
+            LinearPLError::InvalidWeight(w) => {
+                write!(f, "value error: {} weight should be positive value", w)
+            }
+            LinearPLError::InvalidCrossfade(c) => {
+                write!(f, "value error: {} crossfade should be positive value", c)
+            }
+            LinearPLError::InvalidSize(s) => {
+                write!(f, "value error: {} size should be positive value", s)
+            }
+            LinearPLError::InvalidSpread(s) => {
+                write!(f, "value error: {} spread should be positive value", s)
+            }
+            LinearPLError::InvalidDrag(d) => {
+                write!(f, "value error: {} drag should be positive value", d)
+            }
+            LinearPLError::InvalidFade(d) => {
+                write!(f, "value error: {} fade should be positive value", d)
+            }
+            LinearPLError::InvalidGlow(g) => write!(
+                f,
+                "value error: {} glow opacity should be between 0 and 1 inclusive",
+                g
+            ),
+            LinearPLError::InvalidStretch(s) => {
+                write!(f, "value error: {} stretch should be positive value", s)
+            }
+            LinearPLError::InvalidMass(m) => write!(
+                f,
+                "value error: {} mass should be positive value no less than the minimum",
+                m
+            ),
+            LinearPLError::InvalidKeyTime(t) => write!(
+                f,
+                "value error: {} key time should be between 0 and 1 and in increasing order",
+                t
+            ),
+            LinearPLError::InvalidNoise(n) => write!(
+                f,
+                "value error: {} noise amplitude and frequency should be positive values",
+                n
+            ),
+            LinearPLError::InvalidRate(r) => {
+                write!(f, "value error: {} rate should be positive value", r)
+            }
+            LinearPLError::InvalidBurst(t) => {
+                write!(f, "value error: {} burst time should be positive value", t)
+            }
+            LinearPLError::InvalidDirection(c) => {
+                write!(f, "value error: {} direction component should be finite", c)
+            }
+            LinearPLError::InvalidLod(v) => write!(
+                f,
+                "value error: {} level of detail distances should be increasing positive values and scales between 0 and 1",
+                v
+            ),
+            LinearPLError::InvalidTimestep(t) => {
+                write!(f, "value error: {} timestep should be positive value", t)
+            }
+            LinearPLError::InvalidTimeScale(s) => {
+                write!(f, "value error: {} time scale should be non-negative value", s)
+            }
+            LinearPLError::InvalidDuration(d) => {
+                write!(f, "value error: {} duration should be positive value", d)
+            }
+            LinearPLError::InvalidRepeats(r) => {
+                write!(f, "value error: {} repeats should be at least 1", r)
+            }
+            LinearPLError::LengthMismatch {
+                context,
+                expected,
+                found,
+            } => write!(
+                f,
+                "length error: {} expected {} values, found {}",
+                context, expected, found
+            ),
+            LinearPLError::EmptyTrack(name) => {
+                write!(f, "empty: argument '{}' cannot be empty", name)
+            }
+            LinearPLError::NotStarted => write!(f, "object has not been setup yet for running"),
+            LinearPLError::IndexOutOfBounds {
+                context,
+                index,
+                len,
+            } => write!(f, "{} indexing error: {} of {}", context, index, len),
+            LinearPLError::Io { path, reason } => {
+                write!(f, "io error: '{}' could not be accessed: {}", path, reason)
+            }
+            LinearPLError::Parse(reason) => write!(f, "parse error: {}", reason),
+            LinearPLError::ConflictingSettings(a, b) => {
+                write!(f, "conflict error: '{}' and '{}' cannot both be set", a, b)
+            }
+            LinearPLError::SharedBuffer { index, setting } => write!(
+                f,
+                "shared buffer error: part {} has a {} the shared buffer can't draw",
+                index, setting
+            ),
+            LinearPLError::InvalidField { field, source } => write!(f, "{}: {}", field, source),
+        }
+    }
+}
+
+impl Error for LinearPLError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LinearPLError::InvalidField { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// How out-of-range location and density values are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+    /// Out-of-range values are rejected with an error.
+    #[default]
+    Strict,
+    /// Out-of-range values are clamped into range, each reported as a
+    /// `Diagnostic`. NaN and infinite values are still rejected.
+    Clamp,
+}
+
+/// Value clamped into range rather than rejected under
+/// `Validation::Clamp`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Error the value would have been rejected with.
+    pub error: LinearPLError,
+    /// Value used in its place.
+    pub clamped_to: f32,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, clamped to {}", self.error, self.clamped_to)
+    }
+}
+
+#[test]
+fn linearpl_error_display_test() {
+    assert_eq!(
+        LinearPLError::InvalidPeriod(-0.5).to_string(),
+        "value error: -0.5 period should be positive value"
+    );
+    assert_eq!(
+        LinearPLError::EmptyTrack("colors").to_string(),
+        "empty: argument 'colors' cannot be empty"
+    );
+    assert_eq!(
+        LinearPLError::Parse("expected float".into()).to_string(),
+        "parse error: expected float"
+    );
+    let e = LinearPLError::InvalidDensity(2.)
+        .in_field("densities")
+        .in_field("parts[1]");
+    assert_eq!(
+        e.to_string(),
+        "parts[1].densities: value error: 2 density value should be between 0 and 1 inclusive"
+    );
+    assert_eq!(e.root(), &LinearPLError::InvalidDensity(2.));
+}
@@ -0,0 +1,170 @@
+//! # Export
+//!
+//! Writing particles out as point clouds for other tools, such as
+//! Blender, to render. A `FrameExporter` writes the location and color
+//! of every live particle of a system to a numbered file each time it
+//! is given a frame, as PLY or CSV:
+//!
+//! ```ignore
+//! let mut exporter = FrameExporter::new("frames", ExportFormat::Ply)?;
+//!
+//! lp.start()?;
+//! while lp.advance(1. / 30.)? {
+//!     exporter.write_frame(&lp)?;
+//! }
+//! ```
+//!
+//! Frames are best taken from systems stepped with `advance()`, so that
+//! they are evenly spaced in time, and built with the `headless` feature
+//! when no window is needed. Locations are exported as simulated, without
+//! the draw time transforms of a system or group.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::LinearPLError;
+use crate::particle::Particle;
+use crate::particle_sys::ParticleSys;
+
+/// File format particles are exported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// ASCII PLY with a vertex per particle, holding its location and
+    /// its color as bytes.
+    #[default]
+    Ply,
+    /// CSV with a row per particle, holding its location and its color
+    /// from `0` to `1`.
+    Csv,
+}
+
+impl ExportFormat {
+    /// Return the file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Ply => "ply",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Writer of numbered point cloud files, one per frame.
+#[derive(Debug, Clone)]
+pub struct FrameExporter {
+    dir: PathBuf,
+    format: ExportFormat,
+    frame: usize,
+}
+
+impl FrameExporter {
+    /// Create a new FrameExporter writing files in `format` into the
+    /// directory `dir`, creating it if needed.
+    pub fn new(dir: impl AsRef<Path>, format: ExportFormat) -> Result<Self, LinearPLError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| LinearPLError::io(&dir, e))?;
+        Ok(FrameExporter {
+            dir,
+            format,
+            frame: 0,
+        })
+    }
+
+    /// Return the number of frames written so far.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Write the live particles of `sys` to the file of the next frame,
+    /// such as `frame_00042.ply`, returning its path.
+    pub fn write_frame<P: ParticleSys + ?Sized>(
+        &mut self,
+        sys: &P,
+    ) -> Result<PathBuf, LinearPLError> {
+        let path = self.dir.join(format!(
+            "frame_{:05}.{}",
+            self.frame,
+            self.format.extension()
+        ));
+        let doc = match self.format {
+            ExportFormat::Ply => to_ply(sys),
+            ExportFormat::Csv => to_csv(sys),
+        };
+        fs::write(&path, doc).map_err(|e| LinearPLError::io(&path, e))?;
+        self.frame += 1;
+        Ok(path)
+    }
+}
+
+// location and faded color of every live particle of `sys`
+fn points<P: ParticleSys + ?Sized>(sys: &P) -> Vec<([f32; 3], [f32; 4])> {
+    let mut points = Vec::new();
+    sys.for_each_particle(&mut |p: &Particle| {
+        let c = p.faded_color();
+        points.push((p.location().to_array(), [c.r, c.g, c.b, c.a]));
+    });
+    points
+}
+
+/// Return the live particles of `sys` as an ASCII PLY document.
+pub fn to_ply<P: ParticleSys + ?Sized>(sys: &P) -> String {
+    let points = points(sys);
+    let mut doc = String::new();
+    let _ = write!(
+        doc,
+        "ply\nformat ascii 1.0\nelement vertex {}\n\
+         property float x\nproperty float y\nproperty float z\n\
+         property uchar red\nproperty uchar green\nproperty uchar blue\n\
+         property uchar alpha\nend_header\n",
+        points.len()
+    );
+    for ([x, y, z], c) in points {
+        let [r, g, b, a] = c.map(|v| (v.clamp(0., 1.) * 255.).round() as u8);
+        let _ = writeln!(doc, "{} {} {} {} {} {} {}", x, y, z, r, g, b, a);
+    }
+    doc
+}
+
+/// Return the live particles of `sys` as a CSV document.
+pub fn to_csv<P: ParticleSys + ?Sized>(sys: &P) -> String {
+    let mut doc = String::from("x,y,z,r,g,b,a\n");
+    for ([x, y, z], [r, g, b, a]) in points(sys) {
+        let _ = writeln!(doc, "{},{},{},{},{},{},{}", x, y, z, r, g, b, a);
+    }
+    doc
+}
+
+#[test]
+fn export_test() {
+    use crate::groups::SyncGrp;
+    use crate::linear_particles::LinearParticles;
+    use crate::math::Vec3;
+
+    let lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_colors(&[crate::math::Color::new(1., 0., 0., 1.)])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap();
+    let mut grp = SyncGrp::new(1., &[lp.clone(), lp]);
+    grp.start().unwrap();
+    for _ in 0..5 {
+        grp.advance(0.1).unwrap();
+    }
+    let count = grp.particle_count();
+    assert!(count > 0);
+
+    let ply = to_ply(&grp);
+    assert!(ply.contains(&format!("element vertex {}\n", count)));
+    assert_eq!(ply.lines().count(), 11 + count);
+    assert!(ply.lines().last().unwrap().ends_with(" 255 0 0 255"));
+    let csv = to_csv(&grp);
+    assert_eq!(csv.lines().count(), 1 + count);
+
+    let dir = std::env::temp_dir().join(format!("linearpl-export-{}", std::process::id()));
+    let mut exporter = FrameExporter::new(&dir, ExportFormat::Csv).unwrap();
+    let path = exporter.write_frame(&grp).unwrap();
+    assert!(path.ends_with("frame_00000.csv"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), csv);
+    assert_eq!(exporter.frame(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
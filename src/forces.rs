@@ -0,0 +1,156 @@
+//! # Forces
+//!
+//! Forces accelerate moving particles each frame, on top of the
+//! velocity and gravity they were spawned with, with heavier particles
+//! accelerating less. A `ForceField` groups any number of forces so the
+//! same environment, such as a breeze blowing across a scene, can be
+//! attached to many systems at once.
+//!
+//! Any closure taking a `&Particle` and returning an acceleration is
+//! a `Force` itself:
+//!
+//! ```ignore
+//! let field = ForceField::new()
+//!     .with(Wind(vec3(0.5, 0., 0.)))
+//!     .with(PointAttractor::new(Vec3::ZERO, 2.))
+//!     .with(|p: &Particle| vec3(0., p.location().x.sin(), 0.));
+//! let lp = LinearParticles::new(start, end).with_forces(field);
+//! ```
+
+use std::fmt;
+use std::rc::Rc;
+
+use crate::math::Vec3;
+use crate::particle::Particle;
+
+// closest distance used by PointAttractor, keeping the pull finite
+// for particles right on top of the point
+const MIN_DISTANCE: f32 = 0.1;
+
+/// Force applied to moving particles.
+pub trait Force {
+    /// Return the acceleration applied to `p` in units per second squared,
+    /// as if it were of unit mass. Particles accelerate by this divided
+    /// by their mass.
+    fn acceleration(&self, p: &Particle) -> Vec3;
+}
+
+impl<F: Fn(&Particle) -> Vec3> Force for F {
+    fn acceleration(&self, p: &Particle) -> Vec3 {
+        self(p)
+    }
+}
+
+/// Constant force pushing every particle in one direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wind(pub Vec3);
+
+impl Force for Wind {
+    fn acceleration(&self, _p: &Particle) -> Vec3 {
+        self.0
+    }
+}
+
+/// Pull towards `center` falling off with the square of the distance.
+/// A negative `strength` pushes particles away instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointAttractor {
+    pub center: Vec3,
+    pub strength: f32,
+}
+
+impl PointAttractor {
+    /// Create a new PointAttractor pulling towards `center` with `strength`.
+    pub fn new(center: Vec3, strength: f32) -> Self {
+        PointAttractor { center, strength }
+    }
+
+    /// Create a new PointAttractor pushing away from `center` with `strength`.
+    pub fn repulsor(center: Vec3, strength: f32) -> Self {
+        PointAttractor::new(center, -strength)
+    }
+}
+
+impl Force for PointAttractor {
+    fn acceleration(&self, p: &Particle) -> Vec3 {
+        let offset = self.center - p.location();
+        let distance = offset.length().max(MIN_DISTANCE);
+        offset.normalize_or_zero() * self.strength / (distance * distance)
+    }
+}
+
+/// Resistance slowing particles in proportion to their velocity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drag(pub f32);
+
+impl Force for Drag {
+    fn acceleration(&self, p: &Particle) -> Vec3 {
+        -p.velocity() * self.0
+    }
+}
+
+/// Collection of forces applied together. Cloning a ForceField shares
+/// its forces rather than copying them.
+#[derive(Clone, Default)]
+pub struct ForceField(Vec<Rc<dyn Force>>);
+
+impl ForceField {
+    /// Create a new empty ForceField.
+    pub fn new() -> Self {
+        ForceField(Vec::new())
+    }
+
+    /// Add force `f` to the field.
+    pub fn push(&mut self, f: impl Force + 'static) {
+        self.0.push(Rc::new(f));
+    }
+
+    /// Return self (consuming it) with force `f` added.
+    pub fn with(mut self, f: impl Force + 'static) -> Self {
+        self.push(f);
+        self
+    }
+
+    /// Return the number of forces in the field.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return `true` if the field holds no forces.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // whether `other` holds the same forces, as a clone of this field does
+    pub(crate) fn shares(&self, other: &ForceField) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| Rc::ptr_eq(a, b))
+    }
+
+    /// Return the sum of the accelerations every force applies to `p`,
+    /// before dividing by its mass.
+    pub fn acceleration(&self, p: &Particle) -> Vec3 {
+        self.0.iter().map(|f| f.acceleration(p)).sum()
+    }
+}
+
+impl fmt::Debug for ForceField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ForceField({} forces)", self.0.len())
+    }
+}
+
+#[test]
+fn force_field_test() {
+    let p = Particle::default().with_velocity(Vec3::X);
+    let field = ForceField::new()
+        .with(Wind(Vec3::Y))
+        .with(Drag(0.5))
+        .with(|_p: &Particle| Vec3::Z);
+    assert_eq!(field.len(), 3);
+    assert_eq!(field.acceleration(&p), Vec3::new(-0.5, 1., 1.));
+
+    let attractor = PointAttractor::new(Vec3::new(2., 0., 0.), 4.);
+    assert_eq!(attractor.acceleration(&p), Vec3::new(1., 0., 0.));
+    let repulsor = PointAttractor::repulsor(Vec3::new(2., 0., 0.), 4.);
+    assert_eq!(repulsor.acceleration(&p), Vec3::new(-1., 0., 0.));
+}
@@ -0,0 +1,233 @@
+//! # Gizmos
+//!
+//! Debug drawing of where systems are. `draw_debug()` draws, for a system
+//! and every system nested within it, the line particles are emitted
+//! along, the point of the line currently emitting along with the sphere
+//! its particles spread within, and the box bounding the line and live
+//! particles. Each can be turned off through the `Gizmos` given:
+//!
+//! ```ignore
+//! lp.run()?;
+//! lp.draw_debug(&Gizmos::new().with_bounds(false));
+//! ```
+//!
+//! Gizmos are drawn with the transform of the system and of the groups
+//! it is nested in, so they sit where its particles are drawn. Nothing is
+//! drawn with the `headless` feature enabled or the `macroquad` feature
+//! disabled.
+
+#[cfg(feature = "macroquad")]
+use macroquad::models::{draw_cube_wires, draw_line_3d, draw_sphere, draw_sphere_wires};
+
+use crate::culling::Bounds;
+#[cfg(feature = "macroquad")]
+use crate::math::Color;
+use crate::math::Vec3;
+#[cfg(feature = "macroquad")]
+use crate::util::HEADLESS;
+
+/// Settings deciding which gizmos `draw_debug()` draws.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gizmos {
+    /// Whether the line from the start to the end location is drawn.
+    pub path: bool,
+    /// Whether the point of the line currently emitting is drawn.
+    pub point: bool,
+    /// Whether the sphere particles spread within around the emitting
+    /// point is drawn.
+    pub spread: bool,
+    /// Whether the box bounding the line and live particles is drawn.
+    pub bounds: bool,
+}
+
+impl Default for Gizmos {
+    fn default() -> Self {
+        Gizmos {
+            path: true,
+            point: true,
+            spread: true,
+            bounds: true,
+        }
+    }
+}
+
+impl Gizmos {
+    /// Create a new Gizmos drawing every gizmo.
+    pub fn new() -> Self {
+        Gizmos::default()
+    }
+
+    /// Return self (consuming it) drawing the emitter line if `path`.
+    pub fn with_path(mut self, path: bool) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Return self (consuming it) drawing the emitting point if `point`.
+    pub fn with_point(mut self, point: bool) -> Self {
+        self.point = point;
+        self
+    }
+
+    /// Return self (consuming it) drawing the spread sphere if `spread`.
+    pub fn with_spread(mut self, spread: bool) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Return self (consuming it) drawing the bounding box if `bounds`.
+    pub fn with_bounds(mut self, bounds: bool) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    // draw the gizmos of a system emitting along `start` to `end`, from
+    // `emitting` within a sphere of radius `spread` if active
+    #[cfg(feature = "macroquad")]
+    pub(crate) fn draw(
+        &self,
+        start: Vec3,
+        end: Vec3,
+        emitting: Option<(Vec3, f32)>,
+        bounds: &Bounds,
+    ) {
+        // radius of the sphere marking the point currently emitting
+        const POINT_RADIUS: f32 = 0.03;
+
+        // colors of the emitter line, the emitting point, the spread sphere
+        // and the bounds
+        const PATH_COLOR: Color = Color::new(0.99, 0.98, 0., 1.);
+        const POINT_COLOR: Color = Color::new(0.9, 0.16, 0.22, 1.);
+        const SPREAD_COLOR: Color = Color::new(0., 1., 0.5, 0.6);
+        const BOUNDS_COLOR: Color = Color::new(0.4, 0.6, 1., 0.8);
+
+        if HEADLESS {
+            return;
+        }
+        if self.path {
+            draw_line_3d(start, end, PATH_COLOR);
+        }
+        if let Some((point, spread)) = emitting {
+            if self.point {
+                draw_sphere(point, POINT_RADIUS, None, POINT_COLOR);
+            }
+            if self.spread && spread > 0. {
+                draw_sphere_wires(point, spread, None, SPREAD_COLOR);
+            }
+        }
+        if self.bounds {
+            draw_cube_wires(
+                (bounds.min + bounds.max) / 2.,
+                bounds.max - bounds.min,
+                BOUNDS_COLOR,
+            );
+        }
+    }
+
+    // without macroquad there is nothing to draw gizmos with
+    #[cfg(not(feature = "macroquad"))]
+    pub(crate) fn draw(&self, _: Vec3, _: Vec3, _: Option<(Vec3, f32)>, _: &Bounds) {}
+}
+
+#[test]
+fn gizmos_toggle_test() {
+    assert_eq!(Gizmos::new(), Gizmos::default());
+    let gizmos = Gizmos::new().with_path(false).with_spread(false);
+    assert!(!gizmos.path && gizmos.point && !gizmos.spread && gizmos.bounds);
+    let gizmos = gizmos.with_point(false).with_bounds(false).with_path(true);
+    assert!(gizmos.path && !gizmos.point && !gizmos.spread && !gizmos.bounds);
+}
+
+// drawing, even nothing, needs a window unless headless
+#[cfg(any(feature = "headless", not(feature = "macroquad")))]
+#[test]
+fn draw_debug_test() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::slice::{Iter, IterMut};
+
+    use crate::error::LinearPLError;
+    use crate::groups::SyncGrp;
+    use crate::linear_particles::{LinearGrp, LinearParticles};
+    use crate::math::Mat4;
+    use crate::particle::Particle;
+    use crate::particle_sys::{LoopMode, ParticleSys};
+    use crate::util::current_transform;
+
+    // system recording the transform its gizmos would be drawn with
+    #[derive(Debug, Clone, Default)]
+    struct Probe(Rc<RefCell<Vec<Mat4>>>);
+
+    impl ParticleSys for Probe {
+        type T = Particle;
+
+        fn is_active(&self) -> bool {
+            true
+        }
+        fn is_looping(&self) -> bool {
+            false
+        }
+        fn is_initialized(&mut self) -> bool {
+            true
+        }
+        fn reset_time(&mut self) {}
+        fn elapsed_time(&mut self) -> Option<f32> {
+            None
+        }
+        fn set_time(&mut self, _: f32) {}
+        fn advance_time(&mut self, _: f32) {}
+        fn setup(&mut self, _: LoopMode, _: Option<f32>) -> Result<(), LinearPLError> {
+            Ok(())
+        }
+        fn tear_down(&mut self) {}
+        fn update_frame(&mut self, _: Option<f32>) -> Result<bool, LinearPLError> {
+            Ok(true)
+        }
+        fn iter(&self) -> Option<Iter<'_, Particle>> {
+            None
+        }
+        fn iter_mut(&mut self) -> Option<IterMut<'_, Particle>> {
+            None
+        }
+        fn set_period(&mut self, _: f32) -> Result<(), LinearPLError> {
+            Ok(())
+        }
+        fn draw_debug(&self, _: &Gizmos) {
+            self.0.borrow_mut().push(current_transform());
+        }
+    }
+
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_spread(0.5)
+        .unwrap()
+        .with_transform(Mat4::from_translation(Vec3::Z));
+    lp.start_at(0.).unwrap();
+    lp.run_at(0.25).unwrap();
+    for gizmos in [
+        Gizmos::new(),
+        Gizmos::new().with_path(false).with_point(false),
+        Gizmos::new().with_spread(false).with_bounds(false),
+    ] {
+        lp.draw_debug(&gizmos);
+        LinearGrp::new(1., &[lp.clone()])
+            .with_transform(Mat4::from_translation(Vec3::Y))
+            .draw_debug(&gizmos);
+    }
+    assert_eq!(current_transform(), Mat4::IDENTITY);
+
+    // the transforms of nested groups are combined around their parts
+    // and popped once they have drawn
+    let probe = Probe::default();
+    let (outer, inner) = (
+        Mat4::from_translation(Vec3::Y),
+        Mat4::from_scale(Vec3::splat(2.)),
+    );
+    let grp = SyncGrp::new(
+        1.,
+        &[SyncGrp::new(1., std::slice::from_ref(&probe)).with_transform(inner)],
+    )
+    .with_transform(outer);
+    grp.draw_debug(&Gizmos::new());
+    assert_eq!(*probe.0.borrow(), [outer * inner]);
+    assert_eq!(current_transform(), Mat4::IDENTITY);
+}
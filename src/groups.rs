@@ -6,11 +6,68 @@
 //! to review documentation for it to learn how to interact with
 //! these objects fully.
 
+use rand::Rng;
 use std::slice::{Iter, IterMut};
-use std::time::Instant;
 
-use crate::particle_sys::ParticleSys;
-use crate::util::check_period;
+use crate::callback::{Hooks, SignalCallback};
+use crate::clock::Clock;
+use crate::curves::{map_color_value, Easing, TrackMode};
+use crate::error::LinearPLError;
+use crate::gizmos::Gizmos;
+#[cfg(feature = "parallel")]
+use crate::linear_particles::PARALLEL_MIN;
+use crate::math::{Color, Mat3, Mat4, Quat, Vec3};
+use crate::modulation::{Factors, ModTarget, Modulation};
+use crate::particle_sys::{LoopMode, ParticleSys, SysInfo};
+use crate::rng::SysRng;
+use crate::time::SysTimer;
+use crate::util::{
+    begin_draw, check_colors, check_crossfade, check_offsets, check_period, check_repeats,
+    check_tags, check_weights, end_draw, pop_modulation, pop_tint, push_modulation, push_tint,
+};
+
+/// Playback state of a single part within a SyncGrp or LinearGrp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChildState {
+    /// Simulated and drawn as usual.
+    #[default]
+    Playing,
+    /// Simulated as usual but not drawn.
+    Muted,
+    /// Skipped entirely, neither simulated nor drawn.
+    Paused,
+}
+
+impl ChildState {
+    // whether a part in this state is updated, muted or not
+    pub(crate) fn is_simulated(&self) -> bool {
+        *self != ChildState::Paused
+    }
+
+    // whether a part in this state is drawn
+    pub(crate) fn is_drawn(&self) -> bool {
+        *self == ChildState::Playing
+    }
+}
+
+// age the particles `parts` left to their group with `defer_aging()`,
+// across threads once there are enough of them
+#[cfg(feature = "parallel")]
+pub(crate) fn age_parts<P: ParticleSys>(parts: &mut [P]) {
+    let count: usize = parts.iter().map(|ps| ps.particle_count()).sum();
+    if count < PARALLEL_MIN {
+        parts
+            .iter_mut()
+            .filter_map(|ps| ps.aging_job())
+            .for_each(|j| j.run());
+        return;
+    }
+    rayon::in_place_scope(|s| {
+        for job in parts.iter_mut().filter_map(|ps| ps.aging_job()) {
+            s.spawn(move |_| job.run());
+        }
+    });
+}
 
 /// Group of objects implementing ParticleSys
 /// that are synchronously ran together with a
@@ -19,10 +76,20 @@ use crate::util::check_period;
 pub struct SyncGrp<P: ParticleSys> {
     period: f32,
     parts: Vec<P>,
-    start_time: Instant,
+    states: Vec<ChildState>,
+    tags: Vec<String>,
+    timer: SysTimer,
     active: bool,
-    looping: bool,
+    mode: LoopMode,
+    cycle: u32,
     initialized: bool,
+    transform: Option<Mat4>,
+    tints: Vec<Color>,
+    modulation: Modulation,
+    modulated: Factors,
+    last_time: f32,
+    hooks: Hooks,
+    peak_count: usize,
 }
 
 impl<P: ParticleSys + std::clone::Clone> SyncGrp<P> {
@@ -31,10 +98,20 @@ impl<P: ParticleSys + std::clone::Clone> SyncGrp<P> {
         SyncGrp {
             period,
             parts: sliceparts.into(),
-            start_time: Instant::now(),
+            states: vec![ChildState::Playing; sliceparts.len()],
+            tags: vec![String::new(); sliceparts.len()],
+            timer: SysTimer::new(),
             active: false,
-            looping: false,
+            mode: LoopMode::Once,
+            cycle: 0,
             initialized: false,
+            transform: None,
+            tints: Vec::new(),
+            modulation: Modulation::default(),
+            modulated: Factors::default(),
+            last_time: 0.,
+            hooks: Hooks::default(),
+            peak_count: 0,
         }
     }
 
@@ -42,8 +119,241 @@ impl<P: ParticleSys + std::clone::Clone> SyncGrp<P> {
     /// its group of synched particle systems.
     pub fn with_systems(mut self, sliceparts: &[P]) -> Self {
         self.parts = sliceparts.into();
+        self.states = vec![ChildState::Playing; sliceparts.len()];
+        self.tags = vec![String::new(); sliceparts.len()];
+        self
+    }
+
+    /// Return self with the tags `tags`, one per part, used to change
+    /// the state of parts by name with `set_tag_state()`.
+    pub fn with_tags(mut self, tags: &[&str]) -> Result<Self, LinearPLError> {
+        check_tags(tags, self.parts.len())?;
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        Ok(self)
+    }
+
+    /// Return the playback state of the part at `index`, if any.
+    pub fn child_state(&self, index: usize) -> Option<ChildState> {
+        self.states.get(index).cloned()
+    }
+
+    /// Set the playback state of the part at `index` to `state`, muting
+    /// or pausing it while the rest of the SyncGrp keeps running.
+    pub fn set_child_state(
+        &mut self,
+        index: usize,
+        state: ChildState,
+    ) -> Result<(), LinearPLError> {
+        let len = self.states.len();
+        let s = self
+            .states
+            .get_mut(index)
+            .ok_or(LinearPLError::IndexOutOfBounds {
+                context: "SyncGrp set_child_state",
+                index,
+                len,
+            })?;
+        *s = state;
+        Ok(())
+    }
+
+    /// Set the playback state of every part tagged `tag` to `state`,
+    /// returning how many parts were changed.
+    pub fn set_tag_state(&mut self, tag: &str, state: ChildState) -> usize {
+        let mut count = 0;
+        for (s, t) in self.states.iter_mut().zip(self.tags.iter()) {
+            if t == tag {
+                *s = state;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Add `ps` to the group. If the SyncGrp is running, `ps` is set up
+    /// with the group's period and mode and joins on the next frame.
+    pub fn push(&mut self, mut ps: P) -> Result<(), LinearPLError> {
+        if self.active {
+            ps.setup(self.mode, Some(self.period))?;
+        }
+        self.parts.push(ps);
+        self.states.push(ChildState::Playing);
+        self.tags.push(String::new());
+        Ok(())
+    }
+
+    /// Remove and return the system at `index`, stopping it first.
+    pub fn remove(&mut self, index: usize) -> Result<P, LinearPLError> {
+        let len = self.parts.len();
+        if index >= len {
+            return Err(LinearPLError::IndexOutOfBounds {
+                context: "SyncGrp remove",
+                index,
+                len,
+            });
+        }
+        let mut ps = self.parts.remove(index);
+        self.states.remove(index);
+        self.tags.remove(index);
+        ps.tear_down();
+        Ok(ps)
+    }
+
+    /// Keep only the systems for which `f` returns `true`, stopping
+    /// the others.
+    pub fn retain(&mut self, mut f: impl FnMut(&P) -> bool) {
+        let keep: Vec<bool> = self.parts.iter().map(&mut f).collect();
+        for (ps, k) in self.parts.iter_mut().zip(keep.iter()) {
+            if !k {
+                ps.tear_down();
+            }
+        }
+        let mut kept = keep.iter();
+        self.parts.retain(|_| *kept.next().unwrap_or(&true));
+        let mut kept = keep.iter();
+        self.states.retain(|_| *kept.next().unwrap_or(&true));
+        let mut kept = keep.iter();
+        self.tags.retain(|_| *kept.next().unwrap_or(&true));
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the SyncGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping SyncGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    /// Set the transform `m` applied to everything drawn by the parts of
+    /// the SyncGrp, moving, rotating or scaling them together.
+    pub fn set_transform(&mut self, m: Mat4) {
+        self.transform = Some(m);
+    }
+
+    /// Return self (consuming it) with the draw time transform `m`.
+    pub fn with_transform(mut self, m: Mat4) -> Self {
+        self.set_transform(m);
+        self
+    }
+
+    /// Set the translation of the draw time transform to `t`, keeping
+    /// its rotation and scale.
+    pub fn set_translation(&mut self, t: Vec3) {
+        let (s, r, _) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    /// Set the rotation of the draw time transform to `r`, keeping its
+    /// translation and scale.
+    pub fn set_rotation(&mut self, r: Quat) {
+        let (s, _, t) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    /// Set the scale of the draw time transform to `s`, keeping its
+    /// translation and rotation.
+    pub fn set_scale(&mut self, s: Vec3) {
+        let (_, r, t) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    // scale, rotation and translation of the current transform
+    fn transform_parts(&self) -> (Vec3, Quat, Vec3) {
+        self.transform
+            .unwrap_or(Mat4::IDENTITY)
+            .to_scale_rotation_translation()
+    }
+
+    /// Set the tint track `tints` combined with the color of every
+    /// particle drawn by the parts of the SyncGrp, linearly interpolated
+    /// over its period.
+    pub fn set_tints(&mut self, tints: &[Color]) -> Result<(), LinearPLError> {
+        check_colors(tints)?;
+        self.tints = tints.into();
+        Ok(())
+    }
+
+    /// Return self (consuming it) with the tint track `tints`.
+    pub fn with_tints(mut self, tints: &[Color]) -> Result<Self, LinearPLError> {
+        self.set_tints(tints)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with the constant tint `tint`.
+    pub fn with_tint(self, tint: Color) -> Result<Self, LinearPLError> {
+        self.with_tints(&[tint])
+    }
+
+    /// Return self (consuming it) with the constant alpha multiplier
+    /// `alpha`, fading the parts without changing their colors.
+    pub fn with_alpha(self, alpha: f32) -> Result<Self, LinearPLError> {
+        self.with_tint(Color::new(1., 1., 1., alpha))
+    }
+
+    /// Scale `target` of every system within the SyncGrp by the signal
+    /// returned by `f`, sampled once each frame. See
+    /// `linearpl::modulation`.
+    pub fn set_modulation(&mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) {
+        self.modulation.set(target, SignalCallback::new(f));
+    }
+
+    /// Return self (consuming it) with `target` scaled by the signal
+    /// returned by `f`.
+    pub fn with_modulation(mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) -> Self {
+        self.set_modulation(target, f);
         self
     }
+
+    // tint to combine with the parts' colors at `current_time`, if any
+    fn tint_at(&self, current_time: f32) -> Result<Option<Color>, LinearPLError> {
+        if self.tints.is_empty() {
+            return Ok(None);
+        }
+        let (r, g, b, a) = map_color_value(
+            &self.tints,
+            current_time,
+            self.period,
+            &[Easing::Linear],
+            TrackMode::Linear,
+        )?;
+        Ok(Some(Color::new(r, g, b, a)))
+    }
+
+    // update the parts as `update_frame()` does, within the modulation
+    // of the group
+    fn update_parts(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let child_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        self.last_time = current_time;
+        for (ps, _) in self
+            .parts
+            .iter_mut()
+            .zip(self.states.iter())
+            .filter(|(_, s)| s.is_simulated())
+        {
+            #[cfg(feature = "parallel")]
+            ps.defer_aging();
+            ps.update_frame(Some(child_time))?;
+        }
+        #[cfg(feature = "parallel")]
+        age_parts(&mut self.parts);
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
 }
 
 impl<P> ParticleSys for SyncGrp<P>
@@ -57,7 +367,7 @@ where
     }
 
     fn is_looping(&self) -> bool {
-        self.active && self.looping
+        self.active && self.mode.repeats_after(self.cycle)
     }
 
     fn is_initialized(&mut self) -> bool {
@@ -65,14 +375,26 @@ where
     }
 
     fn reset_time(&mut self) {
-        self.start_time = Instant::now();
+        self.timer.reset();
     }
 
     fn elapsed_time(&mut self) -> Option<f32> {
-        Some(self.start_time.elapsed().as_secs_f32())
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
     }
 
-    fn setup(&mut self, should_loop: bool, p: Option<f32>) -> Result<(), String> {
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
         self.period = match p {
             Some(p) => {
                 check_period(p)?;
@@ -82,12 +404,15 @@ where
         };
 
         for ps in self.parts.iter_mut() {
-            ps.setup(should_loop, Some(self.period))?;
+            ps.setup(mode, Some(self.period))?;
         }
+        self.peak_count = 0;
 
-        self.looping = should_loop;
+        self.mode = mode;
+        self.cycle = 0;
         self.active = true;
         self.initialized = true;
+        self.hooks.reset();
         self.reset_time();
         Ok(())
     }
@@ -101,17 +426,12 @@ where
         self.initialized = false;
     }
 
-    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, String> {
-        let current_time = match time {
-            None => Some(self.start_time.elapsed().as_secs_f32()),
-            v => v,
-        };
-
-        for ps in self.parts.iter_mut() {
-            ps.next_frame(current_time)?;
-        }
-
-        Ok(self.start_time.elapsed().as_secs_f32() <= self.period)
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        self.modulated = self.modulation.sample();
+        push_modulation(self.modulated);
+        let updated = self.update_parts(time);
+        pop_modulation();
+        updated
     }
 
     fn iter(&self) -> Option<Iter<'_, Self::T>> {
@@ -122,10 +442,74 @@ where
         Some(self.parts.iter_mut())
     }
 
-    fn with_period(mut self, p: f32) -> Result<Self, String> {
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
         check_period(p)?;
         self.period = p;
-        Ok(self)
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+
+        self.last_time = current_time;
+        self.parts
+            .iter_mut()
+            .zip(self.states.iter())
+            .filter(|(_, s)| s.is_simulated())
+            .try_fold(false, |remaining, (ps, _)| {
+                Ok(ps.drain_frame(Some(current_time))? || remaining)
+            })
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        let tint = self.modulated.tinted(self.tint_at(self.last_time)?);
+        begin_draw(self.transform, tint);
+        let drawn = self
+            .parts
+            .iter_mut()
+            .zip(self.states.iter())
+            .filter(|(_, s)| s.is_drawn())
+            .try_for_each(|(ps, _)| ps.redraw_frame());
+        end_draw(self.transform, tint);
+        drawn
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        begin_draw(self.transform, None);
+        self.parts.iter().for_each(|ps| ps.draw_debug(gizmos));
+        end_draw(self.transform, None);
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
     }
 }
 
@@ -135,22 +519,43 @@ impl<P: ParticleSys + std::clone::Clone> Default for SyncGrp<P> {
     }
 }
 
+/// SyncGrp holding any combination of ParticleSys types, such as
+/// LinearParticles alongside a SeqGrp of them, as boxed `ParticleSysDyn`
+/// objects.
+pub type MixedGrp = SyncGrp<Box<dyn crate::particle_sys::ParticleSysDyn>>;
+
 /// Group of objects implementing ParticleSys that are
 /// ran sequentially in the order they are defined within
 /// the member `parts`, each with period equal to the SeqGrp's
 /// `period` value divided by the number of ParticleSys's in
-/// `parts`.
+/// `parts`, unless weighted otherwise with `with_weights()`.
+///
+/// Parts added with `add_repeated()` play several consecutive times,
+/// each repetition taking its own share of the period.
 #[derive(Debug, Clone)]
 pub struct SeqGrp<P: ParticleSys> {
     period: f32,
     parts: Vec<P>,
-    start_time: Instant,
+    timer: SysTimer,
     active: bool,
-    looping: bool,
+    mode: LoopMode,
+    cycle: u32,
     initialized: bool,
-    part_period: f32,
-    current_part: usize,
-    time_offset: f32,
+    weights: Vec<f32>,
+    repeats: Vec<u32>,
+    slot_parts: Vec<usize>,
+    part_periods: Vec<f32>,
+    current_slot: usize,
+    handoff: bool,
+    draining: Vec<(usize, f32)>,
+    drain_clock: f32,
+    last_time: f32,
+    transform: Option<Mat4>,
+    tints: Vec<Color>,
+    modulation: Modulation,
+    modulated: Factors,
+    hooks: Hooks,
+    peak_count: usize,
 }
 
 impl<P> SeqGrp<P>
@@ -160,27 +565,377 @@ where
     /// Return's a new SeqGrp with `sliceparts` as its
     /// sequence of ParticleSys objects.
     pub fn new(period: f32, sliceparts: &[P]) -> Self {
-        let part_period = period / sliceparts.len() as f32;
-        SeqGrp {
+        let mut grp = SeqGrp {
             period,
             parts: sliceparts.into(),
-            start_time: Instant::now(),
+            timer: SysTimer::new(),
             active: false,
-            looping: false,
+            mode: LoopMode::Once,
+            cycle: 0,
             initialized: false,
-            part_period,
-            current_part: 0,
-            time_offset: 0.,
-        }
+            weights: vec![1.; sliceparts.len()],
+            repeats: vec![1; sliceparts.len()],
+            slot_parts: Vec::new(),
+            part_periods: Vec::new(),
+            current_slot: 0,
+            handoff: false,
+            draining: Vec::new(),
+            drain_clock: 0.,
+            last_time: 0.,
+            transform: None,
+            tints: Vec::new(),
+            modulation: Modulation::default(),
+            modulated: Factors::default(),
+            hooks: Hooks::default(),
+            peak_count: 0,
+        };
+        grp.update_part_periods();
+        grp
     }
 
     /// Return self with ParticleSys obj's `sliceparts` as
-    /// its group of sequential particle systems.
+    /// its group of sequential particle systems. Any weights
+    /// or repeat counts previously set are reset.
     pub fn with_systems(mut self, sliceparts: &[P]) -> Self {
         self.parts = sliceparts.into();
-        self.part_period = self.period / self.parts.len() as f32;
+        self.weights = vec![1.; self.parts.len()];
+        self.repeats = vec![1; self.parts.len()];
+        self.update_part_periods();
+        self
+    }
+
+    /// Append `ps` to the end of the sequence, playing `repeats`
+    /// consecutive times with weight `1` for each repetition. Returns
+    /// an error if `repeats` is `0`.
+    pub fn add_repeated(&mut self, ps: P, repeats: u32) -> Result<(), LinearPLError> {
+        check_repeats(repeats)?;
+        self.parts.push(ps);
+        self.weights.push(1.);
+        self.repeats.push(repeats);
+        self.update_part_periods();
+        Ok(())
+    }
+
+    /// Return self with each part playing for a share of the period
+    /// proportional to its weight in `weights`, one per part. A part
+    /// that repeats takes this share for each of its repetitions.
+    pub fn with_weights(mut self, weights: &[f32]) -> Result<Self, LinearPLError> {
+        check_weights(weights, self.parts.len())?;
+        self.weights = weights.into();
+        self.update_part_periods();
+        Ok(self)
+    }
+
+    /// Return self with each part playing for exactly the number of
+    /// seconds given in `durations`, one per part. The period of the
+    /// SeqGrp becomes the total duration of the sequence.
+    pub fn with_durations(mut self, durations: &[f32]) -> Result<Self, LinearPLError> {
+        if durations.len() != self.parts.len() {
+            return Err(LinearPLError::LengthMismatch {
+                context: "durations",
+                expected: self.parts.len(),
+                found: durations.len(),
+            });
+        }
+        for d in durations.iter() {
+            check_period(*d)?;
+        }
+
+        let period = durations
+            .iter()
+            .zip(self.repeats.iter())
+            .map(|(d, r)| d * *r as f32)
+            .sum();
+        check_period(period)?;
+        self.period = period;
+        self.weights = durations.into();
+        self.update_part_periods();
+        Ok(self)
+    }
+
+    /// Return the index within `parts` of the part currently playing.
+    pub fn current_child_index(&self) -> usize {
+        self.slot_parts.get(self.current_slot).cloned().unwrap_or(0)
+    }
+
+    /// Return how far through its playing time part `i` was as of the
+    /// last frame, from `0` before it has started to `1` once finished,
+    /// or `None` if there is no part `i`. A repeated part reports its
+    /// progress through all of its repetitions.
+    pub fn child_progress(&self, i: usize) -> Option<f32> {
+        if i >= self.parts.len() {
+            return None;
+        }
+        let seq_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - self.last_time,
+            false => self.last_time,
+        };
+
+        let mut start = 0.;
+        let mut duration = 0.;
+        for (slot, part) in self.slot_parts.iter().enumerate() {
+            let pp = self.part_periods.get(slot).cloned().unwrap_or(0.);
+            if *part == i {
+                duration += pp;
+            } else if duration == 0. {
+                start += pp;
+            }
+        }
+        match duration > 0. {
+            true => Some(((seq_time - start) / duration).clamp(0., 1.)),
+            false => Some(match seq_time >= start {
+                true => 1.,
+                false => 0.,
+            }),
+        }
+    }
+
+    /// Return self with seamless handoff set to `handoff`. When set,
+    /// a part that has finished keeps drawing its remaining particles
+    /// until they decay rather than being cleared as the next part
+    /// begins.
+    pub fn with_handoff(mut self, handoff: bool) -> Self {
+        self.handoff = handoff;
+        self
+    }
+
+    // recompute the slots of the sequence, one per repetition of each
+    // part, and each slot's period from the group period and weights
+    fn update_part_periods(&mut self) {
+        self.slot_parts = self
+            .repeats
+            .iter()
+            .enumerate()
+            .flat_map(|(i, r)| std::iter::repeat_n(i, *r as usize))
+            .collect();
+
+        let total: f32 = self.slot_parts.iter().map(|i| self.weights[*i]).sum();
+        let period = self.period;
+        self.part_periods = self
+            .slot_parts
+            .iter()
+            .map(|i| match total > 0. {
+                true => period * self.weights[*i] / total,
+                false => 0.,
+            })
+            .collect();
+    }
+
+    // advance part `index` to `part_time` along with any outgoing parts
+    // still draining
+    fn play_part(&mut self, index: usize, part_time: f32) -> Result<(), LinearPLError> {
+        let len = self.parts.len();
+        let p = self
+            .parts
+            .get_mut(index)
+            .ok_or(LinearPLError::IndexOutOfBounds {
+                context: "SeqGrp update_frame",
+                index,
+                len,
+            })?;
+        p.update_frame(Some(part_time))?;
+
+        // outgoing parts age what is left of their particles until
+        // it has decayed, then are torn down
+        let mut draining = std::mem::take(&mut self.draining);
+        for (i, drain_origin) in draining.iter() {
+            if let Some(ps) = self.parts.get_mut(*i) {
+                if !ps.drain_frame(Some(self.drain_clock - drain_origin))? {
+                    ps.tear_down();
+                }
+            }
+        }
+        draining.retain(|(i, _)| self.parts.get(*i).is_some_and(|ps| ps.is_active()));
+        self.draining = draining;
+        Ok(())
+    }
+
+    // index of the slot playing at time `t` and the time that slot starts
+    fn part_at(&self, t: f32) -> (usize, f32) {
+        let mut start = 0.;
+        for (i, pp) in self.part_periods.iter().enumerate() {
+            if t < start + pp || i + 1 == self.part_periods.len() {
+                return (i, start);
+            }
+            start += pp;
+        }
+        (0, 0.)
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the SeqGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping SeqGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    /// Set the transform `m` applied to everything drawn by the parts of
+    /// the SeqGrp, moving, rotating or scaling them together.
+    pub fn set_transform(&mut self, m: Mat4) {
+        self.transform = Some(m);
+    }
+
+    /// Return self (consuming it) with the draw time transform `m`.
+    pub fn with_transform(mut self, m: Mat4) -> Self {
+        self.set_transform(m);
+        self
+    }
+
+    /// Set the translation of the draw time transform to `t`, keeping
+    /// its rotation and scale.
+    pub fn set_translation(&mut self, t: Vec3) {
+        let (s, r, _) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    /// Set the rotation of the draw time transform to `r`, keeping its
+    /// translation and scale.
+    pub fn set_rotation(&mut self, r: Quat) {
+        let (s, _, t) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    /// Set the scale of the draw time transform to `s`, keeping its
+    /// translation and rotation.
+    pub fn set_scale(&mut self, s: Vec3) {
+        let (_, r, t) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    // scale, rotation and translation of the current transform
+    fn transform_parts(&self) -> (Vec3, Quat, Vec3) {
+        self.transform
+            .unwrap_or(Mat4::IDENTITY)
+            .to_scale_rotation_translation()
+    }
+
+    /// Set the tint track `tints` combined with the color of every
+    /// particle drawn by the parts of the SeqGrp, linearly interpolated
+    /// over its period.
+    pub fn set_tints(&mut self, tints: &[Color]) -> Result<(), LinearPLError> {
+        check_colors(tints)?;
+        self.tints = tints.into();
+        Ok(())
+    }
+
+    /// Return self (consuming it) with the tint track `tints`.
+    pub fn with_tints(mut self, tints: &[Color]) -> Result<Self, LinearPLError> {
+        self.set_tints(tints)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with the constant tint `tint`.
+    pub fn with_tint(self, tint: Color) -> Result<Self, LinearPLError> {
+        self.with_tints(&[tint])
+    }
+
+    /// Return self (consuming it) with the constant alpha multiplier
+    /// `alpha`, fading the parts without changing their colors.
+    pub fn with_alpha(self, alpha: f32) -> Result<Self, LinearPLError> {
+        self.with_tint(Color::new(1., 1., 1., alpha))
+    }
+
+    /// Scale `target` of every system within the SeqGrp by the signal
+    /// returned by `f`, sampled once each frame. See
+    /// `linearpl::modulation`.
+    pub fn set_modulation(&mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) {
+        self.modulation.set(target, SignalCallback::new(f));
+    }
+
+    /// Return self (consuming it) with `target` scaled by the signal
+    /// returned by `f`.
+    pub fn with_modulation(mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) -> Self {
+        self.set_modulation(target, f);
         self
     }
+
+    // tint to combine with the parts' colors at `current_time`, if any
+    fn tint_at(&self, current_time: f32) -> Result<Option<Color>, LinearPLError> {
+        if self.tints.is_empty() {
+            return Ok(None);
+        }
+        let (r, g, b, a) = map_color_value(
+            &self.tints,
+            current_time,
+            self.period,
+            &[Easing::Linear],
+            TrackMode::Linear,
+        )?;
+        Ok(Some(Color::new(r, g, b, a)))
+    }
+
+    // update the parts as `update_frame()` does, within the modulation
+    // of the group
+    fn update_parts(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let seq_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        // draining parts age on a monotonic clock so they keep decaying
+        // across loop resets of `current_time`
+        self.drain_clock += match current_time >= self.last_time {
+            true => current_time - self.last_time,
+            false => current_time,
+        };
+        self.last_time = current_time;
+
+        // the part playing is derived from the time alone so that the
+        // sequence can be driven backwards as well as forwards
+        let len = self.parts.len();
+        let (slot, start) = self.part_at(seq_time);
+        let part_period = self.part_periods.get(slot).cloned().unwrap_or(0.);
+        let index = self.slot_parts.get(slot).cloned().unwrap_or(0);
+        let prev_index = self.slot_parts.get(self.current_slot).cloned().unwrap_or(0);
+        if slot != self.current_slot {
+            if self.handoff {
+                // the outgoing part left off at its end, or at its
+                // start when traversed in reverse
+                let prev_end = match self.mode.is_reversed(self.cycle) {
+                    true => 0.,
+                    false => self
+                        .part_periods
+                        .get(self.current_slot)
+                        .cloned()
+                        .unwrap_or(0.),
+                };
+                self.draining
+                    .push((prev_index, self.drain_clock - prev_end));
+            } else if let Some(prev) = self.parts.get_mut(prev_index) {
+                prev.tear_down();
+            }
+            self.draining.retain(|(i, _)| *i != index);
+
+            let p = self
+                .parts
+                .get_mut(index)
+                .ok_or(LinearPLError::IndexOutOfBounds {
+                    context: "SeqGrp update_frame-setup",
+                    index,
+                    len,
+                })?;
+            p.tear_down();
+            p.setup(self.mode, Some(part_period))?;
+            self.current_slot = slot;
+        }
+
+        self.play_part(index, seq_time - start)?;
+
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
 }
 
 impl<P> ParticleSys for SeqGrp<P>
@@ -194,7 +949,7 @@ where
     }
 
     fn is_looping(&self) -> bool {
-        self.active && self.looping
+        self.active && self.mode.repeats_after(self.cycle)
     }
 
     fn is_initialized(&mut self) -> bool {
@@ -202,33 +957,57 @@ where
     }
 
     fn reset_time(&mut self) {
-        self.start_time = Instant::now();
+        self.timer.reset();
     }
 
     fn elapsed_time(&mut self) -> Option<f32> {
-        Some(self.start_time.elapsed().as_secs_f32())
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
     }
 
-    fn setup(&mut self, should_loop: bool, p: Option<f32>) -> Result<(), String> {
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
         self.period = match p {
             Some(p) => {
                 check_period(p)?;
-                self.part_period = p / self.parts.len() as f32;
                 p
             }
             None => self.period,
         };
+        self.update_part_periods();
+        let first_period = self.part_periods.first().cloned().unwrap_or(0.);
+        let first_part = self.slot_parts.first().cloned().unwrap_or(0);
 
+        let len = self.parts.len();
         self.parts
-            .get_mut(0)
-            .ok_or("indexing out of bounds for SeqGrp part in setup: 0")?
-            .setup(should_loop, Some(self.part_period))?;
+            .get_mut(first_part)
+            .ok_or(LinearPLError::IndexOutOfBounds {
+                context: "SeqGrp setup",
+                index: first_part,
+                len,
+            })?
+            .setup(mode, Some(first_period))?;
 
-        self.current_part = 0;
-        self.time_offset = 0.;
-        self.looping = should_loop;
+        self.current_slot = 0;
+        self.draining.clear();
+        self.drain_clock = 0.;
+        self.last_time = 0.;
+        self.peak_count = 0;
+        self.mode = mode;
+        self.cycle = 0;
         self.active = true;
         self.initialized = true;
+        self.hooks.reset();
         self.reset_time();
         Ok(())
     }
@@ -238,47 +1017,18 @@ where
             ps.tear_down();
         }
 
-        self.current_part = 0;
-        self.time_offset = 0.;
+        self.current_slot = 0;
+        self.draining.clear();
         self.active = false;
         self.initialized = false;
     }
 
-    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, String> {
-        let current_time = match time {
-            None => Some(self.start_time.elapsed().as_secs_f32()),
-            Some(v) => Some(v - self.time_offset),
-        };
-
-        let p = self.parts.get_mut(self.current_part).ok_or(format!(
-            "indexing out of bounds for SeqGrp part in next_frame: {}",
-            self.current_part
-        ))?;
-
-        if !p.next_frame(current_time)? {
-            self.current_part += 1;
-            self.time_offset += self.part_period;
-            if self.current_part == self.parts.len() {
-                match self.looping {
-                    true => {
-                        self.current_part = 0;
-                        self.time_offset = 0.;
-                        self.reset_time();
-                    }
-                    false => {
-                        return Ok(false);
-                    }
-                }
-            }
-            let p = self.parts.get_mut(self.current_part).ok_or(format!(
-                "indexing out of bounds for SeqGrp part in next_frame-setup: {}",
-                self.current_part
-            ))?;
-            p.tear_down();
-            p.setup(self.looping, Some(self.part_period))?;
-        }
-
-        Ok(true)
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        self.modulated = self.modulation.sample();
+        push_modulation(self.modulated);
+        let updated = self.update_parts(time);
+        pop_modulation();
+        updated
     }
 
     fn iter(&self) -> Option<Iter<'_, Self::T>> {
@@ -289,10 +1039,2150 @@ where
         Some(self.parts.iter_mut())
     }
 
-    fn with_period(mut self, p: f32) -> Result<Self, String> {
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
         check_period(p)?;
         self.period = p;
-        self.part_period = p / self.parts.len() as f32;
-        Ok(self)
+        self.update_part_periods();
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        let tint = self.modulated.tinted(self.tint_at(self.last_time)?);
+        begin_draw(self.transform, tint);
+        let drawn = self.parts.iter_mut().try_for_each(|ps| ps.redraw_frame());
+        end_draw(self.transform, tint);
+        drawn
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        begin_draw(self.transform, None);
+        self.parts.iter().for_each(|ps| ps.draw_debug(gizmos));
+        end_draw(self.transform, None);
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
     }
 }
+
+#[test]
+fn sync_grp_push_remove_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = SyncGrp::new(1., &[LinearParticles::default()]);
+    grp.start_loop().unwrap();
+    grp.push(LinearParticles::default()).unwrap();
+    assert!(grp.iter().unwrap().all(|ps| ps.is_active()));
+
+    let removed = grp.remove(0).unwrap();
+    assert!(!removed.is_active());
+    assert!(grp.remove(1).is_err());
+
+    grp.retain(|_| false);
+    assert_eq!(grp.iter().unwrap().count(), 0);
+}
+
+#[test]
+fn update_without_draw_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap();
+    let mut grp = SyncGrp::new(1., &[lp]);
+    assert!(matches!(grp.update(), Err(LinearPLError::NotStarted)));
+
+    grp.start_at(0.).unwrap();
+    grp.set_time(0.5);
+    assert!(grp.update().unwrap());
+    let count = grp.particle_count();
+    assert!(count > 0);
+    // without a new clock reading updating again spawns nothing more
+    assert!(grp.update().unwrap());
+    assert_eq!(grp.particle_count(), count);
+
+    grp.set_time(2.);
+    assert!(!grp.update().unwrap());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn headless_run_test() {
+    use crate::culling::Culling;
+    use crate::linear_particles::LinearParticles;
+
+    let lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap()
+        .with_culling(Culling::new());
+    let mut grp = SyncGrp::new(1., &[lp]).with_transform(Mat4::from_translation(Vec3::Y));
+    grp.start_at(0.).unwrap();
+    assert!(grp.run_at(0.5).unwrap());
+    let lp = grp.iter().unwrap().next().unwrap();
+    assert!(lp.iter().unwrap().all(|p| p.location().y == 0.));
+    assert!(lp.particle_count() > 0);
+}
+
+#[test]
+fn shared_clock_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let clock = Clock::new();
+    clock.pause();
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap()
+        .with_clock(&clock);
+    let mut grp = SyncGrp::new(1., &[lp.clone()]).with_clock(&clock);
+    lp.start().unwrap();
+    grp.start().unwrap();
+
+    assert!(lp.update().unwrap() && grp.update().unwrap());
+    assert_eq!(lp.particle_count() + grp.particle_count(), 0);
+
+    clock.advance(0.5);
+    lp.update().unwrap();
+    grp.update().unwrap();
+    assert!(lp.particle_count() > 0);
+    assert_eq!(grp.particle_count(), lp.particle_count());
+}
+
+#[test]
+fn sync_grp_child_state_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = SyncGrp::new(1., &vec![LinearParticles::default(); 3])
+        .with_tags(&["lead", "pad", "pad"])
+        .unwrap();
+    assert_eq!(grp.set_tag_state("pad", ChildState::Muted), 2);
+    grp.set_child_state(0, ChildState::Paused).unwrap();
+    assert!(grp.set_child_state(3, ChildState::Paused).is_err());
+
+    grp.remove(0).unwrap();
+    assert_eq!(grp.child_state(0), Some(ChildState::Muted));
+    grp.push(LinearParticles::default()).unwrap();
+    assert_eq!(grp.child_state(2), Some(ChildState::Playing));
+}
+
+#[test]
+fn sync_grp_transform_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = SyncGrp::new(1., &[LinearParticles::default()]);
+    grp.set_scale(Vec3::splat(2.));
+    grp.set_translation(Vec3::new(1., 0., 0.));
+    let m = grp.transform.unwrap();
+    assert_eq!(m.transform_point3(Vec3::ONE), Vec3::new(3., 2., 2.));
+}
+
+#[test]
+fn sync_grp_tint_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let grp = SyncGrp::new(1., &[LinearParticles::default()])
+        .with_alpha(0.5)
+        .unwrap();
+    assert_eq!(grp.tint_at(0.3), Ok(Some(Color::new(1., 1., 1., 0.5))));
+    assert_eq!(
+        grp.with_tints(&[]).err(),
+        Some(LinearPLError::EmptyTrack("colors"))
+    );
+}
+
+#[test]
+fn sync_grp_modulation_test() {
+    use crate::linear_particles::LinearParticles;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let level = Rc::new(Cell::new(0.));
+    let l = level.clone();
+    let part = LinearParticles::default()
+        .with_sizes(&[0.1])
+        .unwrap()
+        .with_modulation(ModTarget::Size, || 2.);
+    let mut grp = SyncGrp::new(1., &[part.clone(), part])
+        .with_modulation(ModTarget::Density, move || l.get())
+        .with_modulation(ModTarget::Size, || 1.5);
+    grp.start().unwrap();
+    grp.update_frame(Some(0.25)).unwrap();
+    assert_eq!(grp.particle_count(), 0);
+    level.set(1.);
+    grp.update_frame(Some(0.5)).unwrap();
+    assert!(grp.particle_count() > 0);
+    for ps in grp.iter().unwrap() {
+        assert!(ps
+            .iter()
+            .unwrap()
+            .all(|p| (p.thickness() - 0.3).abs() < 1e-6));
+    }
+    assert_eq!(crate::util::current_modulation(), Factors::default());
+}
+
+#[test]
+fn seq_grp_weights_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let grp = SeqGrp::new(
+        4.,
+        &[LinearParticles::default(), LinearParticles::default()],
+    )
+    .with_weights(&[3., 1.])
+    .unwrap();
+    assert_eq!(grp.part_periods, vec![3., 1.]);
+    assert_eq!(grp.part_at(2.9), (0, 0.));
+    assert_eq!(grp.part_at(3.5), (1, 3.));
+    assert!(grp.with_weights(&[1.]).is_err());
+
+    let grp = SeqGrp::new(1., &vec![LinearParticles::default(); 3])
+        .with_durations(&[0.5, 2., 1.25])
+        .unwrap();
+    assert_eq!(grp.period, 3.75);
+    assert_eq!(grp.part_periods, vec![0.5, 2., 1.25]);
+
+    // durations that are each valid can still sum past any period
+    let grp = SeqGrp::new(1., &vec![LinearParticles::default(); 2]);
+    assert_eq!(
+        grp.with_durations(&[f32::MAX, f32::MAX]).err(),
+        Some(LinearPLError::InvalidPeriod(f32::INFINITY))
+    );
+
+    let mut grp = SeqGrp::new(4., &[LinearParticles::default()]);
+    grp.add_repeated(LinearParticles::default(), 3).unwrap();
+    assert_eq!(grp.slot_parts, vec![0, 1, 1, 1]);
+    assert_eq!(grp.part_periods, vec![1., 1., 1., 1.]);
+    assert_eq!(grp.part_at(2.5), (2, 2.));
+    assert_eq!(
+        grp.add_repeated(LinearParticles::default(), 0),
+        Err(LinearPLError::InvalidRepeats(0))
+    );
+    assert_eq!(grp.parts.len(), 2);
+}
+
+#[test]
+fn seq_grp_introspection_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = SeqGrp::new(4., &vec![LinearParticles::default(); 2]);
+    grp.start().unwrap();
+    grp.last_time = 1.;
+    assert_eq!(grp.current_child_index(), 0);
+    assert_eq!(grp.child_progress(0), Some(0.5));
+    assert_eq!(grp.child_progress(1), Some(0.));
+    assert_eq!(grp.child_progress(2), None);
+
+    let info = grp.describe();
+    assert_eq!(info.name, "SeqGrp");
+    assert_eq!(info.period, Some(4.));
+    assert_eq!(info.children.len(), 2);
+    assert_eq!(info.children[0].name, "LinearParticles");
+    assert!(info.to_string().starts_with("SeqGrp active period=4.00"));
+}
+
+#[test]
+fn mixed_grp_test() {
+    use crate::linear_particles::LinearParticles;
+    use crate::particle_sys::ParticleSysDyn;
+
+    let parts: Vec<Box<dyn ParticleSysDyn>> = vec![
+        Box::new(LinearParticles::default()),
+        Box::new(SeqGrp::new(1., &[LinearParticles::default()])),
+    ];
+    let mut grp = MixedGrp::new(2., &parts);
+    ParticleSys::start_loop(&mut grp).unwrap();
+    assert!(ParticleSys::is_looping(&grp));
+    assert!(ParticleSys::iter(&grp)
+        .unwrap()
+        .all(|ps| ParticleSysDyn::is_active(ps.as_ref())));
+}
+
+/// Group of objects implementing ParticleSys that share a period and
+/// clock like SyncGrp, but where each part starts later than the one
+/// before it. By default part `i` starts `offset * i` seconds after the
+/// group; explicit offsets can be given with `with_offsets()`.
+///
+/// When looping, each part's time wraps within the group period so the
+/// cascade repeats seamlessly. Otherwise the group finishes once the
+/// last part has run for a full period.
+#[derive(Debug, Clone)]
+pub struct StaggerGrp<P: ParticleSys> {
+    period: f32,
+    parts: Vec<P>,
+    offsets: Vec<f32>,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> StaggerGrp<P> {
+    /// Create a new StaggerGrp where part `i` of `sliceparts` starts
+    /// `offset * i` seconds into the group. Returns an error if `offset`
+    /// is negative or not finite.
+    pub fn new(period: f32, offset: f32, sliceparts: &[P]) -> Result<Self, LinearPLError> {
+        check_offsets(&[offset], 1)?;
+        Ok(StaggerGrp {
+            period,
+            parts: sliceparts.into(),
+            offsets: (0..sliceparts.len()).map(|i| offset * i as f32).collect(),
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+        })
+    }
+
+    /// Return self with explicit start offsets `offsets` in seconds,
+    /// one per part.
+    pub fn with_offsets(mut self, offsets: &[f32]) -> Result<Self, LinearPLError> {
+        check_offsets(offsets, self.parts.len())?;
+        self.offsets = offsets.into();
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the StaggerGrp finishes without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping StaggerGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    // latest start offset of all parts
+    fn max_offset(&self) -> f32 {
+        self.offsets.iter().cloned().fold(0., f32::max)
+    }
+}
+
+impl<P> ParticleSys for StaggerGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        for ps in self.parts.iter_mut() {
+            ps.setup(mode, Some(self.period))?;
+        }
+        self.peak_count = 0;
+
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let group_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+        let wrapping = self.mode.repeats_after(0);
+
+        for (ps, offset) in self.parts.iter_mut().zip(self.offsets.iter()) {
+            let t = group_time - offset;
+            if wrapping && self.period > 0. {
+                ps.update_frame(Some(t.rem_euclid(self.period)))?;
+            } else if t >= 0. {
+                ps.update_frame(Some(t))?;
+            }
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        match wrapping {
+            true => Ok(current_time <= self.period),
+            false => Ok(current_time <= self.period + self.max_offset()),
+        }
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        for ps in self.parts.iter_mut() {
+            ps.redraw_frame()?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for StaggerGrp<P> {
+    fn default() -> Self {
+        StaggerGrp::new(1.0, 0.0, &[]).unwrap()
+    }
+}
+
+#[test]
+fn stagger_grp_offsets_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let parts = [LinearParticles::default(), LinearParticles::default()];
+    assert!(StaggerGrp::new(1., -0.5, &parts).is_err());
+    assert!(StaggerGrp::new(1., f32::NAN, &parts).is_err());
+    assert!(StaggerGrp::new(1., 0.5, &parts)
+        .unwrap()
+        .with_offsets(&[0., -1.])
+        .is_err());
+
+    // played once, the second part waits for its offset and the group
+    // runs until it has played its full period
+    let mut grp = StaggerGrp::new(1., 0.5, &parts).unwrap();
+    grp.start_at(0.).unwrap();
+    grp.set_time(0.25);
+    assert!(grp.update().unwrap());
+    assert!(grp.parts[0].spawned_total() > 0);
+    assert_eq!(grp.parts[1].spawned_total(), 0);
+    grp.set_time(1.25);
+    assert!(grp.update().unwrap());
+    assert!(grp.parts[1].spawned_total() > 0);
+    grp.set_time(1.6);
+    assert!(!grp.update().unwrap());
+
+    // looping, the second part's time wraps within the period so it is
+    // already playing three quarters in
+    let mut grp = StaggerGrp::new(1., 0.5, &parts).unwrap();
+    grp.start_loop_at(0.).unwrap();
+    grp.set_time(0.25);
+    assert!(grp.update().unwrap());
+    assert!(grp.parts[1].spawned_total() > grp.parts[0].spawned_total());
+    grp.set_time(1.1);
+    assert!(!grp.update().unwrap());
+}
+
+/// Group of objects implementing ParticleSys where a single part,
+/// chosen at random, plays for the group period. A new part is chosen
+/// each time the group is started and each time it loops, giving
+/// variation to repeated effects. Parts are equally likely unless
+/// weighted with `with_weights()`.
+#[derive(Debug, Clone)]
+pub struct RandomGrp<P: ParticleSys> {
+    period: f32,
+    parts: Vec<P>,
+    weights: Vec<f32>,
+    selected: usize,
+    last_time: f32,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+    rand_generator: SysRng,
+}
+
+impl<P: ParticleSys + std::clone::Clone> RandomGrp<P> {
+    /// Create a new RandomGrp choosing between `sliceparts`.
+    pub fn new(period: f32, sliceparts: &[P]) -> Self {
+        RandomGrp {
+            period,
+            parts: sliceparts.into(),
+            weights: vec![1.; sliceparts.len()],
+            selected: 0,
+            last_time: 0.,
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+            rand_generator: SysRng::new(),
+        }
+    }
+
+    /// Return self with each part chosen with a likelihood proportional
+    /// to its weight in `weights`, one per part.
+    pub fn with_weights(mut self, weights: &[f32]) -> Result<Self, LinearPLError> {
+        check_weights(weights, self.parts.len())?;
+        self.weights = weights.into();
+        Ok(self)
+    }
+
+    /// Return self (consuming it) choosing parts from the random sequence
+    /// of `seed`, the same every time the group is started.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rand_generator = SysRng::seeded(seed);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the RandomGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping RandomGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    /// Return the index of the part currently chosen to play.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    // choose a part at random according to the weights and set it up
+    fn select(&mut self) -> Result<(), LinearPLError> {
+        let total: f32 = self.weights.iter().sum();
+        let mut choice = match total > 0. {
+            true => self.rand_generator.random_range(0.0..total),
+            false => 0.,
+        };
+        self.selected = 0;
+        for (i, w) in self.weights.iter().enumerate() {
+            if choice < *w {
+                self.selected = i;
+                break;
+            }
+            choice -= w;
+        }
+
+        if let Some(ps) = self.parts.get_mut(self.selected) {
+            ps.tear_down();
+            ps.setup(self.mode, Some(self.period))?;
+        }
+        Ok(())
+    }
+}
+
+impl<P> ParticleSys for RandomGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        self.mode = mode;
+        self.cycle = 0;
+        self.last_time = 0.;
+        self.rand_generator.restart();
+        self.select()?;
+        self.peak_count = 0;
+
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+
+        // time running backwards means a new cycle has begun
+        if current_time < self.last_time && self.mode.repeats_after(0) {
+            self.select()?;
+        }
+        self.last_time = current_time;
+
+        if let Some(ps) = self.parts.get_mut(self.selected) {
+            ps.update_frame(Some(current_time))?;
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        match self.parts.get_mut(self.selected) {
+            Some(ps) => ps.redraw_frame(),
+            None => Ok(()),
+        }
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for RandomGrp<P> {
+    fn default() -> Self {
+        RandomGrp::new(1.0, &[])
+    }
+}
+
+#[test]
+fn random_grp_weights_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = RandomGrp::new(
+        1.,
+        &[LinearParticles::default(), LinearParticles::default()],
+    )
+    .with_weights(&[0., 1.])
+    .unwrap();
+    for _ in 0..10 {
+        grp.start().unwrap();
+        assert_eq!(grp.selected(), 1);
+    }
+}
+
+/// Group of objects implementing ParticleSys that plays its parts one
+/// after another like SeqGrp, but overlaps the end of each part with
+/// the start of the next by `crossfade` seconds. Both parts run during
+/// the overlap, avoiding hard cuts between continuous effects.
+///
+/// Every part plays for the same duration, chosen so that the whole
+/// sequence including overlaps fits within the group period.
+#[derive(Debug, Clone)]
+pub struct CrossfadeGrp<P: ParticleSys> {
+    period: f32,
+    crossfade: f32,
+    parts: Vec<P>,
+    running: Vec<bool>,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> CrossfadeGrp<P> {
+    /// Create a new CrossfadeGrp playing `sliceparts` in order with
+    /// `crossfade` seconds of overlap between consecutive parts.
+    pub fn new(period: f32, crossfade: f32, sliceparts: &[P]) -> Result<Self, LinearPLError> {
+        check_crossfade(crossfade)?;
+        Ok(CrossfadeGrp {
+            period,
+            crossfade,
+            parts: sliceparts.into(),
+            running: vec![false; sliceparts.len()],
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+        })
+    }
+
+    /// Set the overlap between consecutive parts to `crossfade` seconds.
+    pub fn set_crossfade(&mut self, crossfade: f32) -> Result<(), LinearPLError> {
+        check_crossfade(crossfade)?;
+        self.crossfade = crossfade;
+        Ok(())
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the CrossfadeGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping CrossfadeGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    // duration each part plays for and the time between part starts
+    fn part_timing(&self) -> (f32, f32) {
+        let n = self.parts.len().max(1) as f32;
+        let fade = self.crossfade.min(self.period);
+        let part_period = (self.period + (n - 1.) * fade) / n;
+        (part_period, part_period - fade)
+    }
+}
+
+impl<P> ParticleSys for CrossfadeGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        self.running = vec![false; self.parts.len()];
+
+        self.peak_count = 0;
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        self.running = vec![false; self.parts.len()];
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let seq_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        // every part whose window contains the time is run, so that
+        // two parts play at once during each overlap
+        let (part_period, step) = self.part_timing();
+        let last = self.parts.len().saturating_sub(1);
+        for (i, (ps, running)) in self
+            .parts
+            .iter_mut()
+            .zip(self.running.iter_mut())
+            .enumerate()
+        {
+            let part_time = seq_time - step * i as f32;
+            let in_window = part_time >= 0. && (part_time <= part_period || i == last);
+            if in_window {
+                if !*running {
+                    ps.tear_down();
+                    ps.setup(self.mode, Some(part_period))?;
+                    *running = true;
+                }
+                ps.update_frame(Some(part_time))?;
+            } else if *running {
+                ps.tear_down();
+                *running = false;
+            }
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        for ps in self.parts.iter_mut() {
+            ps.redraw_frame()?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for CrossfadeGrp<P> {
+    fn default() -> Self {
+        CrossfadeGrp::new(1.0, 0., &[]).unwrap()
+    }
+}
+
+#[test]
+fn crossfade_grp_timing_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let parts = [LinearParticles::default(), LinearParticles::default()];
+    let grp = CrossfadeGrp::new(3., 1., &parts).unwrap();
+    assert_eq!(grp.part_timing(), (2., 1.));
+    assert!(CrossfadeGrp::new(3., -1., &parts).is_err());
+}
+
+/// Group of objects implementing ParticleSys that are armed but only
+/// start playing when triggered by the user with `trigger()` or
+/// `trigger_tag()`. Each triggered part plays once on its own clock,
+/// while `run()` on the group draws whichever parts are playing.
+///
+/// A TriggerGrp has no period of its own and keeps running until it
+/// is stopped, making it suited to one-shot effects driven by events.
+#[derive(Debug, Clone)]
+pub struct TriggerGrp<P: ParticleSys> {
+    parts: Vec<P>,
+    tags: Vec<String>,
+    timer: SysTimer,
+    active: bool,
+    initialized: bool,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> TriggerGrp<P> {
+    /// Create a new TriggerGrp with the armed parts `sliceparts`.
+    pub fn new(sliceparts: &[P]) -> Self {
+        TriggerGrp {
+            parts: sliceparts.into(),
+            tags: vec![String::new(); sliceparts.len()],
+            timer: SysTimer::new(),
+            active: false,
+            initialized: false,
+            peak_count: 0,
+        }
+    }
+
+    /// Return self with the tags `tags`, one per part, used to trigger
+    /// parts by name with `trigger_tag()`.
+    pub fn with_tags(mut self, tags: &[&str]) -> Result<Self, LinearPLError> {
+        check_tags(tags, self.parts.len())?;
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        Ok(self)
+    }
+
+    /// Add the armed part `ps` with tag `tag` to the group.
+    pub fn push(&mut self, tag: &str, ps: P) {
+        self.parts.push(ps);
+        self.tags.push(tag.to_string());
+    }
+
+    /// Start the part at `index` playing from the beginning, restarting
+    /// it if it is already playing.
+    pub fn trigger(&mut self, index: usize) -> Result<(), LinearPLError> {
+        let len = self.parts.len();
+        let ps = self
+            .parts
+            .get_mut(index)
+            .ok_or(LinearPLError::IndexOutOfBounds {
+                context: "TriggerGrp trigger",
+                index,
+                len,
+            })?;
+        fire(ps, self.timer.elapsed())
+    }
+
+    /// Start every part tagged `tag`, returning how many were started.
+    pub fn trigger_tag(&mut self, tag: &str) -> Result<usize, LinearPLError> {
+        let mut count = 0;
+        let now = self.timer.elapsed();
+        for (ps, t) in self.parts.iter_mut().zip(self.tags.iter()) {
+            if t == tag {
+                fire(ps, now)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+// restart the triggered part `ps` from the group's time `now`, which its
+// clock follows, so it plays from the start rather than from wherever its
+// clock stopped when it last finished
+fn fire<P: ParticleSys>(ps: &mut P, now: f32) -> Result<(), LinearPLError> {
+    ps.tear_down();
+    ps.set_time(now);
+    ps.start()
+}
+
+impl<P> ParticleSys for TriggerGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        false
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, _mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        if let Some(p) = p {
+            self.set_period(p)?;
+        }
+
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        self.peak_count = 0;
+
+        self.active = true;
+        self.initialized = true;
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        // triggered parts run on their own clocks, which follow the
+        // group's time when it is driven externally
+        for ps in self.parts.iter_mut() {
+            if !(ps.is_active() && ps.is_initialized()) {
+                continue;
+            }
+            if let Some(t) = time {
+                ps.set_time(t);
+            }
+            if !ps.update()? {
+                ps.tear_down();
+            }
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(true)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    // a TriggerGrp has no period of its own, so this sets the period
+    // each part plays for once triggered
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        for ps in self.parts.iter_mut() {
+            ps.set_period(p)?;
+        }
+        Ok(())
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        for ps in self.parts.iter_mut() {
+            ps.redraw_frame()?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for TriggerGrp<P> {
+    fn default() -> Self {
+        TriggerGrp::new(&[])
+    }
+}
+
+#[test]
+fn trigger_grp_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let parts = [LinearParticles::default(), LinearParticles::default()];
+    let mut grp = TriggerGrp::new(&parts)
+        .with_tags(&["explosion", "smoke"])
+        .unwrap();
+    grp.start().unwrap();
+    assert!(grp.iter().unwrap().all(|ps| !ps.is_active()));
+
+    assert_eq!(grp.trigger_tag("explosion").unwrap(), 1);
+    assert!(grp.iter().unwrap().next().unwrap().is_active());
+    grp.trigger(1).unwrap();
+    assert!(grp.iter().unwrap().all(|ps| ps.is_active()));
+    assert!(grp.trigger(2).is_err());
+}
+
+#[test]
+fn trigger_grp_retrigger_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = TriggerGrp::new(&[LinearParticles::default()]);
+    grp.start_at(0.).unwrap();
+    grp.trigger(0).unwrap();
+
+    // play the part past its period of 1 second until it finishes
+    for t in [0.5, 1.5] {
+        grp.set_time(t);
+        grp.update().unwrap();
+    }
+    assert!(!grp.iter().unwrap().next().unwrap().is_active());
+
+    // triggered again well after, it plays from the beginning
+    grp.set_time(5.);
+    grp.trigger(0).unwrap();
+    grp.set_time(5.5);
+    grp.update().unwrap();
+    let ps = grp.iter().unwrap().next().unwrap();
+    assert!(ps.is_active());
+    assert!(ps.spawned_total() > 0);
+}
+
+/// Group wrapping a single object implementing ParticleSys that is
+/// drawn several times, rotated and optionally reflected about an
+/// `axis` through `center`, for symmetric and kaleidoscopic effects.
+/// All copies share the one simulation of the wrapped part.
+///
+/// `MirrorGrp::radial()` draws `copies` evenly rotated copies, and
+/// `MirrorGrp::reflect()` draws the part and its reflection across
+/// the plane with normal `axis`.
+#[derive(Debug, Clone)]
+pub struct MirrorGrp<P: ParticleSys> {
+    period: f32,
+    part: P,
+    copies: u32,
+    axis: Vec3,
+    center: Vec3,
+    reflected: bool,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> MirrorGrp<P> {
+    /// Create a new MirrorGrp drawing `copies` copies of `part`, each
+    /// rotated by an equal share of a full turn about `axis`.
+    pub fn radial(period: f32, part: P, copies: u32, axis: Vec3) -> Self {
+        MirrorGrp {
+            period,
+            part,
+            copies: copies.max(1),
+            axis: axis.normalize_or(Vec3::Z),
+            center: Vec3::ZERO,
+            reflected: false,
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+        }
+    }
+
+    /// Create a new MirrorGrp drawing `part` and its reflection across
+    /// the plane with normal `normal`.
+    pub fn reflect(period: f32, part: P, normal: Vec3) -> Self {
+        MirrorGrp {
+            axis: normal.normalize_or(Vec3::X),
+            reflected: true,
+            ..MirrorGrp::radial(period, part, 1, Vec3::Z)
+        }
+    }
+
+    /// Return self with every rotated copy also drawn reflected across
+    /// a plane containing the axis, doubling the number of copies for
+    /// kaleidoscope effects.
+    pub fn with_reflection(mut self, reflected: bool) -> Self {
+        self.reflected = reflected;
+        self
+    }
+
+    /// Return self with the axis or plane passing through `center`
+    /// rather than the origin.
+    pub fn with_center(mut self, center: Vec3) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the MirrorGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping MirrorGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    // transform of every copy drawn, starting with the identity
+    fn copy_transforms(&self) -> Vec<Mat4> {
+        let to_center = Mat4::from_translation(self.center);
+        let from_center = Mat4::from_translation(-self.center);
+        let rotations = (0..self.copies).map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / self.copies as f32;
+            Mat4::from_axis_angle(self.axis, angle)
+        });
+
+        let mut transforms: Vec<Mat4> = rotations.collect();
+        if self.reflected {
+            // with a single copy the axis is the plane normal, otherwise
+            // the plane contains the axis of rotation
+            let normal = match self.copies {
+                1 => self.axis,
+                _ => self.axis.any_orthonormal_vector(),
+            };
+            let outer = Mat3::from_cols(normal * normal.x, normal * normal.y, normal * normal.z);
+            let reflection = Mat4::from_mat3(Mat3::IDENTITY - outer * 2.);
+            let reflected: Vec<Mat4> = transforms.iter().map(|m| *m * reflection).collect();
+            transforms.extend(reflected);
+        }
+        transforms
+            .into_iter()
+            .map(|m| to_center * m * from_center)
+            .collect()
+    }
+}
+
+impl<P> ParticleSys for MirrorGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        self.part.setup(mode, Some(self.period))?;
+        self.peak_count = 0;
+
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        self.part.tear_down();
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+
+        // the part is simulated once and drawn under every transform
+        self.part.update_frame(Some(current_time))?;
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(std::slice::from_ref(&self.part).iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(std::slice::from_mut(&mut self.part).iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.part.particle_count()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.part.spawned_total()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        for m in self.copy_transforms() {
+            begin_draw(Some(m), None);
+            let drawn = self.part.redraw_frame();
+            end_draw(Some(m), None);
+            drawn?;
+        }
+        Ok(())
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        for m in self.copy_transforms() {
+            begin_draw(Some(m), None);
+            self.part.draw_debug(gizmos);
+            end_draw(Some(m), None);
+        }
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone + Default> Default for MirrorGrp<P> {
+    fn default() -> Self {
+        MirrorGrp::radial(1.0, P::default(), 1, Vec3::Z)
+    }
+}
+
+#[test]
+fn mirror_grp_transforms_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let grp = MirrorGrp::radial(1., LinearParticles::default(), 4, Vec3::Z);
+    let points: Vec<Vec3> = grp
+        .copy_transforms()
+        .iter()
+        .map(|m| m.transform_point3(Vec3::X))
+        .collect();
+    assert_eq!(points.len(), 4);
+    assert!(points[1].abs_diff_eq(Vec3::Y, 1e-6));
+    assert!(points[2].abs_diff_eq(-Vec3::X, 1e-6));
+
+    let grp = MirrorGrp::reflect(1., LinearParticles::default(), Vec3::X);
+    let m = grp.copy_transforms()[1];
+    assert!(m
+        .transform_point3(Vec3::new(1., 2., 3.))
+        .abs_diff_eq(Vec3::new(-1., 2., 3.), 1e-6));
+}
+
+/// Group of objects implementing ParticleSys that share a period and
+/// clock like SyncGrp, but where part `i` is evaluated `i * phase`
+/// seconds ahead, wrapping within the period. Spreading the phase of
+/// many similar parts produces chasing patterns across them.
+#[derive(Debug, Clone)]
+pub struct PhaseOffsetGrp<P: ParticleSys> {
+    period: f32,
+    phase: f32,
+    parts: Vec<P>,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> PhaseOffsetGrp<P> {
+    /// Create a new PhaseOffsetGrp where part `i` of `sliceparts` runs
+    /// `phase * i` seconds ahead of the group.
+    pub fn new(period: f32, phase: f32, sliceparts: &[P]) -> Self {
+        PhaseOffsetGrp {
+            period,
+            phase,
+            parts: sliceparts.into(),
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+        }
+    }
+
+    /// Return self with the phase between consecutive parts set to
+    /// `phase` seconds.
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the PhaseOffsetGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping PhaseOffsetGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    // time seen by part `i` at group time `t`
+    fn part_time(&self, i: usize, t: f32) -> f32 {
+        match self.period > 0. {
+            true => (t + i as f32 * self.phase).rem_euclid(self.period),
+            false => t,
+        }
+    }
+}
+
+impl<P> ParticleSys for PhaseOffsetGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        for ps in self.parts.iter_mut() {
+            ps.setup(mode, Some(self.period))?;
+        }
+        self.peak_count = 0;
+
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let group_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        for i in 0..self.parts.len() {
+            let t = self.part_time(i, group_time);
+            self.parts[i].update_frame(Some(t))?;
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        for ps in self.parts.iter_mut() {
+            ps.redraw_frame()?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for PhaseOffsetGrp<P> {
+    fn default() -> Self {
+        PhaseOffsetGrp::new(1.0, 0., &[])
+    }
+}
+
+#[test]
+fn phase_offset_grp_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let grp = PhaseOffsetGrp::new(2., 0.75, &vec![LinearParticles::default(); 3]);
+    assert_eq!(grp.part_time(0, 0.5), 0.5);
+    assert_eq!(grp.part_time(1, 0.5), 1.25);
+    assert_eq!(grp.part_time(2, 0.5), 0.);
+}
+
+/// Group of alternative objects implementing ParticleSys where exactly
+/// one part, chosen with `select()`, runs on the group's clock. This
+/// suits state based effects, such as idle, charged and overdrive
+/// variations of one effect.
+///
+/// With a crossfade set, the outgoing part keeps running while fading
+/// out over `crossfade` seconds as the newly selected part fades in.
+#[derive(Debug, Clone)]
+pub struct SwitchGrp<P: ParticleSys> {
+    period: f32,
+    parts: Vec<P>,
+    selected: usize,
+    previous: Option<usize>,
+    crossfade: f32,
+    fade_origin: f32,
+    clock: f32,
+    last_time: f32,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> SwitchGrp<P> {
+    /// Create a new SwitchGrp choosing between `sliceparts`, starting
+    /// with the first.
+    pub fn new(period: f32, sliceparts: &[P]) -> Self {
+        SwitchGrp {
+            period,
+            parts: sliceparts.into(),
+            selected: 0,
+            previous: None,
+            crossfade: 0.,
+            fade_origin: 0.,
+            clock: 0.,
+            last_time: 0.,
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+        }
+    }
+
+    /// Return self with switches between parts crossfading over
+    /// `crossfade` seconds.
+    pub fn with_crossfade(mut self, crossfade: f32) -> Result<Self, LinearPLError> {
+        check_crossfade(crossfade)?;
+        self.crossfade = crossfade;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the SwitchGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping SwitchGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    /// Return the index of the selected part.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Switch to the part at `index`. If the SwitchGrp is running, the
+    /// part is set up to join on the group's clock and the previous
+    /// part is stopped, or faded out if a crossfade is set.
+    pub fn select(&mut self, index: usize) -> Result<(), LinearPLError> {
+        let len = self.parts.len();
+        if index >= len {
+            return Err(LinearPLError::IndexOutOfBounds {
+                context: "SwitchGrp select",
+                index,
+                len,
+            });
+        }
+        if index == self.selected {
+            return Ok(());
+        }
+
+        if self.active {
+            if let Some(prev) = self.previous.take() {
+                self.parts[prev].tear_down();
+            }
+            if self.crossfade > 0. {
+                self.previous = Some(self.selected);
+                self.fade_origin = self.clock;
+            } else {
+                self.parts[self.selected].tear_down();
+            }
+            self.parts[index].tear_down();
+            self.parts[index].setup(self.mode, Some(self.period))?;
+        }
+        self.selected = index;
+        Ok(())
+    }
+
+    // opacity of the selected part through the crossfade from the
+    // previous one, `1.` once it has finished
+    fn fade(&self) -> f32 {
+        match self.crossfade > 0. {
+            true => ((self.clock - self.fade_origin) / self.crossfade).min(1.),
+            false => 1.,
+        }
+    }
+}
+
+impl<P> ParticleSys for SwitchGrp<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        let len = self.parts.len();
+        self.parts
+            .get_mut(self.selected)
+            .ok_or(LinearPLError::IndexOutOfBounds {
+                context: "SwitchGrp setup",
+                index: self.selected,
+                len,
+            })?
+            .setup(mode, Some(self.period))?;
+        self.previous = None;
+        self.clock = 0.;
+        self.last_time = 0.;
+        self.peak_count = 0;
+
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        self.previous = None;
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let child_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        // crossfades run on a monotonic clock so they carry on across
+        // loop resets of `current_time`
+        self.clock += match current_time >= self.last_time {
+            true => current_time - self.last_time,
+            false => current_time,
+        };
+        self.last_time = current_time;
+
+        if self.fade() >= 1. {
+            if let Some(prev) = self.previous.take() {
+                self.parts[prev].tear_down();
+            }
+        }
+
+        if let Some(prev) = self.previous {
+            self.parts[prev].update_frame(Some(child_time))?;
+        }
+        if let Some(ps) = self.parts.get_mut(self.selected) {
+            ps.update_frame(Some(child_time))?;
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        let Some(prev) = self.previous else {
+            return match self.parts.get_mut(self.selected) {
+                Some(ps) => ps.redraw_frame(),
+                None => Ok(()),
+            };
+        };
+
+        let fade = self.fade();
+        push_tint(Color::new(1., 1., 1., 1. - fade));
+        let drawn = self.parts[prev].redraw_frame();
+        pop_tint();
+        drawn?;
+
+        push_tint(Color::new(1., 1., 1., fade));
+        let drawn = self.parts[self.selected].redraw_frame();
+        pop_tint();
+        drawn
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for SwitchGrp<P> {
+    fn default() -> Self {
+        SwitchGrp::new(1.0, &[])
+    }
+}
+
+#[test]
+fn switch_grp_select_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut grp = SwitchGrp::new(1., &vec![LinearParticles::default(); 3])
+        .with_crossfade(0.5)
+        .unwrap();
+    grp.start_loop().unwrap();
+    grp.select(2).unwrap();
+    assert_eq!(grp.selected(), 2);
+    assert_eq!(grp.previous, Some(0));
+
+    let active: Vec<bool> = grp.iter().unwrap().map(|ps| ps.is_active()).collect();
+    assert_eq!(active, vec![true, false, true]);
+    assert!(grp.select(3).is_err());
+}
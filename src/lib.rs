@@ -13,7 +13,7 @@
 //! 
 //! A linear particle instance starting at (0, 0, 0) and ending at (1, 1, 1):
 //! 
-//! ```
+//! ```ignore
 //! use macroquad::prelude::*;
 //! use linearpl::linear_particles::LinearParticles;
 //! 
@@ -47,26 +47,34 @@
 //! trait's start and stop methods:
 //! 
 //! * `start()` and `start_loop()` setup and prepare the particle system to be drawn
+//! * `start_with_mode()` does the same with a `LoopMode` (`Once`, `Loop`, `PingPong` or `LoopN`)
 //! * `run()` displays particles with respect to the amount of elapsed time from "starting"
 //! * `stop()` stops the particle system before termination in `run()` or while looping
 //! 
 //! Along with these methods, all implementations of `ParticleSys` in the library implement
 //! particle systems that span a set `period` held by the object, which is the number of seconds
-//! the particle system should run.
+//! the particle system should run. How systems step through that period, split `run()` into
+//! `update()` and `draw()`, or follow a shared `Clock` is covered in the `particle_sys` module.
 //! 
 //! ### LinearParticles
 //! 
-//! For the linear particle system `linearpl::linear_particles::LinearParticles`, the particles 
+//! For the linear particle system `linearpl::linear_particles::LinearParticles`, the particles
 //! fall along a linear path defined by the `start_location` and `end_location` Vec3 members of
 //! the object. The user then has control over some other settings which are linearly interpolated
 //! over throughout the entire `period` of the objects particle generation:
 //! 
 //! * `densities` : chance that a particle will be drawn in the given frame (0 to 1)
+//! * `rates` : alternatively, particles spawned per second, the same at any framerate
 //! * `locations` : location to generate particle on line from 0 (`start_location`) to 1 (`end_location`)
 //! * `colors` : color of particle generated in the given frame (using `macroquad::color::Color`)
+//! * `sizes` : thickness of particle generated in the given frame in world units (0 draws a hairline)
+//! * `spreads` : radius particles generated in the given frame are scattered around the line by
+//! * `directions` : direction particles are launched in at the speed of `velocity` (zero keeps `velocity` as is)
 //! 
 //! Other than that, there is a `decay` control which sets the amount of time it a particle
-//! is drawn for (i.e. defines the `period` of each individual particle).
+//! is drawn for (i.e. defines the `period` of each individual particle). Easing, motion,
+//! forces, seeding, rewinding and the rest of its settings are described in the
+//! `linear_particles` module.
 //! 
 //! ### SyncGrp and SeqGrp
 //! 
@@ -74,10 +82,73 @@
 //! using a single clock, making it easier for the user to create more complex and interesting
 //! graphics from the particle system implementation in the library. These objects hold any
 //! type of `ParticleSys` implementation, including other SyncGrp and SeqGrp objects.
+//! 
+//! ### Timeline
+//! 
+//! `linearpl::timeline::Timeline` schedules objects implementing `ParticleSys` at explicit
+//! start times and durations on a single clock, so that systems can overlap in ways that
+//! would otherwise need deeply nested groups. Systems whose time runs out stop emitting
+//! but keep aging their particles until they have decayed.
+//! 
+//! ### Other modules
+//! 
+//! * `curves` : tracks, easings and interpolators behind the settings of a system
+//! * `forces` and `collision` : forces and solid volumes acting on moving particles
+//! * `modulation` : live signals, such as music, scaling what a system spawns
+//! * `config`, `load` and `watch` : effects as plain data, read from RON or JSON files and reloaded as they change
+//! * `presets` and `import` : ready-made effects, and effects brought over from `macroquad-particles`
+//! * `render`, `screen` and `culling` : drawing backends, screen space effects, and culling and level of detail
+//! * `gizmos` and `diagnostics` : debug drawing and on-screen stats
+//! * `export` and `record` : particles written out as point clouds, and the screen captured as images
+//! * `editor` : live tuning of effects in an egui panel
+//! 
+//! ---
+//! 
+//! # Features
+//! 
+//! * `macroquad` (default) : drawing with macroquad; without it only the simulation core is built,
+//!   with vectors and colors from `linearpl::math`
+//! * `headless` : nothing is drawn, so systems `run()` in tests and on servers without a window
+//! * `parallel` : large systems and groups age their particles across threads with rayon
+//! * `instancing` : `RenderMode::Instanced`, drawing lines through a custom shader
+//! * `serde`, `ron` and `json` : serializable configs and loading effects from files
+//! * `editor` : the egui `Editor`
+//! * `gif` : animated GIFs from a `Recorder`
+//! 
+//! LinearPL runs in macroquad web builds as well, reading wall time from macroquad
+//! rather than `std::time::Instant` on `wasm32`.
 
+mod callback;
+mod rng;
+mod time;
 mod util;
 
+pub mod clock;
+pub mod collision;
+pub mod config;
+pub mod culling;
+pub mod diagnostics;
+#[cfg(feature = "editor")]
+pub mod editor;
+pub mod curves;
+pub mod error;
+pub mod export;
+pub mod forces;
+pub mod gizmos;
 pub mod groups;
+pub mod import;
 pub mod linear_particles;
+pub mod math;
+pub mod modulation;
+#[cfg(any(feature = "ron", feature = "json"))]
+pub mod load;
 pub mod particle;
 pub mod particle_sys;
+pub mod presets;
+#[cfg(feature = "macroquad")]
+pub mod record;
+pub mod render;
+pub mod screen;
+pub mod timeline;
+#[cfg(any(feature = "ron", feature = "json"))]
+pub mod watch;
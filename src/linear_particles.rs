@@ -6,286 +6,4365 @@
 //! of these Particle Systems is held within the `linearpl::particle_sys::ParticleSys`
 //! trait. It's recommended to look at the documentation for `ParticleSys`
 //! before using this module.
+//!
+//! Each particle's size can also follow a `size_curve`, interpolated over its own decay
+//! time rather than the period, such as growing smoke puffs that fade as they expand.
+//!
+//! Exact numbers of particles can be spawned at once with `with_bursts()`, given as
+//! pairs of a time in seconds into the period and a count, such as impacts or heartbeat
+//! pulses, on top of or instead of the densities.
+//!
+//! Track values are checked as they are set, rejecting NaN and infinities along with
+//! values out of range. `with_validation(Validation::Clamp)` instead clamps locations
+//! and densities slightly outside `0` to `1` into range, reporting each in `diagnostics()`
+//! as a `linearpl::error::Diagnostic` holding the error it would have raised.
+//!
+//! Tracks blend linearly between neighbouring values by default; `with_easing()`
+//! takes a `TrackKind` and a `linearpl::curves::Easing`, such as `QuadOut`, `SineInOut`
+//! or `Bounce`, to shape that blend for any one track.
+//! `with_segment_easings()` picks a different easing for each segment between
+//! neighbouring values instead, including `Easing::CubicBezier` handles like CSS
+//! `cubic-bezier()` for precisely authored acceleration.
+//! Tracks can also be given as keys with their own normalized times through the
+//! period, such as `with_color_keys(&[(0., RED), (0.2, WHITE), (1., BLUE)])`, so
+//! values can hold and then change quickly without padding duplicates.
+//! For looping systems, `with_seamless(kind, true)` blends a track from its last value
+//! back to its first across each loop boundary instead of snapping.
+//! Times outside of the period, such as the far end of particles spawned right as it
+//! closes, clamp to a track's last value unless `with_edge()` sets it to `Edge::Extrapolate`
+//! or `Edge::Wrap`.
+//! `with_track_mode(kind, TrackMode::CatmullRom)` sweeps a track along a smooth
+//! curve through its values instead, without corners where it changes direction.
+//! `TrackMode::Step` holds each value until the next one instead, for discrete
+//! color flips or switching density on and off.
+//! Beyond these, such as for logarithmic sweeps or data-driven curves, each track
+//! accepts a custom `linearpl::curves::Interpolator` from the ratio through the
+//! period to a value, like `with_density_interpolator(|r: f32| (r * 20.).sin().abs())`.
+//! `with_noise(kind, Noise::new(amplitude, frequency))` overlays seeded noise on a
+//! track for flickering densities, shimmering colors or wobbling locations, the
+//! same every time it plays rather than hand-authored from dozens of keys.
+//! All of this lives in the public `linearpl::curves` module, so other `ParticleSys`
+//! implementations can reuse its `Track` type and `map_*` functions.
+//!
+//! Besides the tracks, there is a `decay` control which sets the amount of time it a particle
+//! is drawn for (i.e. defines the `period` of each individual particle), which can
+//! also be a `decays` track over the period for build-up and die-down phases. Particles
+//! can fade in over `fade_in` seconds as well as fading out, along a `FadeCurve`.
+//!
+//! Particles are drawn as lines by default; `with_shape()` takes a
+//! `linearpl::particle::ParticleShape` to draw them as cubes, spheres,
+//! camera-facing billboards or points instead, with `with_spin()` turning
+//! cubes and billboards as they age.
+//! `with_blend_mode(BlendMode::Additive)` adds particles onto what is
+//! beneath them rather than covering it, for energy and fire effects, and
+//! `with_glow()` draws a wider, fainter halo beneath each particle.
+//!
+//! `with_spread()` scatters spawned particles within a radius around the line
+//! instead of placing them exactly on it.
+//!
+//! Spawned particles sit still on the line unless given a velocity with
+//! `with_velocity()`, randomly varied by a spread, and pulled along by
+//! `with_gravity()` while they decay, slowing down under `with_drag()`.
+//! Moving particles can leave a fading trail of their last positions with
+//! `with_trail()`, or be stretched into streaks along their velocity with
+//! `with_stretch()`.
+//! Further forces from `linearpl::forces`, such as `Wind`, `PointAttractor`
+//! and `Drag`, can be gathered in a `ForceField` and attached to any number
+//! of systems with `with_forces()`. Forces are divided by each particle's mass,
+//! drawn from the range given to `with_mass()`, so heavy sparks hold their course
+//! while light embers drift.
+//! Likewise, planes and boxes from `linearpl::collision` gathered in
+//! `Colliders` make particles bounce, slide or die on contact with
+//! `with_colliders()`.
+//!
+//! For anything else, `with_update()` runs a closure on every live particle
+//! each frame before it is drawn, given the seconds it was stepped by and
+//! its age.
+//!
+//! To attach an effect to a moving object, such as a thruster trail to a ship,
+//! `set_anchor()` moves the line of a system by an offset each frame, or
+//! `with_follow(f)` by the location `f` returns, interpolated across the steps of
+//! the frame. Only particles spawned afterwards move with it, so they trail behind
+//! the object while the tracks keep interpolating along the line as before.
+//!
+//! Random spawn decisions are drawn fresh each run unless a system is given
+//! `with_seed(seed)`, after which it spawns the exact same particles every time it
+//! starts, for visual tests, replays and lockstep networking. `RandomGrp::with_seed()`
+//! does the same for the parts a group chooses.
+//!
+//! Systems given `with_rewind(true)` play backwards faithfully when their time
+//! decreases, as when scrubbing an editor's timeline or rewinding time in a game,
+//! replaying their period from the latest snapshot before the earlier time, kept
+//! every 32 fixed steps, to rebuild the particles alive then. Combined with a seed,
+//! these are exactly the particles that were there on the way forward, though
+//! `with_on_spawn()` callbacks run again for each particle the replay respawns.
+//!
+//! Dense, long-lived effects can be bounded with `with_max_particles()`, which
+//! preallocates room for that many particles and drops the oldest ones past the cap.
+//! Scenes full of ambient emitters can skip drawing those that can't be seen with
+//! `with_culling()`, given a `linearpl::culling::Culling` that tests the box around
+//! each system against the camera's view and an optional maximum distance, and can
+//! pause their simulation as well while culled.
+//! Systems drawn with `run_with_camera(&camera)` can also be given a `Lod` from the same
+//! module with `with_lod()`, spawning fewer and smaller particles as the emitter gets
+//! farther from the camera.
+
+#[cfg(feature = "macroquad")]
+use macroquad::camera::Camera3D;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::f32::consts::TAU;
+use std::rc::Rc;
+use std::slice::{Iter, IterMut};
+
+use crate::callback::{FollowCallback, Hooks, SignalCallback, SpawnCallback, UpdateCallback};
+use crate::clock::Clock;
+use crate::collision::Colliders;
+use crate::config::LinearParticlesConfig;
+use crate::culling::{Bounds, Culling, Lod};
+use crate::curves::{
+    map_color_value, map_float_value, map_location, Easing, Edge, Interpolator, Noise, Track,
+    TrackMode, TrackOptions,
+};
+use crate::error::{Diagnostic, LinearPLError, Validation};
+use crate::forces::{Force, ForceField};
+use crate::gizmos::Gizmos;
+#[cfg(feature = "parallel")]
+use crate::groups::age_parts;
+use crate::groups::ChildState;
+use crate::math::{Color, Mat4, Position, Quat, Vec3};
+use crate::modulation::{Factors, ModTarget, Modulation};
+use crate::particle::{BlendMode, FadeCurve, Particle, ParticleShape};
+use crate::particle_sys::{LoopMode, ParticleSys, PerfCounters, SysInfo};
+#[cfg(feature = "macroquad")]
+use crate::render::register_camera;
+use crate::render::{ParticleRenderer, RenderMode, Renderers};
+use crate::rng::SysRng;
+use crate::screen::ScreenPoint;
+use crate::time::{now, SysTimer};
+use crate::util::{
+    begin_blend, begin_draw, begin_screen, check_bursts, check_colors, check_decay, check_decays,
+    check_densities, check_directions, check_drag, check_exclusive, check_fade, check_glow,
+    check_key_times, check_locations, check_lod, check_mass, check_noise, check_period,
+    check_rates, check_size_curve, check_sizes, check_spread, check_spreads, check_stretch,
+    check_tags, check_timestep, check_warmup, current_modulation, current_transform, end_blend,
+    end_draw, end_screen, pop_modulation, push_modulation, screen_projection, screen_size,
+    view_projection, HEADLESS,
+};
+
+// ***************************************
+// LinearParticles
+// ***************************************
+
+// seconds simulated by each fixed step unless set by `with_timestep()`
+const DEFAULT_TIMESTEP: f32 = 1. / 60.;
+
+// fewest live particles a system is aged in parallel with, below which
+// handing the work out to threads costs more than it saves
+#[cfg(feature = "parallel")]
+pub(crate) const PARALLEL_MIN: usize = 1024;
+
+// most fixed steps simulated in a single frame, so that a long stall
+// doesn't make the following frame slower still catching up
+const MAX_STEPS: usize = 240;
+
+// fixed steps between the snapshots kept by rewinding systems, the most
+// a rewind has to replay once the system has played through that time
+const SNAPSHOT_STEPS: usize = 32;
+
+/// Track of a LinearParticles system, used to select which track
+/// settings such as `with_easing()` apply to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackKind {
+    Locations,
+    Densities,
+    Colors,
+    Sizes,
+    Decays,
+    Rates,
+    Directions,
+    Spreads,
+}
+
+// state of a rewinding system after `step` fixed steps of the period,
+// which a rewind to a later time replays from
+#[derive(Debug, Clone)]
+struct Snapshot {
+    step: usize,
+    particles: Vec<Particle>,
+    clock: f32,
+    last_time: f32,
+    rate_owed: f32,
+    spawned: usize,
+    oldest: usize,
+    rng: SysRng,
+}
+
+/// LinearParticle system. User should be in charge of setting
+/// appropriate `locations`, `densities`, `colors`, and `sizes`
+/// such that their values are interpolated over the defined `period`
+/// in seconds. `decay` refers to the amount of time the particles
+/// generated stay visible.
+#[derive(Debug, Clone)]
+pub struct LinearParticles {
+    particles: Vec<Particle>,
+    start_location: Vec3,
+    end_location: Vec3,
+    locations: Track<f32>,
+    densities: Track<f32>,
+    colors: Track<Color>,
+    sizes: Track<f32>,
+    size_curve: Vec<f32>,
+    shape: ParticleShape,
+    blend: BlendMode,
+    render_mode: RenderMode,
+    renderers: Renderers,
+    glow: (f32, f32),
+    velocity: Vec3,
+    velocity_spread: f32,
+    directions: Track<Vec3>,
+    spreads: Track<f32>,
+    gravity: Vec3,
+    drag: f32,
+    mass: (f32, f32),
+    stretch: f32,
+    spin: f32,
+    spin_spread: f32,
+    trail: usize,
+    max_particles: Option<usize>,
+    // slot of the oldest particle once spawning has wrapped around the
+    // cap, overwritten by the next one spawned
+    oldest: usize,
+    timestep: f32,
+    sim_time: f32,
+    rewind: bool,
+    snapshots: Vec<Snapshot>,
+    perf: Option<PerfCounters>,
+    culling: Option<Culling>,
+    lod: Option<Lod>,
+    camera: Option<Vec3>,
+    forces: ForceField,
+    colliders: Colliders,
+    period: f32,
+    decays: Track<f32>,
+    rates: Track<f32>,
+    by_rate: bool,
+    rate_owed: f32,
+    bursts: Vec<(f32, usize)>,
+    fade_in: f32,
+    fade_curve: FadeCurve,
+    warmup: f32,
+    initialized: bool,
+    mode: LoopMode,
+    cycle: u32,
+    active: bool,
+    timer: SysTimer,
+    rand_generator: SysRng,
+    hooks: Hooks,
+    on_spawn: Option<SpawnCallback>,
+    on_update: Option<UpdateCallback>,
+    modulation: Modulation,
+    modulated: Factors,
+    clock: f32,
+    last_time: f32,
+    peak_count: usize,
+    spawned: usize,
+    transform: Option<Mat4>,
+    screen: Option<(ScreenPoint, ScreenPoint)>,
+    // offset of the line, moving new particles with the object followed
+    anchor: Vec3,
+    follow: Option<FollowCallback>,
+    // whether created from 2D points, keeping particles in the `z = 0`
+    // plane
+    planar: bool,
+    validation: Validation,
+    diagnostics: Vec<Diagnostic>,
+    // whether the group asked to age the particles of the next frame,
+    // and the clock and particle count of each step it spawned
+    #[cfg(feature = "parallel")]
+    defer: bool,
+    #[cfg(feature = "parallel")]
+    deferred: Vec<(f32, usize)>,
+}
+
+impl LinearParticles {
+    /// Return a LinearParticlesBuilder with a starting location of
+    /// `start_loc` and an ending location of `end_loc`.
+    pub fn builder<P: Position>(start_loc: P, end_loc: P) -> LinearParticlesBuilder {
+        LinearParticlesBuilder::new(start_loc, end_loc)
+    }
+
+    /// Create a new LinearParticles from the plain data settings of
+    /// `config`. Invalid settings are reported as an `InvalidField`
+    /// error naming the setting.
+    pub fn from_config(config: &LinearParticlesConfig) -> Result<Self, LinearPLError> {
+        config.validate()?;
+        config.builder().build()
+    }
+
+    /// Create a new LinearParticles struct with a starting location of
+    /// `start_loc` and an ending location of `end_loc`. Systems created
+    /// from `Vec2` points keep their particles in the `z = 0` plane.
+    pub fn new<P: Position>(start_loc: P, end_loc: P) -> Self {
+        LinearParticles {
+            start_location: start_loc.to_vec3(),
+            end_location: end_loc.to_vec3(),
+            particles: Vec::new(),
+            locations: Track::new(vec![0., 1.]),
+            densities: Track::new(vec![1.]),
+            colors: Track::new(vec![Color::new(1., 1., 1., 1.)]),
+            sizes: Track::new(vec![0.]),
+            size_curve: vec![1.],
+            shape: ParticleShape::Line,
+            blend: BlendMode::Alpha,
+            render_mode: RenderMode::Immediate,
+            renderers: Renderers::default(),
+            glow: (0., 0.),
+            velocity: Vec3::ZERO,
+            velocity_spread: 0.,
+            directions: Track::new(vec![Vec3::ZERO]),
+            spreads: Track::new(vec![0.]),
+            gravity: Vec3::ZERO,
+            drag: 0.,
+            mass: (1., 1.),
+            stretch: 0.,
+            spin: 0.,
+            spin_spread: 0.,
+            trail: 0,
+            max_particles: None,
+            oldest: 0,
+            timestep: DEFAULT_TIMESTEP,
+            sim_time: 0.,
+            rewind: false,
+            snapshots: Vec::new(),
+            perf: None,
+            culling: None,
+            lod: None,
+            camera: None,
+            forces: ForceField::new(),
+            colliders: Colliders::new(),
+            period: 1.,
+            decays: Track::new(vec![0.09]),
+            rates: Track::new(vec![0.]),
+            by_rate: false,
+            rate_owed: 0.,
+            bursts: Vec::new(),
+            fade_in: 0.,
+            fade_curve: FadeCurve::Linear,
+            warmup: 0.,
+            initialized: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            active: false,
+            timer: SysTimer::new(),
+            rand_generator: SysRng::new(),
+            hooks: Hooks::default(),
+            on_spawn: None,
+            on_update: None,
+            modulation: Modulation::default(),
+            modulated: Factors::default(),
+            clock: 0.,
+            last_time: 0.,
+            peak_count: 0,
+            spawned: 0,
+            transform: None,
+            screen: None,
+            anchor: Vec3::ZERO,
+            follow: None,
+            planar: P::DIMENSIONS == 2,
+            validation: Validation::Strict,
+            diagnostics: Vec::new(),
+            #[cfg(feature = "parallel")]
+            defer: false,
+            #[cfg(feature = "parallel")]
+            deferred: Vec::new(),
+        }
+    }
+
+    // used in density calculations
+    fn should_generate(&mut self, chance: f32) -> bool {
+        chance > self.rand_generator.random_range(0.0..1.0)
+    }
+
+    // random offset with each component within `-spread` to `spread`,
+    // leaving `z` at `0` for planar systems
+    fn random_spread(&mut self, spread: f32) -> Vec3 {
+        if spread <= 0. {
+            return Vec3::ZERO;
+        }
+        let mut v = Vec3::new(
+            self.rand_generator.random_range(-spread..=spread),
+            self.rand_generator.random_range(-spread..=spread),
+            self.rand_generator.random_range(-spread..=spread),
+        );
+        if self.planar {
+            v.z = 0.;
+        }
+        v
+    }
+
+    // random offset within `spread` of the line, perpendicular to it
+    fn random_jitter(&mut self, spread: f32) -> Vec3 {
+        if spread <= 0. {
+            return Vec3::ZERO;
+        }
+        let direction = (self.end_location - self.start_location)
+            .try_normalize()
+            .unwrap_or(Vec3::Y);
+        if self.planar {
+            let normal = Vec3::Z.cross(direction).try_normalize().unwrap_or(Vec3::X);
+            return normal * self.rand_generator.random_range(-spread..=spread);
+        }
+        let (u, v) = direction.any_orthonormal_pair();
+        let radius = spread * self.rand_generator.random_range(0.0..=1.0f32).sqrt();
+        let angle = self.rand_generator.random_range(0.0..TAU);
+        (u * angle.cos() + v * angle.sin()) * radius
+    }
+
+    // advance the clock to `current_time`, returning the previous time
+    // and the seconds stepped by
+    fn advance_clock(&mut self, current_time: f32) -> (f32, f32) {
+        // particles age on a monotonic clock so they keep decaying
+        // across loop resets of `current_time`. Ping-pong traversal
+        // runs backwards continuously rather than resetting.
+        let dt = if self.mode == LoopMode::PingPong {
+            (current_time - self.last_time).abs()
+        } else if current_time >= self.last_time {
+            current_time - self.last_time
+        } else {
+            current_time
+        };
+        let last_time = self.last_time;
+        self.last_time = current_time;
+        self.clock += dt;
+        (last_time, dt)
+    }
+
+    // advance the clock to `current_time` and push the particles spawned
+    // in this step onto `out`, stamped with the clock. Returns the number
+    // of particles spawned.
+    fn spawn(
+        &mut self,
+        current_time: f32,
+        out: &mut Vec<Particle>,
+    ) -> Result<usize, LinearPLError> {
+        let (last_time, dt) = self.advance_clock(current_time);
+        let (density, _) = self.spawn_scales();
+
+        // bursts passed over in this step, spawned at their own time
+        let before = out.len();
+        for i in 0..self.bursts.len() {
+            let (t, count) = self.bursts[i];
+            if t <= self.period && self.passed(last_time, current_time, t) {
+                for _ in 0..(count as f32 * density).round() as usize {
+                    out.push(self.spawn_one(t)?);
+                }
+            }
+        }
+
+        if current_time > self.period {
+            return Ok(out.len() - before);
+        }
+        let count = if self.by_rate {
+            // whole particles owed at the current rate, carrying the
+            // remainder over so emission is the same at any framerate
+            let rate = self.rates.value_at(current_time, self.period)?.max(0.) * density;
+            self.rate_owed += rate * dt;
+            let count = self.rate_owed.floor();
+            self.rate_owed -= count;
+            count as usize
+        } else {
+            let gen_flag = self.densities.value_at(current_time, self.period)?;
+            self.should_generate(gen_flag * density) as usize
+        };
+        for _ in 0..count {
+            out.push(self.spawn_one(current_time)?);
+        }
+        Ok(out.len() - before)
+    }
+
+    // whether stepping from `last` to `current` passed over time `t`,
+    // counting each time once across consecutive steps
+    fn passed(&self, last: f32, current: f32, t: f32) -> bool {
+        if current >= last {
+            last <= t && t < current
+        } else if self.mode == LoopMode::PingPong {
+            current < t && t <= last
+        } else {
+            t >= last || t < current
+        }
+    }
+
+    // create a particle `current_time` into the period, stamped with the
+    // clock
+    fn spawn_one(&mut self, current_time: f32) -> Result<Particle, LinearPLError> {
+        let (start, end) = self.line();
+        let location = map_location(&self.locations, start, end, current_time, self.period)?;
+        // stretched particles are drawn along their velocity, the
+        // rest as a segment reaching a few steps along the line
+        let end_location = if self.stretch > 0. {
+            location
+        } else {
+            let nft = 4.0 * self.timestep;
+            map_location(&self.locations, start, end, current_time + nft, self.period)?
+        };
+        let c = self.colors.value_at(current_time, self.period)?;
+        let mut p = Particle::new_line(
+            location.into(),
+            end_location.into(),
+            (c.r, c.g, c.b, c.a),
+            self.decays.value_at(current_time, self.period)?.max(0.),
+            true,
+        )?;
+        let (_, size) = self.spawn_scales();
+        p.set_thickness(self.sizes.value_at(current_time, self.period)?.max(0.) * size);
+        p.set_shape(self.shape);
+        let spread = self.spreads.value_at(current_time, self.period)?.max(0.);
+        p.translate(self.random_jitter(spread));
+        p.set_fade_in(self.fade_in);
+        p.set_fade_curve(self.fade_curve);
+        p.set_glow(self.glow.0, self.glow.1);
+        let direction = self.directions.value_at(current_time, self.period)?;
+        let velocity = match direction.try_normalize() {
+            Some(d) => d * self.velocity.length(),
+            None => self.velocity,
+        };
+        p.set_velocity(velocity + self.random_spread(self.velocity_spread));
+        p.set_acceleration(self.gravity);
+        p.set_drag(self.drag);
+        let (min, max) = self.mass;
+        if max > min {
+            p.set_mass(self.rand_generator.random_range(min..=max));
+        } else {
+            p.set_mass(min);
+        }
+        p.set_stretch(self.stretch);
+        p.set_trail(self.trail);
+        if self.spin != 0. || self.spin_spread > 0. {
+            let spread = self.spin_spread;
+            p.set_spin(self.spin + self.rand_generator.random_range(-spread..=spread));
+            let axis = match self.planar {
+                true => Vec3::Z,
+                false => self.random_spread(1.),
+            };
+            p.set_spin_axis(axis);
+            p.set_angle(self.rand_generator.random_range(0.0..TAU));
+        }
+        p.set_time(self.clock);
+        p.reset();
+        if let Some(f) = &self.on_spawn {
+            f.call(&mut p);
+        }
+        self.spawned += 1;
+        Ok(p)
+    }
+
+    // spawn new particles and age existing ones for `current_time`
+    // without drawing. Returns `true` while still within the period.
+    // Particles are spawned straight into the live buffer, which keeps
+    // its capacity as they die, so a system no longer allocates once
+    // its particle count has settled.
+    fn simulate(&mut self, current_time: f32) -> Result<bool, LinearPLError> {
+        let mut particles = std::mem::take(&mut self.particles);
+        let spawned = self.spawn(current_time, &mut particles);
+        self.particles = particles;
+        spawned?;
+        if let Some(max) = self.max_particles {
+            self.wrap_spawned(max);
+        }
+
+        age_in_place(
+            &mut self.particles,
+            self.clock,
+            &self.forces,
+            &self.colliders,
+            &self.size_curve,
+            self.on_update.as_ref(),
+        );
+        let (oldest, mut i, mut before) = (self.oldest, 0, 0);
+        self.particles.retain(|p| {
+            let alive = !p.is_finished();
+            before += usize::from(!alive && i < oldest);
+            i += 1;
+            alive
+        });
+        // once particles die, a wrapped buffer is put back in spawn
+        // order so the next ones spawned can go on its end
+        if self.particles.len() < i && oldest > 0 {
+            self.particles.rotate_left(oldest - before);
+            self.oldest = 0;
+        }
+        self.peak_count = self.peak_count.max(self.particles.len());
+        Ok(current_time <= self.period)
+    }
+
+    // move the particles just spawned past the cap `max` over the oldest
+    // ones in turn, so that a capped system only ever touches the slots
+    // it spawns into
+    fn wrap_spawned(&mut self, max: usize) {
+        let len = self.particles.len();
+        if max == 0 {
+            self.particles.clear();
+            return;
+        }
+        self.oldest %= max;
+        for i in max..len {
+            self.particles.swap(self.oldest, i);
+            self.oldest = (self.oldest + 1) % max;
+        }
+        self.particles.truncate(max);
+    }
+
+    // start the simulation over from the beginning of the period with
+    // no particles, warming up looping systems
+    fn restart(&mut self) -> Result<(), LinearPLError> {
+        self.particles.clear();
+        #[cfg(feature = "parallel")]
+        self.deferred.clear();
+        self.snapshots.clear();
+        self.oldest = 0;
+        self.last_time = 0.;
+        self.clock = 0.;
+        self.rate_owed = 0.;
+        self.spawned = 0;
+        self.rand_generator.restart();
+        if self.mode.repeats_after(0) && self.warmup > 0. {
+            self.warm_up()?;
+        }
+        self.sim_time = 0.;
+        Ok(())
+    }
+
+    // rebuild the particles alive at `current_time`, earlier than the
+    // simulation has reached, by replaying the period from the latest
+    // snapshot before it, or from its start if there is none
+    fn rewind_to(&mut self, current_time: f32) -> Result<(), LinearPLError> {
+        let target = (current_time / self.timestep).floor() as usize;
+        while self.snapshots.last().is_some_and(|s| s.step > target) {
+            self.snapshots.pop();
+        }
+        let from = match self.snapshots.last() {
+            Some(s) => {
+                self.particles.clone_from(&s.particles);
+                self.clock = s.clock;
+                self.last_time = s.last_time;
+                self.rate_owed = s.rate_owed;
+                self.spawned = s.spawned;
+                self.oldest = s.oldest;
+                self.rand_generator = s.rng.clone();
+                self.sim_time = s.step as f32 * self.timestep;
+                s.step
+            }
+            None => {
+                self.restart()?;
+                0
+            }
+        };
+        for _ in from..target {
+            self.step(self.timestep)?;
+            self.keep_snapshot();
+        }
+        Ok(())
+    }
+
+    // snapshot a rewinding system every SNAPSHOT_STEPS fixed steps it
+    // plays through for the first time
+    fn keep_snapshot(&mut self) {
+        if !self.rewind {
+            return;
+        }
+        let step = (self.sim_time / self.timestep).round() as usize;
+        if !step.is_multiple_of(SNAPSHOT_STEPS)
+            || self.snapshots.last().is_some_and(|s| s.step >= step)
+        {
+            return;
+        }
+        self.snapshots.push(Snapshot {
+            step,
+            particles: self.particles.clone(),
+            clock: self.clock,
+            last_time: self.last_time,
+            rate_owed: self.rate_owed,
+            spawned: self.spawned,
+            oldest: self.oldest,
+            rng: self.rand_generator.clone(),
+        });
+    }
+
+    // spawn as `simulate()` does, leaving the particles to be aged by the
+    // AgingJob taken by the group, step by step
+    #[cfg(feature = "parallel")]
+    fn spawn_deferred(&mut self, current_time: f32) -> Result<(), LinearPLError> {
+        let mut particles = std::mem::take(&mut self.particles);
+        let spawned = self.spawn(current_time, &mut particles);
+        self.particles = particles;
+        spawned?;
+        self.deferred.push((self.clock, self.particles.len()));
+        Ok(())
+    }
+
+    // age particles spawned for a group that never took their AgingJob
+    #[cfg(feature = "parallel")]
+    fn age_deferred(&mut self) {
+        if let Some(job) = ParticleSys::aging_job(self) {
+            job.run();
+        }
+    }
+
+    /// Return `true` if the particles of the system are aged across
+    /// threads with the `parallel` feature, whether it runs on its own or
+    /// as part of a group. Forces and `with_update()` closures can't be
+    /// shared across threads, so systems with either are aged on the
+    /// calling thread. Within groups, so are the particles of systems with
+    /// `with_max_particles()` or `with_rewind(true)`, and this returns
+    /// `false` for those as well.
+    #[cfg(feature = "parallel")]
+    pub fn ages_in_parallel(&self) -> bool {
+        self.forces.is_empty()
+            && self.on_update.is_none()
+            && self.max_particles.is_none()
+            && !self.rewind
+    }
+
+    // advance the clock to `current_time` without spawning or aging,
+    // dropping the live particles, for systems culled without simulation
+    fn skip(&mut self, current_time: f32) {
+        self.advance_clock(current_time);
+        self.rate_owed = 0.;
+        self.particles.clear();
+        self.oldest = 0;
+    }
+
+    // scales of the density and size of spawned particles at the level of
+    // detail for the distance from the camera to the emitter line
+    fn lod_scales(&self) -> (f32, f32) {
+        let (Some(lod), Some(camera)) = (self.lod, self.camera) else {
+            return (1., 1.);
+        };
+        let m = self.transform.unwrap_or(Mat4::IDENTITY);
+        let (start, end) = self.line();
+        let start = m.transform_point3(start);
+        let line = m.transform_point3(end) - start;
+        let ratio = match line.length_squared() {
+            l if l > 0. => ((camera - start).dot(line) / l).clamp(0., 1.),
+            _ => 0.,
+        };
+        lod.scales(camera.distance(start + line * ratio))
+    }
+
+    // scales of the density and size of particles spawned, from the
+    // level of detail and the modulation of the system and its groups
+    fn spawn_scales(&self) -> (f32, f32) {
+        let (density, size) = self.lod_scales();
+        let m = self.modulated.within(current_modulation());
+        (density * m.density, size * m.size)
+    }
+
+    // whether the system is culled by the current camera, accounting for
+    // its transform and any pushed by its group. Headless builds have no
+    // camera and cull nothing.
+    fn is_culled(&self) -> bool {
+        let Some(culling) = self.culling.filter(|_| !HEADLESS) else {
+            return false;
+        };
+        let m = current_transform() * self.transform.unwrap_or(Mat4::IDENTITY);
+        let view = match self.screen {
+            Some(_) => screen_projection(),
+            None => view_projection(),
+        };
+        !culling.is_visible(&self.bounds().transformed(&m), &view)
+    }
+
+    // move the ends of the line to the points of the screen they are
+    // anchored to, if in screen space
+    fn place_on_screen(&mut self) {
+        if let Some((start, end)) = self.screen {
+            let size = screen_size();
+            self.set_start_end(start.resolve(size), end.resolve(size));
+        }
+    }
+
+    // ends of the line particles spawn along, moved by the anchor
+    fn line(&self) -> (Vec3, Vec3) {
+        (
+            self.start_location + self.anchor,
+            self.end_location + self.anchor,
+        )
+    }
+
+    // sample the object followed, if any, returning the anchor to move
+    // from and to over the steps of the frame
+    fn follow_target(&mut self) -> (Vec3, Vec3) {
+        let from = self.anchor;
+        (from, self.follow.as_ref().map_or(from, |f| f.call()))
+    }
+
+    // move the anchor `step` of `steps` of the way between the ends of
+    // the frame, so particles spawned on each step trail smoothly behind
+    // a fast moving object
+    fn follow_step(&mut self, (from, to): (Vec3, Vec3), step: usize, steps: usize) {
+        self.anchor = from.lerp(to, step as f32 / steps.max(1) as f32);
+    }
+
+    // step to `time` (or the internal clock if `None`) as `next_frame()`
+    // does, but push the particles spawned onto `out` rather than keeping
+    // them. Used by LinearGrp to gather particles into a shared buffer.
+    fn emit(&mut self, time: Option<f32>, out: &mut Vec<Particle>) -> Result<usize, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        self.modulated = self.modulation.sample();
+        let follow = self.follow_target();
+        let steps = self.steps_to(current_time);
+        let mut count = 0;
+        for i in 0..steps {
+            self.follow_step(follow, i + 1, steps);
+            self.sim_time += self.timestep;
+            count += self.spawn(self.track_time(self.sim_time), out)?;
+        }
+        self.anchor = follow.1;
+        Ok(count)
+    }
+
+    // time through the tracks `t` seconds into the current cycle
+    fn track_time(&self, t: f32) -> f32 {
+        match self.mode.is_reversed(self.cycle) {
+            true => self.period - t,
+            false => t,
+        }
+    }
+
+    // number of fixed steps to simulate to catch up to `current_time`,
+    // starting over from the beginning of the period once it has looped
+    fn steps_to(&mut self, current_time: f32) -> usize {
+        if current_time < self.sim_time {
+            self.sim_time = 0.;
+        }
+        let behind = ((current_time - self.sim_time) / self.timestep).floor() as usize;
+        if behind > MAX_STEPS {
+            self.sim_time = current_time - MAX_STEPS as f32 * self.timestep;
+            return MAX_STEPS;
+        }
+        behind
+    }
+
+    /// Step the simulation `dt` seconds further through the period,
+    /// spawning and aging particles without drawing them. This is what
+    /// `ParticleSys::update()` does in fixed steps each frame. Returns
+    /// `true` while still within the period.
+    pub fn step(&mut self, dt: f32) -> Result<bool, LinearPLError> {
+        self.sim_time += dt;
+        self.simulate(self.track_time(self.sim_time))?;
+        Ok(self.sim_time <= self.period)
+    }
+
+    // draw the live particles, applying the transform if one is set
+    // and the blend mode
+    fn draw_particles(&mut self) {
+        if HEADLESS || self.is_culled() {
+            return;
+        }
+        if self.screen.is_some() {
+            begin_screen();
+        }
+        let tint = self.modulated.tinted(None);
+        begin_draw(self.transform, tint);
+        begin_blend(self.blend);
+        self.renderers
+            .draw(self.render_mode, self.blend, &mut self.particles);
+        end_blend(self.blend);
+        end_draw(self.transform, tint);
+        if self.screen.is_some() {
+            end_screen();
+        }
+    }
+
+    // pre-simulate `warmup` seconds of the loop with synthetic timestamps
+    fn warm_up(&mut self) -> Result<(), LinearPLError> {
+        if self.period <= 0. {
+            return Ok(());
+        }
+        let steps = (self.warmup / self.timestep).ceil() as usize;
+        for i in 0..steps {
+            let t = i as f32 * self.timestep - self.warmup;
+            let t = match self.mode {
+                LoopMode::PingPong => {
+                    let t = t.rem_euclid(2. * self.period);
+                    if t > self.period {
+                        2. * self.period - t
+                    } else {
+                        t
+                    }
+                }
+                _ => t.rem_euclid(self.period),
+            };
+            self.simulate(t)?;
+        }
+        Ok(())
+    }
+
+    /// Return self (consuming it) with decay `d`.
+    pub fn with_decay(mut self, d: f32) -> Result<Self, LinearPLError> {
+        self.set_decay(d)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with particles fading in over `f` seconds.
+    pub fn with_fade_in(mut self, f: f32) -> Result<Self, LinearPLError> {
+        self.set_fade_in(f)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with particles fading along `curve`.
+    pub fn with_fade_curve(mut self, curve: FadeCurve) -> Self {
+        self.set_fade_curve(curve);
+        self
+    }
+
+    /// Return self (consuming it) with `w` seconds of warm-up. When
+    /// started with a repeating `LoopMode`, the system pre-simulates `w` seconds of its loop
+    /// so the first visible frame already contains decaying particles.
+    pub fn with_warmup(mut self, w: f32) -> Result<Self, LinearPLError> {
+        check_warmup(w)?;
+        self.warmup = w;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with locations `l`.
+    pub fn with_locations(mut self, l: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_locations(l)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with densities `d`.
+    pub fn with_densities(mut self, d: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_densities(d)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with rates `r`.
+    pub fn with_rates(mut self, r: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_rates(r)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with decays `d`.
+    pub fn with_decays(mut self, d: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_decays(d)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with colors `c`.
+    pub fn with_colors(mut self, c: &[Color]) -> Result<Self, LinearPLError> {
+        self.set_colors(c)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with sizes `s`.
+    pub fn with_sizes(mut self, s: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_sizes(s)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with locations keyed at times `k`.
+    pub fn with_location_keys(mut self, k: &[(f32, f32)]) -> Result<Self, LinearPLError> {
+        self.set_location_keys(k)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with densities keyed at times `k`.
+    pub fn with_density_keys(mut self, k: &[(f32, f32)]) -> Result<Self, LinearPLError> {
+        self.set_density_keys(k)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with colors keyed at times `k`.
+    pub fn with_color_keys(mut self, k: &[(f32, Color)]) -> Result<Self, LinearPLError> {
+        self.set_color_keys(k)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with sizes keyed at times `k`.
+    pub fn with_size_keys(mut self, k: &[(f32, f32)]) -> Result<Self, LinearPLError> {
+        self.set_size_keys(k)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with the locations track given by `i`.
+    pub fn with_location_interpolator(mut self, i: impl Interpolator<f32> + 'static) -> Self {
+        self.set_location_interpolator(i);
+        self
+    }
+
+    /// Return self (consuming it) with the densities track given by `i`.
+    pub fn with_density_interpolator(mut self, i: impl Interpolator<f32> + 'static) -> Self {
+        self.set_density_interpolator(i);
+        self
+    }
+
+    /// Return self (consuming it) with the colors track given by `i`.
+    pub fn with_color_interpolator(mut self, i: impl Interpolator<Color> + 'static) -> Self {
+        self.set_color_interpolator(i);
+        self
+    }
+
+    /// Return self (consuming it) with the sizes track given by `i`.
+    pub fn with_size_interpolator(mut self, i: impl Interpolator<f32> + 'static) -> Self {
+        self.set_size_interpolator(i);
+        self
+    }
+
+    /// Return self (consuming it) with size curve `c`.
+    pub fn with_size_curve(mut self, c: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_size_curve(c)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with start-location `sl`, ending location `el.
+    pub fn with_start_end<P: Position>(mut self, sl: P, el: P) -> Result<Self, LinearPLError> {
+        self.set_start_end(sl, el);
+        Ok(self)
+    }
+
+    /// Set the decay to `d`. May be called while the system is active,
+    /// affecting only particles spawned afterwards.
+    pub fn set_decay(&mut self, d: f32) -> Result<(), LinearPLError> {
+        check_decay(d)?;
+        self.decays.set_values(&[d]);
+        Ok(())
+    }
+
+    /// Set particles to fade in over the first `f` seconds of their
+    /// decay rather than appearing at full brightness. May be called while
+    /// the system is active, affecting only particles spawned afterwards.
+    pub fn set_fade_in(&mut self, f: f32) -> Result<(), LinearPLError> {
+        check_fade(f)?;
+        self.fade_in = f;
+        Ok(())
+    }
+
+    /// Set the curve particles fade in and out along to `curve`. May be
+    /// called while the system is active, affecting only particles
+    /// spawned afterwards.
+    pub fn set_fade_curve(&mut self, curve: FadeCurve) {
+        self.fade_curve = curve;
+    }
+
+    /// Set the Validation `v` deciding whether out-of-range locations
+    /// and densities set afterwards are rejected or clamped into range,
+    /// each clamped value reported in `diagnostics()`.
+    pub fn set_validation(&mut self, v: Validation) {
+        self.validation = v;
+    }
+
+    /// Return self (consuming it) with out-of-range locations and
+    /// densities handled by `v`.
+    pub fn with_validation(mut self, v: Validation) -> Self {
+        self.set_validation(v);
+        self
+    }
+
+    /// Return the values clamped into range so far under
+    /// `Validation::Clamp`, with the error each would have raised.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    // `values` checked by `check`, or clamped into `0` to `1` with a
+    // Diagnostic noted for each out-of-range value if validation clamps
+    fn validated(
+        &mut self,
+        values: &[f32],
+        check: fn(&[f32]) -> Result<(), LinearPLError>,
+    ) -> Result<Vec<f32>, LinearPLError> {
+        if self.validation == Validation::Strict || values.iter().any(|v| !v.is_finite()) {
+            check(values)?;
+            return Ok(values.into());
+        }
+        let clamped: Vec<f32> = values.iter().map(|v| v.clamp(0., 1.)).collect();
+        check(&clamped)?;
+        for (v, c) in values.iter().zip(&clamped) {
+            if let Err(error) = check(&[*v]) {
+                self.diagnostics.push(Diagnostic {
+                    error,
+                    clamped_to: *c,
+                });
+            }
+        }
+        Ok(clamped)
+    }
+
+    /// Set the locations track to `l`. May be called while the system
+    /// is active, affecting only particles spawned afterwards.
+    pub fn set_locations(&mut self, l: &[f32]) -> Result<(), LinearPLError> {
+        let l = self.validated(l, check_locations)?;
+        self.locations.set_values(&l);
+        Ok(())
+    }
+
+    /// Set the densities track to `d`. May be called while the system
+    /// is active.
+    pub fn set_densities(&mut self, d: &[f32]) -> Result<(), LinearPLError> {
+        let d = self.validated(d, check_densities)?;
+        self.densities.set_values(&d);
+        self.by_rate = false;
+        Ok(())
+    }
+
+    /// Set the rates track to `r`, the number of particles spawned per
+    /// second, in place of the per-frame chance of the densities track.
+    /// Fractions of a particle carry over between frames so the same
+    /// number are spawned at any framerate. Setting densities again
+    /// switches back to them. May be called while the system is active.
+    pub fn set_rates(&mut self, r: &[f32]) -> Result<(), LinearPLError> {
+        check_rates(r)?;
+        self.rates.set_values(r);
+        self.by_rate = true;
+        Ok(())
+    }
+
+    /// Set the decays track to `d`, the time particles spawned at each
+    /// point of the period are drawn for, so that those spawned early can
+    /// outlive those spawned later or the other way around. May be called
+    /// while the system is active, affecting only particles spawned
+    /// afterwards.
+    pub fn set_decays(&mut self, d: &[f32]) -> Result<(), LinearPLError> {
+        check_decays(d)?;
+        self.decays.set_values(d);
+        Ok(())
+    }
+
+    /// Set the colors track to `c`. May be called while the system is
+    /// active, affecting only particles spawned afterwards.
+    pub fn set_colors(&mut self, c: &[Color]) -> Result<(), LinearPLError> {
+        check_colors(c)?;
+        self.colors.set_values(c);
+        Ok(())
+    }
+
+    // options of the `kind` track
+    fn track_options_mut(&mut self, kind: TrackKind) -> &mut TrackOptions {
+        match kind {
+            TrackKind::Locations => &mut self.locations.options,
+            TrackKind::Densities => &mut self.densities.options,
+            TrackKind::Colors => &mut self.colors.options,
+            TrackKind::Sizes => &mut self.sizes.options,
+            TrackKind::Decays => &mut self.decays.options,
+            TrackKind::Rates => &mut self.rates.options,
+            TrackKind::Directions => &mut self.directions.options,
+            TrackKind::Spreads => &mut self.spreads.options,
+        }
+    }
+
+    /// Set the Easing `easing` shaping the blend between neighbouring
+    /// values of the `kind` track. May be called while the system is
+    /// active.
+    pub fn set_easing(&mut self, kind: TrackKind, easing: Easing) {
+        self.track_options_mut(kind).easings = vec![easing];
+    }
+
+    /// Return self (consuming it) with the `kind` track blended along
+    /// `easing`.
+    pub fn with_easing(mut self, kind: TrackKind, easing: Easing) -> Self {
+        self.set_easing(kind, easing);
+        self
+    }
+
+    /// Set the Easing of each segment between neighbouring values of the
+    /// `kind` track in turn to `easings`, such as a different
+    /// `Easing::CubicBezier` per pair of keys, with the last carrying on
+    /// for any remaining segments. May be called while the system is
+    /// active.
+    pub fn set_segment_easings(&mut self, kind: TrackKind, easings: &[Easing]) {
+        self.track_options_mut(kind).easings = easings.into();
+    }
+
+    /// Return self (consuming it) with the segments of the `kind` track
+    /// blended along `easings` in turn.
+    pub fn with_segment_easings(mut self, kind: TrackKind, easings: &[Easing]) -> Self {
+        self.set_segment_easings(kind, easings);
+        self
+    }
+
+    /// Set the TrackMode `mode` the `kind` track moves between its values
+    /// by, such as `TrackMode::CatmullRom` to sweep smoothly through them.
+    /// May be called while the system is active.
+    pub fn set_track_mode(&mut self, kind: TrackKind, mode: TrackMode) {
+        self.track_options_mut(kind).mode = mode;
+    }
+
+    /// Return self (consuming it) with the `kind` track moving between
+    /// its values by `mode`.
+    pub fn with_track_mode(mut self, kind: TrackKind, mode: TrackMode) -> Self {
+        self.set_track_mode(kind, mode);
+        self
+    }
+
+    /// Set the Noise `noise` overlaid on the `kind` track, such as
+    /// flickering densities, shimmering colors or wobbling locations,
+    /// or `None` to remove it. May be called while the system is active.
+    pub fn set_noise(
+        &mut self,
+        kind: TrackKind,
+        noise: Option<Noise>,
+    ) -> Result<(), LinearPLError> {
+        if let Some(n) = noise {
+            check_noise(n.amplitude, n.frequency)?;
+        }
+        self.track_options_mut(kind).noise = noise;
+        Ok(())
+    }
+
+    /// Return self (consuming it) with `noise` overlaid on the `kind` track.
+    pub fn with_noise(mut self, kind: TrackKind, noise: Noise) -> Result<Self, LinearPLError> {
+        self.set_noise(kind, Some(noise))?;
+        Ok(self)
+    }
+
+    /// Set whether the `kind` track blends from its last value back to
+    /// its first by the end of the period rather than stopping at it,
+    /// so looping systems carry smoothly across each loop boundary
+    /// without repeating the first value at the end. The values are then
+    /// spread evenly over the whole period, the seam included. May be
+    /// called while the system is active.
+    pub fn set_seamless(&mut self, kind: TrackKind, seamless: bool) {
+        self.track_options_mut(kind).seamless = seamless;
+    }
+
+    /// Return self (consuming it) with the `kind` track blending across
+    /// loop boundaries if `seamless`.
+    pub fn with_seamless(mut self, kind: TrackKind, seamless: bool) -> Self {
+        self.set_seamless(kind, seamless);
+        self
+    }
+
+    /// Set the Edge `edge` deciding what the `kind` track gives for times
+    /// outside of the period, such as the end of particles spawned right
+    /// at its close: clamped to the last value, extrapolated past it or
+    /// wrapped back to the start. May be called while the system is
+    /// active.
+    pub fn set_edge(&mut self, kind: TrackKind, edge: Edge) {
+        self.track_options_mut(kind).edge = edge;
+    }
+
+    /// Return self (consuming it) with the `kind` track handling times
+    /// outside of the period by `edge`.
+    pub fn with_edge(mut self, kind: TrackKind, edge: Edge) -> Self {
+        self.set_edge(kind, edge);
+        self
+    }
+
+    /// Set the sizes track to `s`, the thickness of particles in world
+    /// units, where `0` draws a hairline. May be called while the system
+    /// is active, affecting only particles spawned afterwards.
+    pub fn set_sizes(&mut self, s: &[f32]) -> Result<(), LinearPLError> {
+        check_sizes(s)?;
+        self.sizes.set_values(s);
+        Ok(())
+    }
+
+    /// Set the locations track to keys `k`, each a normalized time
+    /// from `0` to `1` through the period and the value at that time.
+    /// Between keys values are blended as with evenly spaced tracks, so
+    /// `[(0., 0.), (0.8, 0.1), (1., 1.)]` creeps along the line before
+    /// sweeping to its end. May be called while the system is active,
+    /// affecting only particles spawned afterwards.
+    pub fn set_location_keys(&mut self, k: &[(f32, f32)]) -> Result<(), LinearPLError> {
+        let values: Vec<f32> = k.iter().map(|(_, v)| *v).collect();
+        let times: Vec<f32> = k.iter().map(|(t, _)| *t).collect();
+        check_key_times(&times)?;
+        let values = self.validated(&values, check_locations)?;
+        let keys: Vec<(f32, f32)> = times.into_iter().zip(values).collect();
+        self.locations.set_keys(&keys);
+        Ok(())
+    }
+
+    /// Set the densities track to keys `k` of normalized times and values,
+    /// as with `set_location_keys()`.
+    pub fn set_density_keys(&mut self, k: &[(f32, f32)]) -> Result<(), LinearPLError> {
+        let values: Vec<f32> = k.iter().map(|(_, v)| *v).collect();
+        let times: Vec<f32> = k.iter().map(|(t, _)| *t).collect();
+        check_key_times(&times)?;
+        let values = self.validated(&values, check_densities)?;
+        let keys: Vec<(f32, f32)> = times.into_iter().zip(values).collect();
+        self.densities.set_keys(&keys);
+        self.by_rate = false;
+        Ok(())
+    }
+
+    /// Set the colors track to keys `k` of normalized times and values,
+    /// as with `set_location_keys()`.
+    pub fn set_color_keys(&mut self, k: &[(f32, Color)]) -> Result<(), LinearPLError> {
+        let values: Vec<Color> = k.iter().map(|(_, v)| *v).collect();
+        let times: Vec<f32> = k.iter().map(|(t, _)| *t).collect();
+        check_colors(&values)?;
+        check_key_times(&times)?;
+        self.colors.set_keys(k);
+        Ok(())
+    }
+
+    /// Set the sizes track to keys `k` of normalized times and values,
+    /// as with `set_location_keys()`.
+    pub fn set_size_keys(&mut self, k: &[(f32, f32)]) -> Result<(), LinearPLError> {
+        let values: Vec<f32> = k.iter().map(|(_, v)| *v).collect();
+        let times: Vec<f32> = k.iter().map(|(t, _)| *t).collect();
+        check_sizes(&values)?;
+        check_key_times(&times)?;
+        self.sizes.set_keys(k);
+        Ok(())
+    }
+
+    /// Set the locations track to the custom Interpolator `i`, such as
+    /// a closure from the ratio through the period to a location, in
+    /// place of its values. It is used until the track is next given
+    /// values or keys. May be called while the system is active,
+    /// affecting only particles spawned afterwards.
+    pub fn set_location_interpolator(&mut self, i: impl Interpolator<f32> + 'static) {
+        self.locations.set_interpolator(Rc::new(i));
+    }
+
+    /// Set the densities track to the custom Interpolator `i`, as with
+    /// `set_location_interpolator()`.
+    pub fn set_density_interpolator(&mut self, i: impl Interpolator<f32> + 'static) {
+        self.densities.set_interpolator(Rc::new(i));
+        self.by_rate = false;
+    }
+
+    /// Set the colors track to the custom Interpolator `i`, as with
+    /// `set_location_interpolator()`.
+    pub fn set_color_interpolator(&mut self, i: impl Interpolator<Color> + 'static) {
+        self.colors.set_interpolator(Rc::new(i));
+    }
+
+    /// Set the sizes track to the custom Interpolator `i`, as with
+    /// `set_location_interpolator()`.
+    pub fn set_size_interpolator(&mut self, i: impl Interpolator<f32> + 'static) {
+        self.sizes.set_interpolator(Rc::new(i));
+    }
+
+    /// Set the size curve to `c`, scaling each particle's size over its
+    /// own decay time rather than over the period like `sizes`. For
+    /// example `[0.5, 1.5, 0.]` grows each particle then shrinks it away.
+    /// May be called while the system is active.
+    pub fn set_size_curve(&mut self, c: &[f32]) -> Result<(), LinearPLError> {
+        check_size_curve(c)?;
+        self.size_curve = c.into();
+        Ok(())
+    }
+
+    /// Set the start-location to `sl` and ending location to `el`. May be
+    /// called while the system is active, affecting only particles
+    /// spawned afterwards.
+    pub fn set_start_end<P: Position>(&mut self, sl: P, el: P) {
+        self.start_location = sl.to_vec3();
+        self.end_location = el.to_vec3();
+    }
+
+    /// Set the initial velocity of spawned particles to `v` units per
+    /// second, with each component varied randomly by up to `spread`.
+    /// May be called while the system is active, affecting only
+    /// particles spawned afterwards.
+    pub fn set_velocity<P: Position>(&mut self, v: P, spread: f32) -> Result<(), LinearPLError> {
+        check_spread(spread)?;
+        self.velocity = v.to_vec3();
+        self.velocity_spread = spread;
+        Ok(())
+    }
+
+    /// Return self (consuming it) with initial velocity `v` varied
+    /// randomly by up to `spread`.
+    pub fn with_velocity<P: Position>(mut self, v: P, spread: f32) -> Result<Self, LinearPLError> {
+        self.set_velocity(v, spread)?;
+        Ok(self)
+    }
+
+    /// Set the bursts of particles spawned at once to `b`, each a time in
+    /// seconds into the period and the exact number of particles spawned
+    /// as it passes, such as an impact or a heartbeat pulse. Bursts are
+    /// spawned on top of the densities or rates tracks; set densities to
+    /// `[0.]` to emit only bursts. May be called while the system is
+    /// active.
+    pub fn set_bursts(&mut self, b: &[(f32, usize)]) -> Result<(), LinearPLError> {
+        check_bursts(b)?;
+        self.bursts = b.into();
+        Ok(())
+    }
+
+    /// Return self (consuming it) with bursts `b`.
+    pub fn with_bursts(mut self, b: &[(f32, usize)]) -> Result<Self, LinearPLError> {
+        self.set_bursts(b)?;
+        Ok(self)
+    }
+
+    /// Set the directions track to `d`, sweeping the direction particles
+    /// are launched in over the period while keeping the speed given by
+    /// `set_velocity`. A zero direction keeps the velocity as it is. May
+    /// be called while the system is active, affecting only particles
+    /// spawned afterwards.
+    pub fn set_directions<P: Position>(&mut self, d: &[P]) -> Result<(), LinearPLError> {
+        let d: Vec<Vec3> = d.iter().map(|v| v.to_vec3()).collect();
+        check_directions(&d)?;
+        self.directions.set_values(&d);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with directions `d`.
+    pub fn with_directions<P: Position>(mut self, d: &[P]) -> Result<Self, LinearPLError> {
+        self.set_directions(d)?;
+        Ok(self)
+    }
+
+    /// Set particles to spawn offset from the line by a random distance
+    /// of up to `radius`, perpendicular to it, forming a cloud around the
+    /// line rather than sitting exactly on it. May be called while the
+    /// system is active, affecting only particles spawned afterwards.
+    pub fn set_spread(&mut self, radius: f32) -> Result<(), LinearPLError> {
+        check_spread(radius)?;
+        self.spreads.set_values(&[radius]);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with particles spread up to `radius`
+    /// around the line.
+    pub fn with_spread(mut self, radius: f32) -> Result<Self, LinearPLError> {
+        self.set_spread(radius)?;
+        Ok(self)
+    }
+
+    /// Set the spreads track to `s`, the radius particles spawned at each
+    /// point of the period are offset from the line by, so that an effect
+    /// can start tight and grow more chaotic or the other way around. May
+    /// be called while the system is active, affecting only particles
+    /// spawned afterwards.
+    pub fn set_spreads(&mut self, s: &[f32]) -> Result<(), LinearPLError> {
+        check_spreads(s)?;
+        self.spreads.set_values(s);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with spreads `s`.
+    pub fn with_spreads(mut self, s: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_spreads(s)?;
+        Ok(self)
+    }
+
+    /// Set the gravity `g` accelerating particles in units per second
+    /// squared. May be called while the system is active, affecting
+    /// only particles spawned afterwards.
+    pub fn set_gravity<P: Position>(&mut self, g: P) {
+        self.gravity = g.to_vec3();
+    }
+
+    /// Return self (consuming it) with gravity `g`.
+    pub fn with_gravity<P: Position>(mut self, g: P) -> Self {
+        self.set_gravity(g);
+        self
+    }
+
+    /// Set the drag coefficient `k` damping the velocity of particles
+    /// exponentially over their lifetime, so they decelerate rather than
+    /// flying off at a constant speed. May be called while the system is
+    /// active, affecting only particles spawned afterwards.
+    pub fn set_drag(&mut self, k: f32) -> Result<(), LinearPLError> {
+        check_drag(k)?;
+        self.drag = k;
+        Ok(())
+    }
+
+    /// Return self (consuming it) with drag coefficient `k`.
+    pub fn with_drag(mut self, k: f32) -> Result<Self, LinearPLError> {
+        self.set_drag(k)?;
+        Ok(self)
+    }
+
+    /// Set spawned particles to a random mass between `min` and `max`.
+    /// Forces and drag are divided by the mass, so mixed-weight particles
+    /// respond differently to the same wind. May be called while the
+    /// system is active, affecting only particles spawned afterwards.
+    pub fn set_mass(&mut self, min: f32, max: f32) -> Result<(), LinearPLError> {
+        check_mass(min, max)?;
+        self.mass = (min, max);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with particle masses between `min`
+    /// and `max`.
+    pub fn with_mass(mut self, min: f32, max: f32) -> Result<Self, LinearPLError> {
+        self.set_mass(min, max)?;
+        Ok(self)
+    }
+
+    /// Stretch moving particles along their velocity over `s` seconds
+    /// of motion, drawing proper streaks for fast particles in place of
+    /// the short segment along the line. May be called while the system
+    /// is active, affecting only particles spawned afterwards.
+    pub fn set_stretch(&mut self, s: f32) -> Result<(), LinearPLError> {
+        check_stretch(s)?;
+        self.stretch = s;
+        Ok(())
+    }
+
+    /// Return self (consuming it) stretching particles over `s` seconds
+    /// of motion.
+    pub fn with_stretch(mut self, s: f32) -> Result<Self, LinearPLError> {
+        self.set_stretch(s)?;
+        Ok(self)
+    }
+
+    /// Set the angular velocity of spawned particles to `s` radians per
+    /// second, varied randomly by up to `spread`. Spinning particles start
+    /// at a random rotation around a random axis. May be called while the
+    /// system is active, affecting only particles spawned afterwards.
+    pub fn set_spin(&mut self, s: f32, spread: f32) -> Result<(), LinearPLError> {
+        check_spread(spread)?;
+        self.spin = s;
+        self.spin_spread = spread;
+        Ok(())
+    }
+
+    /// Return self (consuming it) with angular velocity `s` varied
+    /// randomly by up to `spread`.
+    pub fn with_spin(mut self, s: f32, spread: f32) -> Result<Self, LinearPLError> {
+        self.set_spin(s, spread)?;
+        Ok(self)
+    }
+
+    /// Set each moving particle to keep its last `n` positions, up to
+    /// `linearpl::particle::MAX_TRAIL`, drawn as a trail fading out
+    /// behind it. May be called while the system is active, affecting
+    /// only particles spawned afterwards.
+    pub fn set_trail(&mut self, n: usize) {
+        self.trail = n;
+    }
+
+    /// Return self (consuming it) with particles keeping trails of `n`
+    /// positions.
+    pub fn with_trail(mut self, n: usize) -> Self {
+        self.set_trail(n);
+        self
+    }
+
+    /// Set a hard cap of `n` live particles, each one spawned past the
+    /// cap taking the place of the oldest, and preallocate room for them
+    /// so the system never grows its buffer past the cap. May be called
+    /// while the system is active.
+    pub fn set_max_particles(&mut self, n: usize) {
+        self.max_particles = Some(n);
+        self.particles
+            .reserve(n.saturating_sub(self.particles.len()));
+    }
+
+    /// Return self (consuming it) with at most `n` live particles.
+    pub fn with_max_particles(mut self, n: usize) -> Self {
+        self.set_max_particles(n);
+        self
+    }
+
+    /// Set the Culling `c` deciding when the system is skipped for being
+    /// off screen or too far from the camera.
+    pub fn set_culling(&mut self, c: Culling) {
+        self.culling = Some(c);
+    }
+
+    /// Return self (consuming it) culled by `c`.
+    pub fn with_culling(mut self, c: Culling) -> Self {
+        self.set_culling(c);
+        self
+    }
+
+    /// Set the level of detail `lod` scaling density and sizes down with
+    /// the distance from the camera given to `run_with_camera()`. Returns
+    /// an error if its distances or scales are out of range.
+    pub fn set_lod(&mut self, lod: Lod) -> Result<(), LinearPLError> {
+        check_lod(&lod)?;
+        self.lod = Some(lod);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with the level of detail `lod`.
+    pub fn with_lod(mut self, lod: Lod) -> Result<Self, LinearPLError> {
+        self.set_lod(lod)?;
+        Ok(self)
+    }
+
+    /// Display the next frame as `ParticleSys::run()` does, seen from
+    /// `camera` for the level of detail set by `with_lod()`. `camera` is
+    /// registered with `render::register_camera()` as well, so that
+    /// billboards face it.
+    #[cfg(feature = "macroquad")]
+    pub fn run_with_camera(&mut self, camera: &Camera3D) -> Result<bool, LinearPLError> {
+        self.camera = Some(camera.position);
+        register_camera(camera);
+        self.run()
+    }
+
+    /// Return the Bounds around the system's line and live particles,
+    /// before its transform.
+    pub fn bounds(&self) -> Bounds {
+        let (start, end) = self.line();
+        self.particles.iter().fold(Bounds::new(start, end), |b, p| {
+            b.including(p.location()).including(p.end_location())
+        })
+    }
+
+    /// Return the point of the line particles are currently spawned at,
+    /// before its transform, or `None` if the system isn't active within
+    /// its period.
+    pub fn emission_point(&self) -> Option<Vec3> {
+        if !self.active || self.sim_time > self.period {
+            return None;
+        }
+        let (start, end) = self.line();
+        map_location(
+            &self.locations,
+            start,
+            end,
+            self.track_time(self.sim_time),
+            self.period,
+        )
+        .ok()
+    }
+
+    /// Hand the primitives of every live particle, before the system's
+    /// transform and blend mode, to `renderer` and flush it, such as to
+    /// draw them with another library or check them with a
+    /// `MockRenderer`.
+    pub fn draw_to(&mut self, renderer: &mut dyn ParticleRenderer) {
+        for p in self.particles.iter_mut() {
+            p.draw_to(renderer);
+        }
+        renderer.flush();
+    }
+
+    /// Set the seconds `dt` simulated by each fixed step. Smaller steps
+    /// follow forces and collisions more closely at the cost of more
+    /// steps each frame. Returns an error if `dt` is not positive.
+    pub fn set_timestep(&mut self, dt: f32) -> Result<(), LinearPLError> {
+        check_timestep(dt)?;
+        self.timestep = dt;
+        Ok(())
+    }
+
+    /// Return self (consuming it) simulated in fixed steps of `dt`.
+    pub fn with_timestep(mut self, dt: f32) -> Result<Self, LinearPLError> {
+        self.set_timestep(dt)?;
+        Ok(self)
+    }
+
+    /// Set the seed of the system's random spawn decisions, such as
+    /// densities, spreads and masses, so that it spawns the exact same
+    /// particles every time it is started. Clones of a seeded system
+    /// share its seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rand_generator = SysRng::seeded(seed);
+    }
+
+    /// Return self (consuming it) with random spawn decisions seeded by
+    /// `seed`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.set_seed(seed);
+        self
+    }
+
+    /// Set whether the system plays backwards faithfully when its time
+    /// decreases, such as when scrubbed in an editor, rather than
+    /// carrying on from the particles it has. Rewinding replays the
+    /// period from the latest snapshot before the earlier time, taken
+    /// every 32 fixed steps on the way forward, to rebuild the particles
+    /// alive then, which are the same ones only if the system is seeded.
+    /// A rewind replays no more than 32 steps of time already played
+    /// through, though each snapshot holds a copy of the live particles.
+    /// The `with_on_spawn()` callback runs again for every particle
+    /// respawned by the replay, so callbacks keeping state of their own
+    /// see those particles twice. Off by default.
+    pub fn set_rewind(&mut self, enabled: bool) {
+        self.rewind = enabled;
+    }
+
+    /// Return self (consuming it) playing backwards if `enabled`.
+    pub fn with_rewind(mut self, enabled: bool) -> Self {
+        self.set_rewind(enabled);
+        self
+    }
+
+    /// Return the seed of the system's random spawn decisions, if set.
+    pub fn seed(&self) -> Option<u64> {
+        self.rand_generator.seed()
+    }
+
+    /// Set whether the system gathers PerfCounters of the time spent
+    /// updating and drawing each frame, returned by
+    /// `ParticleSys::perf_counters()`. Off by default.
+    pub fn set_perf_counters(&mut self, enabled: bool) {
+        self.perf = enabled.then(PerfCounters::default);
+    }
+
+    /// Return self (consuming it) gathering PerfCounters if `enabled`.
+    pub fn with_perf_counters(mut self, enabled: bool) -> Self {
+        self.set_perf_counters(enabled);
+        self
+    }
+
+    /// Add force `f` accelerating the particles each frame. Forces can't
+    /// be shared across threads, so with the `parallel` feature a system
+    /// with any is aged on a single thread; see `ages_in_parallel()`.
+    pub fn add_force(&mut self, f: impl Force + 'static) {
+        self.forces.push(f);
+    }
+
+    /// Return self (consuming it) with force `f` added.
+    pub fn with_force(mut self, f: impl Force + 'static) -> Self {
+        self.add_force(f);
+        self
+    }
+
+    /// Set the forces accelerating the particles each frame to those of
+    /// `field`, replacing any added before. Applies to live particles too.
+    pub fn set_forces(&mut self, field: ForceField) {
+        self.forces = field;
+    }
+
+    /// Return self (consuming it) with the forces of `field`.
+    pub fn with_forces(mut self, field: ForceField) -> Self {
+        self.set_forces(field);
+        self
+    }
+
+    /// Set the volumes particles collide with each frame to `colliders`,
+    /// replacing any set before. Applies to live particles too.
+    pub fn set_colliders(&mut self, colliders: Colliders) {
+        self.colliders = colliders;
+    }
+
+    /// Return self (consuming it) colliding with `colliders`.
+    pub fn with_colliders(mut self, colliders: Colliders) -> Self {
+        self.set_colliders(colliders);
+        self
+    }
+
+    /// Set the shape particles are drawn as to `shape`. May be called
+    /// while the system is active, affecting only particles spawned
+    /// afterwards.
+    pub fn set_shape(&mut self, shape: ParticleShape) {
+        self.shape = shape;
+    }
+
+    /// Return self (consuming it) with particles drawn as `shape`.
+    pub fn with_shape(mut self, shape: ParticleShape) -> Self {
+        self.set_shape(shape);
+        self
+    }
+
+    /// Set particles to draw over a halo `width` wider than themselves at
+    /// `alpha` times their opacity, faking a soft glow. May be called
+    /// while the system is active, affecting only particles spawned
+    /// afterwards.
+    pub fn set_glow(&mut self, width: f32, alpha: f32) -> Result<(), LinearPLError> {
+        check_glow(width, alpha)?;
+        self.glow = (width, alpha);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with a glow `width` wide at opacity `alpha`.
+    pub fn with_glow(mut self, width: f32, alpha: f32) -> Result<Self, LinearPLError> {
+        self.set_glow(width, alpha)?;
+        Ok(self)
+    }
+
+    /// Set the BlendMode `mode` particles are drawn with. May be called
+    /// while the system is active.
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.blend = mode;
+    }
+
+    /// Return self (consuming it) drawn with BlendMode `mode`.
+    pub fn with_blend_mode(mut self, mode: BlendMode) -> Self {
+        self.set_blend_mode(mode);
+        self
+    }
+
+    /// Set the RenderMode `mode` the line segments of particles, their
+    /// trails and thick rings included, are drawn with, such as
+    /// `RenderMode::Batched` to draw them all in a single draw call rather
+    /// than one at a time. Cubes, spheres and other shapes are still drawn
+    /// one by one. May be called while the system is active.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = mode;
+    }
+
+    /// Return self (consuming it) with its lines drawn by `mode`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        self.set_render_mode(mode);
+        self
+    }
+
+    /// Set the transform `m` applied to every particle at draw time, moving,
+    /// rotating or scaling the whole system without changing its endpoints.
+    pub fn set_transform(&mut self, m: Mat4) {
+        self.transform = Some(m);
+    }
+
+    /// Return self (consuming it) with the draw time transform `m`.
+    pub fn with_transform(mut self, m: Mat4) -> Self {
+        self.set_transform(m);
+        self
+    }
+
+    /// Set the draw time transform to `m`, then display the next frame
+    /// as `ParticleSys::run()` does.
+    pub fn run_with_transform(&mut self, m: &Mat4) -> Result<bool, LinearPLError> {
+        self.set_transform(*m);
+        self.run()
+    }
+
+    /// Place the system in screen space, with its line running from
+    /// `start` to `end` as resolved against the size of the window each
+    /// frame. Locations, sizes and velocities are then in pixels, and
+    /// the system draws itself with macroquad's default camera. See
+    /// `linearpl::screen`.
+    pub fn set_screen_space(&mut self, start: ScreenPoint, end: ScreenPoint) {
+        self.screen = Some((start, end));
+        self.place_on_screen();
+    }
+
+    /// Return self (consuming it) in screen space, running from `start`
+    /// to `end`.
+    pub fn with_screen_space(mut self, start: ScreenPoint, end: ScreenPoint) -> Self {
+        self.set_screen_space(start, end);
+        self
+    }
+
+    /// Return `true` if the system is in screen space.
+    pub fn is_screen_space(&self) -> bool {
+        self.screen.is_some()
+    }
+
+    /// Move the line by `anchor`, such as the location of the ship a
+    /// thruster trail is attached to. Only particles spawned afterwards
+    /// are moved, so calling it each frame leaves a trail behind the
+    /// anchor while the tracks keep interpolating along the line.
+    pub fn set_anchor<P: Position>(&mut self, anchor: P) {
+        self.anchor = anchor.to_vec3();
+    }
+
+    /// Return self (consuming it) with the line moved by `anchor`.
+    pub fn with_anchor<P: Position>(mut self, anchor: P) -> Self {
+        self.set_anchor(anchor);
+        self
+    }
+
+    /// Return the offset the line is currently moved by.
+    pub fn anchor(&self) -> Vec3 {
+        self.anchor
+    }
+
+    /// Anchor the line to the location returned by `f`, sampled once
+    /// each frame and interpolated across the steps of the frame, in
+    /// place of calling `set_anchor()` before each `run()`.
+    pub fn set_follow(&mut self, f: impl FnMut() -> Vec3 + 'static) {
+        let follow = FollowCallback::new(f);
+        self.anchor = follow.call();
+        self.follow = Some(follow);
+    }
+
+    /// Return self (consuming it) following the location returned by
+    /// `f`.
+    pub fn with_follow(mut self, f: impl FnMut() -> Vec3 + 'static) -> Self {
+        self.set_follow(f);
+        self
+    }
+
+    /// Return clone of self with decay `d`.
+    pub fn clone_with_decay(&self, d: f32) -> Result<Self, LinearPLError> {
+        self.clone().with_decay(d)
+    }
+
+    /// Return clone self with locations `l`.
+    pub fn clone_with_locations(&self, l: &[f32]) -> Result<Self, LinearPLError> {
+        self.clone().with_locations(l)
+    }
+
+    /// Return clone self with densities `d`.
+    pub fn clone_with_densities(&self, d: &[f32]) -> Result<Self, LinearPLError> {
+        self.clone().with_densities(d)
+    }
+
+    /// Return clone self with colors `c`.
+    pub fn clone_with_colors(&self, c: &[Color]) -> Result<Self, LinearPLError> {
+        self.clone().with_colors(c)
+    }
+
+    /// Return clone self with size curve `c`.
+    pub fn clone_with_size_curve(&self, c: &[f32]) -> Result<Self, LinearPLError> {
+        self.clone().with_size_curve(c)
+    }
+
+    /// Return clone self with sizes `s`.
+    pub fn clone_with_sizes(&self, s: &[f32]) -> Result<Self, LinearPLError> {
+        self.clone().with_sizes(s)
+    }
+
+    /// Return clone self with start-location `sl`, ending location `el`.
+    pub fn clone_with_start_end<P: Position>(&self, sl: P, el: P) -> Result<Self, LinearPLError> {
+        self.clone().with_start_end(sl, el)
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the LinearParticles finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping LinearParticles wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked on each
+    /// Particle right after it is spawned, before it is first drawn.
+    pub fn with_on_spawn(mut self, f: impl FnMut(&mut Particle) + 'static) -> Self {
+        self.on_spawn = Some(SpawnCallback::new(f));
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked on each live
+    /// Particle every frame before it is drawn, with the seconds `dt` it
+    /// was just stepped by and its `age` in seconds. Runs after velocity,
+    /// gravity and forces are applied, so it may override any of them.
+    /// With the `parallel` feature, a system with a callback is aged on a
+    /// single thread; see `ages_in_parallel()`.
+    pub fn with_update(mut self, f: impl FnMut(&mut Particle, f32, f32) + 'static) -> Self {
+        self.on_update = Some(UpdateCallback::new(f));
+        self
+    }
+
+    /// Scale `target` by the signal returned by `f`, such as the current
+    /// amplitude of an audio stream, sampled once each frame. See
+    /// `linearpl::modulation`.
+    pub fn set_modulation(&mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) {
+        self.modulation.set(target, SignalCallback::new(f));
+    }
+
+    /// Return self (consuming it) with `target` scaled by the signal
+    /// returned by `f`.
+    pub fn with_modulation(mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) -> Self {
+        self.set_modulation(target, f);
+        self
+    }
+
+    /// Reverse the LinearParticles tracks and bursts over the period, such
+    /// that the presets defined for each create a reverse of the original
+    /// graphic generated, emitting from the end of its sweep back to its
+    /// start. Bursts past the period, which never fire, are dropped. This
+    /// function does not reset the elapsed time of the object.
+    pub fn reverse(&mut self) {
+        self.locations.reverse();
+        self.densities.reverse();
+        self.colors.reverse();
+        self.sizes.reverse();
+        self.decays.reverse();
+        self.rates.reverse();
+        self.directions.reverse();
+        self.spreads.reverse();
+        let period = self.period;
+        self.bursts.retain(|(t, _)| *t <= period);
+        for (t, _) in self.bursts.iter_mut() {
+            *t = period - *t;
+        }
+        self.bursts.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    /// Return self (consuming it) reversed as by `reverse()`.
+    pub fn reversed(mut self) -> Self {
+        self.reverse();
+        self
+    }
+}
+
+// age `particles` to `clock`, moving them under `forces` and `on_update`,
+// colliding them with `colliders`, scaling them along `size_curve` and
+// dropping those that have decayed or were killed
+fn age_particles(
+    particles: &mut Vec<Particle>,
+    clock: f32,
+    forces: &ForceField,
+    colliders: &Colliders,
+    size_curve: &[f32],
+    on_update: Option<&UpdateCallback>,
+) {
+    age_in_place(particles, clock, forces, colliders, size_curve, on_update);
+    particles.retain(|p| !p.is_finished());
+}
+
+// age `particles` as `age_particles()` does, leaving those that have
+// decayed or were killed finished but in place. Forces and callbacks
+// are shared with `Rc` and can't cross threads, so only particles
+// without them are aged in parallel.
+fn age_in_place(
+    particles: &mut [Particle],
+    clock: f32,
+    forces: &ForceField,
+    colliders: &Colliders,
+    size_curve: &[f32],
+    on_update: Option<&UpdateCallback>,
+) {
+    #[cfg(feature = "parallel")]
+    if particles.len() >= PARALLEL_MIN && forces.is_empty() && on_update.is_none() {
+        use rayon::prelude::*;
+        particles.par_iter_mut().for_each(|p| {
+            age_particle(p, clock, None, colliders, size_curve, None);
+        });
+        return;
+    }
+    for p in particles.iter_mut() {
+        age_particle(p, clock, Some(forces), colliders, size_curve, on_update);
+    }
+}
+
+// age a single particle as `age_particles()` does, accelerated by
+// `forces` if any. Returns `false` once it has decayed or was killed,
+// else `true`.
+fn age_particle(
+    p: &mut Particle,
+    clock: f32,
+    forces: Option<&ForceField>,
+    colliders: &Colliders,
+    size_curve: &[f32],
+    on_update: Option<&UpdateCallback>,
+) -> bool {
+    let last_age = p.age();
+    p.set_time(clock);
+    match forces {
+        Some(f) => p.update_with(f),
+        None => p.update(),
+    }
+    if !colliders.resolve(p) {
+        p.kill();
+        return false;
+    }
+    if size_curve.len() > 1 || size_curve[0] != 1. {
+        let ratio = match p.length() {
+            l if l > 0. => (p.age() / l).clamp(0., 1.),
+            _ => 1.,
+        };
+        p.set_scale(
+            map_float_value(size_curve, ratio, 1., &[Easing::Linear], TrackMode::Linear)
+                .unwrap_or(1.),
+        );
+    }
+    if let Some(f) = on_update {
+        let age = p.age();
+        f.call(p, age - last_age, age);
+    }
+    !p.is_finished()
+}
+
+/// Aging of the particles a LinearParticles spawned over its last frame,
+/// left to its group by `ParticleSys::defer_aging()` so the particles of
+/// every part can be aged across threads. Only available with the
+/// `parallel` feature.
+#[cfg(feature = "parallel")]
+pub struct AgingJob<'a> {
+    particles: &'a mut Vec<Particle>,
+    steps: &'a mut Vec<(f32, usize)>,
+    colliders: &'a Colliders,
+    size_curve: &'a [f32],
+    peak_count: &'a mut usize,
+}
+
+#[cfg(feature = "parallel")]
+impl AgingJob<'_> {
+    /// Age each particle through the steps of the frame from the one it
+    /// was spawned on, as the system would have step by step, and drop
+    /// those that have decayed or were killed.
+    pub fn run(self) {
+        let (steps, colliders, size_curve) = (&*self.steps, self.colliders, self.size_curve);
+        let age = |i: usize, p: &mut Particle| {
+            let first = steps.partition_point(|(_, count)| *count <= i);
+            for (clock, _) in steps[first..].iter() {
+                if !age_particle(p, *clock, None, colliders, size_curve, None) {
+                    break;
+                }
+            }
+        };
+        if self.particles.len() >= PARALLEL_MIN {
+            use rayon::prelude::*;
+            self.particles
+                .par_iter_mut()
+                .enumerate()
+                .for_each(|(i, p)| age(i, p));
+        } else {
+            self.particles
+                .iter_mut()
+                .enumerate()
+                .for_each(|(i, p)| age(i, p));
+        }
+        self.particles.retain(|p| !p.is_finished());
+        self.steps.clear();
+        *self.peak_count = (*self.peak_count).max(self.particles.len());
+    }
+}
+
+// particles of the shared buffer of a LinearGrp to draw, skipping those
+// emitted by muted parts. Particles left by removed parts are drawn.
+fn drawn_from_pool<'a>(
+    pool: &'a mut [Particle],
+    parts: &'a [usize],
+    states: &'a [ChildState],
+) -> impl Iterator<Item = &'a mut Particle> {
+    pool.iter_mut()
+        .zip(parts.iter())
+        .filter(|(_, i)| states.get(**i).is_none_or(|s| s.is_drawn()))
+        .map(|(p, _)| p)
+}
+
+// ***************************************
+// Impl's for LinearParticles
+
+impl ParticleSys for LinearParticles {
+    type T = Particle;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        self.period = match p {
+            Some(p) => {
+                check_period(p)?;
+                p
+            }
+            None => self.period,
+        };
+
+        self.peak_count = 0;
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.restart()?;
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+
+        let update_start = self.perf.is_some().then(now);
+        #[cfg(feature = "parallel")]
+        self.age_deferred();
+        #[cfg(feature = "parallel")]
+        let deferring = std::mem::take(&mut self.defer);
+        self.place_on_screen();
+        self.modulated = self.modulation.sample();
+        if self.rewind && current_time < self.sim_time {
+            self.rewind_to(current_time)?;
+        }
+        let spawned = self.spawned;
+        let (mut capacity, mut allocations) = (self.particles.capacity(), 0);
+        let culled = matches!(self.culling, Some(c) if !c.simulate) && self.is_culled();
+        let follow = self.follow_target();
+        let steps = self.steps_to(current_time);
+        for i in 0..steps {
+            self.follow_step(follow, i + 1, steps);
+            if culled {
+                self.sim_time += self.timestep;
+                self.skip(self.track_time(self.sim_time));
+            } else {
+                #[cfg(feature = "parallel")]
+                if deferring {
+                    self.sim_time += self.timestep;
+                    self.spawn_deferred(self.track_time(self.sim_time))?;
+                } else {
+                    self.step(self.timestep)?;
+                }
+                #[cfg(not(feature = "parallel"))]
+                self.step(self.timestep)?;
+                self.keep_snapshot();
+            }
+            if self.particles.capacity() != capacity {
+                capacity = self.particles.capacity();
+                allocations += 1;
+            }
+        }
+        self.anchor = follow.1;
+        if let Some(u) = update_start {
+            self.perf = Some(PerfCounters {
+                update_secs: (now() - u) as f32,
+                draw_secs: 0.,
+                spawned: self.spawned - spawned,
+                particles: self.particles.len(),
+                allocations,
+            });
+        }
+        Ok(current_time <= self.period)
+    }
+
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+
+        #[cfg(feature = "parallel")]
+        self.age_deferred();
+        // particles age in the same fixed steps through the period as
+        // while emitting, only without spawning
+        for _ in 0..self.steps_to(current_time) {
+            self.sim_time += self.timestep;
+            self.advance_clock(self.track_time(self.sim_time));
+            age_particles(
+                &mut self.particles,
+                self.clock,
+                &self.forces,
+                &self.colliders,
+                &self.size_curve,
+                self.on_update.as_ref(),
+            );
+        }
+        Ok(!self.particles.is_empty())
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.particles.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.particles.iter_mut())
+    }
+
+    fn perf_counters(&self) -> Option<PerfCounters> {
+        self.perf
+    }
+
+    fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    #[cfg(feature = "parallel")]
+    fn defer_aging(&mut self) -> bool {
+        self.defer = self.ages_in_parallel();
+        self.defer
+    }
+
+    #[cfg(feature = "parallel")]
+    fn aging_job(&mut self) -> Option<AgingJob<'_>> {
+        if self.deferred.is_empty() {
+            return None;
+        }
+        Some(AgingJob {
+            particles: &mut self.particles,
+            steps: &mut self.deferred,
+            colliders: &self.colliders,
+            size_curve: &self.size_curve,
+            peak_count: &mut self.peak_count,
+        })
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.spawned
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    // the time starting over on a loop isn't a rewind
+    fn handle_loop(&mut self) {
+        self.sim_time = 0.;
+        self.snapshots.clear();
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    // draws made between two updates add up to the draw time of the frame
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        if !self.active {
+            return Ok(());
+        }
+        let draw_start = self.perf.is_some().then(now);
+        self.draw_particles();
+        if let (Some(perf), Some(d)) = (self.perf.as_mut(), draw_start) {
+            perf.draw_secs += (now() - d) as f32;
+        }
+        Ok(())
+    }
+
+    // the particles themselves are left out of the description
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            children: Vec::new(),
+            ..SysInfo::of(self)
+        }
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        let emitting = self.emission_point().map(|p| {
+            let t = self.track_time(self.sim_time);
+            (p, self.spreads.value_at(t, self.period).unwrap_or(0.))
+        });
+        if self.screen.is_some() {
+            begin_screen();
+        }
+        begin_draw(self.transform, None);
+        let (start, end) = self.line();
+        gizmos.draw(start, end, emitting, &self.bounds());
+        end_draw(self.transform, None);
+        if self.screen.is_some() {
+            end_screen();
+        }
+    }
+}
+
+impl Default for LinearParticles {
+    fn default() -> Self {
+        LinearParticles::new(Vec3::new(0., 0., 0.), Vec3::new(0., 0., 0.))
+    }
+}
+
+#[test]
+fn start_at_test() {
+    let mut lp = LinearParticles::default();
+    lp.start_at(5.).unwrap();
+    lp.set_time(5.5);
+    assert_eq!(lp.elapsed_time(), Some(0.5));
+
+    lp.start_loop_at(20.).unwrap();
+    assert!(lp.is_looping());
+    assert_eq!(lp.elapsed_time(), Some(0.));
+}
+
+#[test]
+fn spread_jitter_test() {
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_spread(0.5)
+        .unwrap();
+    for _ in 0..20 {
+        let radius = lp.spreads.value_at(0., 1.).unwrap();
+        let offset = lp.random_jitter(radius);
+        assert!(offset.x.abs() < 1e-6);
+        assert!(offset.length() <= 0.5 + 1e-6);
+    }
+    assert!(lp.with_spread(-1.).is_err());
+}
+
+#[test]
+fn spreads_track_test() {
+    let lp = LinearParticles::default().with_spreads(&[0., 0.5]).unwrap();
+    assert_eq!(lp.spreads.value_at(0.25, 1.), Ok(0.125));
+    let lp = lp.with_spread(1.).unwrap();
+    assert_eq!(lp.spreads.value_at(0.25, 1.), Ok(1.));
+
+    let lp = LinearParticles::default().with_spreads(&[]);
+    assert_eq!(lp.err(), Some(LinearPLError::EmptyTrack("spreads")));
+}
+
+#[test]
+fn track_easing_test() {
+    let lp = LinearParticles::default()
+        .with_densities(&[0., 1.])
+        .unwrap()
+        .with_easing(TrackKind::Densities, Easing::QuadIn);
+    assert_eq!(lp.densities.value_at(0.5, 1.), Ok(0.25));
+    assert_eq!(lp.sizes.options.easings, vec![Easing::Linear]);
+
+    let lp = LinearParticles::default()
+        .with_size_keys(&[(0., 0.), (0.5, 1.), (1., 0.)])
+        .unwrap()
+        .with_segment_easings(
+            TrackKind::Sizes,
+            &[Easing::CubicBezier(0., 0., 0., 1.), Easing::Linear],
+        );
+    assert!(lp.sizes.value_at(0.25, 1.).unwrap() > 0.5);
+    assert_eq!(lp.sizes.value_at(0.75, 1.), Ok(0.5));
+    let lp = LinearParticles::default()
+        .with_densities(&[0., 1., 1., 0.])
+        .unwrap()
+        .with_track_mode(TrackKind::Densities, TrackMode::CatmullRom);
+    assert_eq!(lp.densities.value_at(1. / 3., 1.), Ok(1.));
+    assert!(lp.densities.value_at(0.5, 1.).unwrap() > 1.);
+
+    let lp = lp.with_track_mode(TrackKind::Densities, TrackMode::Step);
+    assert_eq!(lp.densities.value_at(0.3, 1.), Ok(0.));
+    assert_eq!(lp.sizes.options.mode, TrackMode::Linear);
+}
+
+#[test]
+fn track_keys_test() {
+    let red = Color::new(1., 0., 0., 1.);
+    let white = Color::new(1., 1., 1., 1.);
+    let blue = Color::new(0., 0., 1., 1.);
+    let lp = LinearParticles::default()
+        .with_color_keys(&[(0., red), (0.25, white), (1., blue)])
+        .unwrap();
+    assert_eq!(lp.colors.value_at(0.25, 1.), Ok(Color::new(1., 1., 1., 1.)));
+    assert_eq!(
+        lp.colors.value_at(0.625, 1.),
+        Ok(Color::new(0.5, 0.5, 1., 1.))
+    );
+
+    let lp = LinearParticles::default().with_density_keys(&[(0.5, 0.), (0.2, 1.)]);
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidKeyTime(0.2)));
+}
+
+#[test]
+fn track_interpolator_test() {
+    use crate::curves::Curve;
+
+    let mut lp = LinearParticles::default()
+        .with_size_interpolator(Curve::step(vec![0.5, 1.]))
+        .with_density_interpolator(|r: f32| r * r);
+    assert_eq!(lp.sizes.value_at(0.4, 1.), Ok(0.5));
+    assert_eq!(lp.densities.value_at(0.5, 1.), Ok(0.25));
+    lp.densities.reverse();
+    assert_eq!(lp.densities.value_at(1., 1.), Ok(0.));
+
+    lp.set_densities(&[1.]).unwrap();
+    assert!(lp.densities.interpolator.is_none());
+}
+
+#[test]
+fn track_seamless_test() {
+    let lp = LinearParticles::default()
+        .with_densities(&[0., 1.])
+        .unwrap()
+        .with_seamless(TrackKind::Densities, true);
+    assert_eq!(lp.densities.value_at(0.5, 1.), Ok(1.));
+    assert_eq!(lp.densities.value_at(0.75, 1.), Ok(0.5));
+    assert_eq!(lp.densities.value_at(1., 1.), Ok(0.));
+
+    let lp = lp.with_density_keys(&[(0.25, 1.), (0.75, 0.)]).unwrap();
+    assert_eq!(lp.densities.value_at(0., 1.), Ok(0.5));
+    assert_eq!(lp.densities.value_at(1., 1.), Ok(0.5));
+}
+
+#[test]
+fn track_edge_test() {
+    let lp = LinearParticles::default();
+    assert_eq!(lp.locations.value_at(1.5, 1.), Ok(1.));
+
+    let lp = lp.with_edge(TrackKind::Locations, Edge::Extrapolate);
+    assert_eq!(lp.locations.value_at(1.5, 1.), Ok(1.5));
+    assert_eq!(lp.locations.value_at(-0.5, 1.), Ok(-0.5));
+
+    let lp = lp.with_edge(TrackKind::Locations, Edge::Wrap);
+    assert_eq!(lp.locations.value_at(1.25, 1.), Ok(0.25));
+}
+
+#[test]
+fn decays_track_test() {
+    let lp = LinearParticles::default().with_decays(&[2., 0.5]).unwrap();
+    assert_eq!(lp.decays.value_at(0.5, 1.), Ok(1.25));
+    let lp = lp.with_decay(1.).unwrap();
+    assert_eq!(lp.decays.value_at(0.5, 1.), Ok(1.));
+
+    let lp = LinearParticles::default().with_decays(&[1., -1.]);
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidDecay(-1.)));
+}
+
+#[test]
+fn rates_emission_test() {
+    let mut lp = LinearParticles::default()
+        .with_period(2.)
+        .unwrap()
+        .with_rates(&[10.])
+        .unwrap()
+        .with_stretch(1.)
+        .unwrap();
+    let mut total = 0;
+    for i in 1..=8 {
+        total += lp.spawn(i as f32 * 0.125, &mut Vec::new()).unwrap();
+    }
+    assert_eq!(total, 10);
+    assert_eq!(lp.spawn(1.25, &mut Vec::new()).unwrap(), 2);
+
+    let lp = lp.with_densities(&[1.]).unwrap();
+    assert!(!lp.by_rate);
+}
+
+#[test]
+fn particle_reuse_test() {
+    let mut lp = LinearParticles::default()
+        .with_rates(&[64.])
+        .unwrap()
+        .with_decay(0.25)
+        .unwrap()
+        .with_stretch(1.)
+        .unwrap();
+    for i in 0..64 {
+        lp.simulate(i as f32 / 64.).unwrap();
+    }
+    let buffer = lp.particles.as_ptr();
+    for i in 0..256 {
+        lp.simulate((i % 64) as f32 / 64.).unwrap();
+    }
+    assert!(!lp.particles.is_empty());
+    assert_eq!(lp.particles.as_ptr(), buffer);
+}
+
+#[test]
+fn max_particles_test() {
+    let mut lp = LinearParticles::default()
+        .with_period(2.)
+        .unwrap()
+        .with_decay(10.)
+        .unwrap()
+        .with_stretch(1.)
+        .unwrap()
+        .with_max_particles(4);
+    assert!(lp.particles.capacity() >= 4);
+    for i in 0..8 {
+        lp.simulate(i as f32 / 8.).unwrap();
+    }
+    assert_eq!(lp.particles.len(), 4);
+    assert_eq!(lp.spawned, 8);
+    assert_eq!(lp.particles[0].age(), 0.375);
+
+    // two more wrap around onto the first two slots
+    for i in 8..10 {
+        lp.simulate(i as f32 / 8.).unwrap();
+    }
+    let ages = |lp: &LinearParticles| lp.particles.iter().map(|p| p.age()).collect::<Vec<_>>();
+    assert_eq!(lp.oldest, 2);
+    assert_eq!(ages(&lp), [0.125, 0., 0.375, 0.25]);
+
+    // and are put back in spawn order once particles start dying
+    lp.particles[3].kill();
+    lp.set_densities(&[0.]).unwrap();
+    lp.simulate(1.25).unwrap();
+    assert_eq!(lp.oldest, 0);
+    assert_eq!(ages(&lp), [0.5, 0.25, 0.125]);
+}
+
+#[test]
+fn validation_clamp_test() {
+    let lp = LinearParticles::default().with_densities(&[0.5, 1.25]);
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidDensity(1.25)));
+
+    let lp = LinearParticles::builder(Vec3::ZERO, Vec3::X)
+        .validation(Validation::Clamp)
+        .densities(&[0.5, 1.25])
+        .location_keys(&[(0., -0.25), (1., 1.)])
+        .build()
+        .unwrap();
+    assert_eq!(lp.densities.values, vec![0.5, 1.]);
+    assert_eq!(lp.locations.values, vec![0., 1.]);
+    assert_eq!(
+        lp.diagnostics(),
+        [
+            Diagnostic {
+                error: LinearPLError::InvalidDensity(1.25),
+                clamped_to: 1.
+            },
+            Diagnostic {
+                error: LinearPLError::InvalidLocation(-0.25),
+                clamped_to: 0.
+            },
+        ]
+    );
+    let lp = lp.with_locations(&[0., f32::NAN]);
+    assert!(matches!(lp.err(), Some(LinearPLError::InvalidLocation(_))));
+}
+
+#[test]
+fn fixed_timestep_test() {
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::new(4., 0., 0.))
+        .with_timestep(0.125)
+        .unwrap()
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap();
+    assert!(lp.set_timestep(0.).is_err());
+    lp.setup(LoopMode::Once, None).unwrap();
+    assert_eq!(lp.steps_to(0.1), 0);
+    assert_eq!(lp.steps_to(0.3), 2);
+    for _ in 0..2 {
+        lp.step(0.125).unwrap();
+    }
+    assert_eq!(lp.particle_count(), 2);
+    assert_eq!(lp.steps_to(0.3), 0);
+    assert_eq!(lp.steps_to(1000.), MAX_STEPS);
+    assert_eq!(lp.steps_to(0.05), 0);
+    assert_eq!(lp.sim_time, 0.);
+
+    // streaks reach four steps along the line, whatever the frame rate
+    let p = &lp.particles[0];
+    assert_eq!(p.location().distance(p.end_location()), 2.);
+
+    // an external loop advancing by the timestep spawns in lockstep
+    lp.start_at(0.).unwrap();
+    for i in 1..=4 {
+        assert!(lp.advance(0.125).unwrap());
+        assert_eq!(lp.spawned_total(), i);
+    }
+}
+
+#[test]
+fn drain_steps_test() {
+    let lp = || {
+        LinearParticles::default()
+            .with_timestep(0.125)
+            .unwrap()
+            .with_rates(&[8.])
+            .unwrap()
+            .with_decay(2.)
+            .unwrap()
+            .with_velocity(Vec3::Y, 0.)
+            .unwrap()
+            .with_gravity(Vec3::new(0., -10., 0.))
+    };
+    let locations = |lp: &LinearParticles| {
+        lp.particles
+            .iter()
+            .map(|p| p.location())
+            .collect::<Vec<_>>()
+    };
+    let (mut once, mut frames) = (lp(), lp());
+    for lp in [&mut once, &mut frames] {
+        lp.start_at(0.).unwrap();
+        lp.set_time(1.);
+        lp.update().unwrap();
+    }
+
+    // draining in one long frame ages particles in the same fixed steps
+    // as draining frame by frame
+    once.drain_frame(Some(1.5)).unwrap();
+    for i in 1..=5 {
+        frames.drain_frame(Some(1. + i as f32 * 0.1)).unwrap();
+    }
+    assert_eq!(once.clock, 1.5);
+    assert!(once.particle_count() > 0);
+    assert_eq!(locations(&once), locations(&frames));
+}
+
+#[test]
+fn rewind_test() {
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::new(4., 0., 0.))
+        .with_timestep(0.125)
+        .unwrap()
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap()
+        .with_spread(1.)
+        .unwrap()
+        .with_velocity(Vec3::Y, 0.5)
+        .unwrap()
+        .with_seed(7)
+        .with_rewind(true);
+    let locations = |lp: &LinearParticles| {
+        lp.particles
+            .iter()
+            .map(|p| p.location())
+            .collect::<Vec<_>>()
+    };
+    lp.start_at(0.).unwrap();
+    lp.set_time(0.5);
+    lp.update().unwrap();
+    let halfway = locations(&lp);
+    lp.set_time(1.);
+    lp.update().unwrap();
+    assert!(lp.particle_count() > halfway.len());
+
+    lp.set_time(0.5);
+    lp.update().unwrap();
+    assert_eq!(locations(&lp), halfway);
+}
+
+#[test]
+fn rewind_snapshot_test() {
+    use std::cell::Cell;
+
+    let calls = Rc::new(Cell::new(0));
+    let c = calls.clone();
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_timestep(0.125)
+        .unwrap()
+        .with_period(8.)
+        .unwrap()
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(100.)
+        .unwrap()
+        .with_seed(3)
+        .with_rewind(true)
+        .with_on_spawn(move |_| c.set(c.get() + 1));
+    lp.start_at(0.).unwrap();
+    lp.set_time(6.);
+    lp.update().unwrap();
+    assert_eq!(calls.get(), 48);
+
+    // replayed from the snapshot at step 32 rather than from the start,
+    // calling `on_spawn` again only for the 8 particles respawned
+    lp.set_time(5.);
+    lp.update().unwrap();
+    assert_eq!(lp.particle_count(), 40);
+    assert_eq!(lp.spawned_total(), 40);
+    assert_eq!(calls.get(), 56);
+
+    // and from the start once rewound before the first snapshot
+    lp.set_time(1.);
+    lp.update().unwrap();
+    assert_eq!(lp.particle_count(), 8);
+    assert_eq!(calls.get(), 64);
+}
+
+#[test]
+fn seeded_spawn_test() {
+    let lp = LinearParticles::default()
+        .with_densities(&[0.5])
+        .unwrap()
+        .with_spread(1.)
+        .unwrap()
+        .with_seed(42);
+    let spawned = |mut lp: LinearParticles| {
+        lp.setup(LoopMode::Once, None).unwrap();
+        for _ in 0..30 {
+            lp.step(1. / 60.).unwrap();
+        }
+        lp.particles
+            .iter()
+            .map(|p| p.location())
+            .collect::<Vec<_>>()
+    };
+    let first = spawned(lp.clone());
+    assert!(!first.is_empty());
+    assert_eq!(first, spawned(lp.clone()));
+    assert_eq!(lp.seed(), Some(42));
+}
+
+#[test]
+fn lod_test() {
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_stretch(1.)
+        .unwrap()
+        .with_sizes(&[2.])
+        .unwrap()
+        .with_bursts(&[(0., 8)])
+        .unwrap()
+        .with_densities(&[0.])
+        .unwrap()
+        .with_lod(Lod::new(10., 20.).with_density(0.5).with_size(0.25))
+        .unwrap();
+    assert!(lp.set_lod(Lod::new(20., 10.)).is_err());
+    lp.camera = Some(Vec3::new(0.5, 30., 0.));
+    assert_eq!(lp.lod_scales(), (0.5, 0.25));
+    let mut out = Vec::new();
+    assert_eq!(lp.spawn(0.5, &mut out).unwrap(), 4);
+    assert_eq!(out[0].thickness(), 0.5);
+
+    lp.camera = Some(Vec3::new(-5., 0., 0.));
+    assert_eq!(lp.lod_scales(), (1., 1.));
+}
+
+#[test]
+fn emission_point_test() {
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::new(2., 0., 0.));
+    assert_eq!(lp.emission_point(), None);
+    lp.start().unwrap();
+    lp.step(0.5).unwrap();
+    let point = lp.emission_point().unwrap();
+    assert!((point - Vec3::X).length() < 1e-4);
+    lp.step(1.).unwrap();
+    assert_eq!(lp.emission_point(), None);
+}
+
+// resolving points needs the size of the window unless headless
+#[cfg(any(feature = "headless", not(feature = "macroquad")))]
+#[test]
+fn screen_space_test() {
+    use crate::math::Vec2;
+    use crate::screen::Anchor;
+
+    let mut lp = LinearParticles::builder(Vec3::ZERO, Vec3::X)
+        .screen_space(
+            ScreenPoint::new(Anchor::TopRight, Vec2::new(-100., 20.)),
+            ScreenPoint::at(Anchor::Percent(50., 50.)),
+        )
+        .bursts(&[(0., 4)])
+        .build()
+        .unwrap();
+    assert!(lp.is_screen_space());
+    lp.start().unwrap();
+    lp.update_frame(Some(0.1)).unwrap();
+    let bounds = lp.bounds();
+    assert_eq!(bounds.min.x, 400.);
+    assert_eq!(bounds.max, Vec3::new(700., 300., 0.));
+}
+
+#[test]
+fn position_2d_test() {
+    use crate::math::Vec2;
+
+    let mut flat = LinearParticles::builder(Vec2::new(-1., 0.), Vec2::new(1., 0.))
+        .velocity(Vec2::new(0., 2.), 0.5)
+        .gravity(Vec2::new(0., -9.8))
+        .bursts(&[(0., 6)])
+        .seed(7)
+        .build()
+        .unwrap();
+    let mut deep = LinearParticles::builder(Vec3::new(-1., 0., 0.), Vec3::new(1., 0., 0.))
+        .velocity(Vec3::new(0., 2., 0.), 0.5)
+        .gravity(Vec3::new(0., -9.8, 0.))
+        .bursts(&[(0., 6)])
+        .seed(7)
+        .build()
+        .unwrap();
+    for lp in [&mut flat, &mut deep] {
+        lp.start().unwrap();
+        for _ in 0..10 {
+            lp.step(1. / 60.).unwrap();
+        }
+    }
+    let locations = |lp: &LinearParticles| -> Vec<Vec2> {
+        lp.particles
+            .iter()
+            .map(|p| p.location().truncate())
+            .collect()
+    };
+    assert_eq!(locations(&flat), locations(&deep));
+    assert!(flat.particle_count() >= 6);
+    assert!(flat.particles.iter().all(|p| p.location().z == 0.));
+    assert_eq!(
+        flat.emission_point().map(Vec2::from_vec3),
+        deep.emission_point().map(|p| p.truncate())
+    );
+
+    let mut flat = LinearParticles::new(Vec2::ZERO, Vec2::X)
+        .with_spread(0.5)
+        .unwrap()
+        .with_spin(3., 1.)
+        .unwrap();
+    flat.start().unwrap();
+    flat.step(0.5).unwrap();
+    assert!(flat.particle_count() > 0);
+    assert!(flat.particles.iter().all(|p| p.location().z == 0.));
+}
+
+#[test]
+fn modulation_test() {
+    use std::cell::Cell;
+
+    let level = Rc::new(Cell::new(0.));
+    let l = level.clone();
+    let mut lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_modulation(ModTarget::Density, move || l.get())
+        .with_modulation(ModTarget::Brightness, || 0.5);
+    lp.start().unwrap();
+    lp.update_frame(Some(0.25)).unwrap();
+    assert_eq!(lp.particle_count(), 0);
+    level.set(1.);
+    lp.update_frame(Some(0.5)).unwrap();
+    assert!(lp.particle_count() > 0);
+    assert_eq!(
+        lp.modulated.tinted(None),
+        Some(Color::new(0.5, 0.5, 0.5, 1.))
+    );
+}
+
+#[test]
+fn reversed_test() {
+    // sweeping from the start of the line to its end
+    let lp = LinearParticles::builder(Vec3::ZERO, Vec3::X)
+        .locations(&[1., 0.])
+        .bursts(&[(0.75, 3), (0.25, 1), (2., 4)])
+        .build()
+        .unwrap();
+    let emitted = |lp: &LinearParticles| {
+        let mut lp = lp.clone();
+        lp.start().unwrap();
+        lp.step(0.05).unwrap();
+        let first = lp.emission_point().unwrap();
+        lp.step(0.9).unwrap();
+        (first, lp.emission_point().unwrap())
+    };
+    let (start, end) = emitted(&lp);
+    let reversed = lp.reversed();
+    assert_eq!(reversed.bursts, [(0.25, 3), (0.75, 1)]);
+    let (from, to) = emitted(&reversed);
+    assert!(from.distance(end) < 1e-4 && to.distance(start) < 1e-4);
+    assert!(from.distance(Vec3::X) < 0.1 && to.distance(Vec3::ZERO) < 0.1);
+}
+
+#[test]
+fn follow_test() {
+    use std::cell::Cell;
+
+    let ship = Rc::new(Cell::new(Vec3::ZERO));
+    let s = ship.clone();
+    let mut lp = LinearParticles::builder(Vec3::ZERO, Vec3::X)
+        .decay(5.)
+        .bursts(&[(0., 4), (0.5, 4)])
+        .build()
+        .unwrap()
+        .with_follow(move || s.get());
+    lp.start().unwrap();
+    lp.update_frame(Some(0.1)).unwrap();
+    let trail: Vec<Vec3> = lp.particles.iter().map(|p| p.location()).collect();
+    assert!(trail.iter().all(|l| l.y.abs() < 1.));
+
+    ship.set(Vec3::new(0., 10., 0.));
+    lp.update_frame(Some(0.6)).unwrap();
+    assert_eq!(lp.anchor(), Vec3::new(0., 10., 0.));
+    let (old, new): (Vec<&Particle>, Vec<_>) =
+        lp.particles.iter().partition(|p| p.location().y < 5.);
+    assert!(old.len() >= trail.len() && !new.is_empty());
+
+    lp.set_anchor(crate::math::Vec2::new(3., 0.));
+    assert_eq!(lp.line(), (Vec3::new(3., 0., 0.), Vec3::new(4., 0., 0.)));
+}
+
+#[test]
+fn draw_to_test() {
+    use crate::render::{DrawCall, MockRenderer};
+
+    let mut lp = LinearParticles::builder(Vec3::ZERO, Vec3::X)
+        .bursts(&[(0., 4)])
+        .build()
+        .unwrap();
+    lp.start().unwrap();
+    lp.step(0.1).unwrap();
+    let mut mock = MockRenderer::new();
+    lp.draw_to(&mut mock);
+    assert_eq!(mock.segments().count(), lp.particle_count());
+    assert!(lp.particle_count() >= 4);
+    assert_eq!(mock.calls().last(), Some(&DrawCall::Flush));
+}
+
+#[test]
+fn bursts_test() {
+    let mut lp = LinearParticles::default()
+        .with_period(2.)
+        .unwrap()
+        .with_densities(&[0.])
+        .unwrap()
+        .with_bursts(&[(0., 3), (1., 5), (2., 1)])
+        .unwrap()
+        .with_stretch(1.)
+        .unwrap();
+    assert_eq!(lp.spawn(0.5, &mut Vec::new()).unwrap(), 3);
+    assert_eq!(lp.spawn(0.75, &mut Vec::new()).unwrap(), 0);
+    assert_eq!(lp.spawn(1.5, &mut Vec::new()).unwrap(), 5);
+    assert_eq!(lp.spawn(2.5, &mut Vec::new()).unwrap(), 1);
+    assert_eq!(lp.spawn(0.25, &mut Vec::new()).unwrap(), 3);
+    assert_eq!(
+        lp.set_bursts(&[(-0.5, 1)]),
+        Err(LinearPLError::InvalidBurst(-0.5))
+    );
+}
+
+#[test]
+fn directions_track_test() {
+    let mut lp = LinearParticles::default()
+        .with_velocity(Vec3::new(2., 0., 0.), 0.)
+        .unwrap()
+        .with_directions(&[Vec3::X, Vec3::NEG_Y, Vec3::NEG_Y])
+        .unwrap()
+        .with_stretch(1.)
+        .unwrap();
+    let mut particles = Vec::new();
+    lp.spawn(0.5, &mut particles).unwrap();
+    assert_eq!(particles[0].velocity(), Vec3::new(0., -2., 0.));
+
+    lp.set_directions(&[Vec3::ZERO]).unwrap();
+    particles.clear();
+    lp.spawn(0.75, &mut particles).unwrap();
+    assert_eq!(particles[0].velocity(), Vec3::new(2., 0., 0.));
+    assert_eq!(
+        lp.set_directions::<Vec3>(&[]),
+        Err(LinearPLError::EmptyTrack("directions"))
+    );
+}
+
+#[test]
+fn track_noise_test() {
+    let noise = Noise::new(0.25, 8.).with_seed(3);
+    let lp = LinearParticles::default()
+        .with_densities(&[0.5])
+        .unwrap()
+        .with_noise(TrackKind::Densities, noise)
+        .unwrap();
+    assert_eq!(
+        lp.densities.value_at(0.4, 1.),
+        Ok(0.5 + noise.value_at(0.4))
+    );
+    assert_eq!(lp.colors.value_at(0.4, 1.), Ok(Color::new(1., 1., 1., 1.)));
+
+    let lp = LinearParticles::default().with_noise(TrackKind::Sizes, Noise::new(0.1, -1.));
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidNoise(-1.)));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn ages_in_parallel_test() {
+    let mut lp = LinearParticles::default();
+    assert!(lp.ages_in_parallel() && lp.defer_aging());
+    for mut lp in [
+        LinearParticles::default().with_max_particles(10),
+        LinearParticles::default().with_rewind(true),
+        LinearParticles::default().with_update(|_, _, _| {}),
+    ] {
+        assert!(!lp.ages_in_parallel() && !lp.defer_aging());
+    }
+    lp.add_force(crate::forces::Wind(Vec3::X));
+    assert!(!lp.ages_in_parallel() && !lp.defer_aging());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn age_particles_parallel_test() {
+    use crate::collision::{Collider, Response};
+
+    let colliders = Colliders::new().with(Collider::plane(Vec3::ZERO, Vec3::Y, Response::Kill));
+    let mut particles: Vec<Particle> = (0..PARALLEL_MIN * 2)
+        .map(|i| {
+            let mut p =
+                Particle::new_line((0., 1., 0.), (0., 1., 1.), (1., 1., 1., 1.), 10., false)
+                    .unwrap()
+                    .with_velocity(Vec3::new(0., (i % 3) as f32 - 1., 0.));
+            p.set_time(0.);
+            p.reset();
+            p
+        })
+        .collect();
+    let mut serial = particles.clone();
+    age_particles(
+        &mut particles,
+        2.,
+        &ForceField::new(),
+        &colliders,
+        &[1.],
+        None,
+    );
+    serial.retain_mut(|p| age_particle(p, 2., None, &colliders, &[1.], None));
+    assert_eq!(particles.len(), PARALLEL_MIN * 2 - 683);
+    assert_eq!(particles, serial);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn sync_grp_deferred_aging_test() {
+    use crate::collision::{Collider, Response};
+    use crate::groups::SyncGrp;
+
+    let lp = LinearParticles::new(Vec3::ZERO, Vec3::X)
+        .with_bursts(&[(0., PARALLEL_MIN), (0.3, PARALLEL_MIN)])
+        .unwrap()
+        .with_velocity(Vec3::Y, 1.)
+        .unwrap()
+        .with_gravity(Vec3::new(0., -2., 0.))
+        .with_colliders(Colliders::new().with(Collider::plane(
+            Vec3::new(0., -0.2, 0.),
+            Vec3::Y,
+            Response::Kill,
+        )))
+        .with_seed(7);
+    let mut serial = lp.clone();
+    let mut grp = SyncGrp::new(1., &[lp]);
+    serial.setup(LoopMode::Once, Some(1.)).unwrap();
+    grp.start().unwrap();
+    let mut most = 0;
+    for t in [0.1, 0.35, 0.6, 0.9] {
+        serial.update_frame(Some(t)).unwrap();
+        most = most.max(serial.particle_count());
+        grp.update_frame(Some(t)).unwrap();
+        let part = grp.iter().unwrap().next().unwrap();
+        assert!(part.deferred.is_empty());
+        let state = |lp: &LinearParticles| -> Vec<(Vec3, Vec3, f32)> {
+            lp.particles
+                .iter()
+                .map(|p| (p.location(), p.velocity(), p.age()))
+                .collect()
+        };
+        assert_eq!(state(part), state(&serial));
+    }
+    assert!(most > PARALLEL_MIN);
+}
+
+#[test]
+fn age_particles_update_test() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let steps = Rc::new(Cell::new((0., 0.)));
+    let s = steps.clone();
+    let on_update = UpdateCallback::new(move |p, dt, age| {
+        s.set((dt, age));
+        p.set_color(1., 0., 0., 1.);
+    });
+    let mut p = Particle::default();
+    p.set_time(2.);
+    p.reset();
+    let mut particles = vec![p];
+
+    age_particles(
+        &mut particles,
+        2.25,
+        &ForceField::new(),
+        &Colliders::new(),
+        &[1.],
+        Some(&on_update),
+    );
+    assert_eq!(steps.get(), (0.25, 0.25));
+    assert_eq!(particles[0].color(), Color::new(1., 0., 0., 1.));
+
+    age_particles(
+        &mut particles,
+        2.5,
+        &ForceField::new(),
+        &Colliders::new(),
+        &[0., 2.],
+        None,
+    );
+    assert_eq!(particles[0].scale(), 1.);
+
+    age_particles(
+        &mut particles,
+        3.5,
+        &ForceField::new(),
+        &Colliders::new(),
+        &[1.],
+        Some(&on_update),
+    );
+    assert!(particles.is_empty());
+}
+
+// ***************************************
+// LinearParticlesBuilder
+// ***************************************
+
+/// Builder collecting the settings of a LinearParticles system so that
+/// they are validated once in `build()` rather than at every step.
+/// Settings left unset keep the defaults of `LinearParticles::new`.
+#[derive(Debug, Clone, Default)]
+pub struct LinearParticlesBuilder {
+    start_location: Vec3,
+    end_location: Vec3,
+    planar: bool,
+    validation: Option<Validation>,
+    locations: Option<Vec<f32>>,
+    densities: Option<Vec<f32>>,
+    colors: Option<Vec<Color>>,
+    sizes: Option<Vec<f32>>,
+    location_keys: Option<Vec<(f32, f32)>>,
+    density_keys: Option<Vec<(f32, f32)>>,
+    color_keys: Option<Vec<(f32, Color)>>,
+    size_keys: Option<Vec<(f32, f32)>>,
+    easings: Vec<(TrackKind, Easing)>,
+    segment_easings: Vec<(TrackKind, Vec<Easing>)>,
+    track_modes: Vec<(TrackKind, TrackMode)>,
+    noises: Vec<(TrackKind, Noise)>,
+    seamless: Vec<TrackKind>,
+    edges: Vec<(TrackKind, Edge)>,
+    size_curve: Option<Vec<f32>>,
+    shape: Option<ParticleShape>,
+    blend: Option<BlendMode>,
+    render_mode: Option<RenderMode>,
+    glow: Option<(f32, f32)>,
+    velocity: Option<(Vec3, f32)>,
+    directions: Option<Vec<Vec3>>,
+    spread: Option<f32>,
+    spreads: Option<Vec<f32>>,
+    gravity: Option<Vec3>,
+    drag: Option<f32>,
+    mass: Option<(f32, f32)>,
+    stretch: Option<f32>,
+    spin: Option<(f32, f32)>,
+    trail: Option<usize>,
+    max_particles: Option<usize>,
+    timestep: Option<f32>,
+    seed: Option<u64>,
+    rewind: Option<bool>,
+    culling: Option<Culling>,
+    lod: Option<Lod>,
+    screen: Option<(ScreenPoint, ScreenPoint)>,
+    forces: Option<ForceField>,
+    colliders: Option<Colliders>,
+    period: Option<f32>,
+    decay: Option<f32>,
+    decays: Option<Vec<f32>>,
+    rates: Option<Vec<f32>>,
+    bursts: Option<Vec<(f32, usize)>>,
+    fade_in: Option<f32>,
+    fade_curve: Option<FadeCurve>,
+    warmup: Option<f32>,
+    mode: Option<LoopMode>,
+}
+
+impl LinearParticlesBuilder {
+    /// Create a new builder with a starting location of `start_loc`
+    /// and an ending location of `end_loc`.
+    pub fn new<P: Position>(start_loc: P, end_loc: P) -> Self {
+        LinearParticlesBuilder {
+            start_location: start_loc.to_vec3(),
+            end_location: end_loc.to_vec3(),
+            planar: P::DIMENSIONS == 2,
+            ..Default::default()
+        }
+    }
+
+    /// Set the start-location `sl` and ending location `el`.
+    pub fn start_end<P: Position>(mut self, sl: P, el: P) -> Self {
+        self.start_location = sl.to_vec3();
+        self.end_location = el.to_vec3();
+        self.planar = P::DIMENSIONS == 2;
+        self
+    }
+
+    /// Set the Validation `v` of out-of-range locations and densities.
+    pub fn validation(mut self, v: Validation) -> Self {
+        self.validation = Some(v);
+        self
+    }
+
+    /// Set the locations track `l`.
+    pub fn locations(mut self, l: &[f32]) -> Self {
+        self.locations = Some(l.into());
+        self
+    }
+
+    /// Set the densities track `d`.
+    pub fn densities(mut self, d: &[f32]) -> Self {
+        self.densities = Some(d.into());
+        self
+    }
+
+    /// Set the colors track `c`.
+    pub fn colors(mut self, c: &[Color]) -> Self {
+        self.colors = Some(c.into());
+        self
+    }
+
+    /// Set the sizes track `s`.
+    pub fn sizes(mut self, s: &[f32]) -> Self {
+        self.sizes = Some(s.into());
+        self
+    }
+
+    /// Set the locations track to keys `k` of normalized times and values.
+    pub fn location_keys(mut self, k: &[(f32, f32)]) -> Self {
+        self.location_keys = Some(k.into());
+        self
+    }
+
+    /// Set the densities track to keys `k` of normalized times and values.
+    pub fn density_keys(mut self, k: &[(f32, f32)]) -> Self {
+        self.density_keys = Some(k.into());
+        self
+    }
+
+    /// Set the colors track to keys `k` of normalized times and values.
+    pub fn color_keys(mut self, k: &[(f32, Color)]) -> Self {
+        self.color_keys = Some(k.into());
+        self
+    }
+
+    /// Set the sizes track to keys `k` of normalized times and values.
+    pub fn size_keys(mut self, k: &[(f32, f32)]) -> Self {
+        self.size_keys = Some(k.into());
+        self
+    }
+
+    /// Set the Easing `easing` of the `kind` track.
+    pub fn easing(mut self, kind: TrackKind, easing: Easing) -> Self {
+        self.easings.push((kind, easing));
+        self
+    }
+
+    /// Set the Easing of each segment of the `kind` track to `easings`.
+    pub fn segment_easings(mut self, kind: TrackKind, easings: &[Easing]) -> Self {
+        self.segment_easings.push((kind, easings.into()));
+        self
+    }
+
+    /// Set the TrackMode `mode` of the `kind` track.
+    pub fn track_mode(mut self, kind: TrackKind, mode: TrackMode) -> Self {
+        self.track_modes.push((kind, mode));
+        self
+    }
+
+    /// Set the Edge `edge` of the `kind` track.
+    pub fn edge(mut self, kind: TrackKind, edge: Edge) -> Self {
+        self.edges.push((kind, edge));
+        self
+    }
+
+    /// Set the `kind` track to blend across loop boundaries.
+    pub fn seamless(mut self, kind: TrackKind) -> Self {
+        self.seamless.push(kind);
+        self
+    }
+
+    /// Set the Noise `noise` overlaid on the `kind` track.
+    pub fn noise(mut self, kind: TrackKind, noise: Noise) -> Self {
+        self.noises.push((kind, noise));
+        self
+    }
+
+    /// Set the size curve `c`.
+    pub fn size_curve(mut self, c: &[f32]) -> Self {
+        self.size_curve = Some(c.into());
+        self
+    }
+
+    /// Set the particle shape `shape`.
+    pub fn shape(mut self, shape: ParticleShape) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+
+    /// Set the BlendMode `mode`.
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend = Some(mode);
+        self
+    }
+
+    /// Set the RenderMode `mode` lines are drawn with.
+    pub fn render_mode(mut self, mode: RenderMode) -> Self {
+        self.render_mode = Some(mode);
+        self
+    }
+
+    /// Set the glow `width` and opacity `alpha`.
+    pub fn glow(mut self, width: f32, alpha: f32) -> Self {
+        self.glow = Some((width, alpha));
+        self
+    }
+
+    /// Set the initial velocity `v` with random `spread`.
+    pub fn velocity<P: Position>(mut self, v: P, spread: f32) -> Self {
+        self.velocity = Some((v.to_vec3(), spread));
+        self
+    }
+
+    /// Set the directions track `d`.
+    pub fn directions<P: Position>(mut self, d: &[P]) -> Self {
+        self.directions = Some(d.iter().map(|v| v.to_vec3()).collect());
+        self
+    }
+
+    /// Set the spread `radius` around the line.
+    pub fn spread(mut self, radius: f32) -> Self {
+        self.spread = Some(radius);
+        self
+    }
+
+    /// Set the spreads track `s`.
+    pub fn spreads(mut self, s: &[f32]) -> Self {
+        self.spreads = Some(s.into());
+        self
+    }
+
+    /// Set the gravity `g`.
+    pub fn gravity<P: Position>(mut self, g: P) -> Self {
+        self.gravity = Some(g.to_vec3());
+        self
+    }
+
+    /// Set the drag coefficient `k`.
+    pub fn drag(mut self, k: f32) -> Self {
+        self.drag = Some(k);
+        self
+    }
+
+    /// Set the mass range from `min` to `max`.
+    pub fn mass(mut self, min: f32, max: f32) -> Self {
+        self.mass = Some((min, max));
+        self
+    }
+
+    /// Set the velocity stretch `s` in seconds.
+    pub fn stretch(mut self, s: f32) -> Self {
+        self.stretch = Some(s);
+        self
+    }
+
+    /// Set the angular velocity `s` with random `spread`.
+    pub fn spin(mut self, s: f32, spread: f32) -> Self {
+        self.spin = Some((s, spread));
+        self
+    }
+
+    /// Set the trail of `n` positions.
+    pub fn trail(mut self, n: usize) -> Self {
+        self.trail = Some(n);
+        self
+    }
+
+    /// Set the cap of `n` live particles.
+    pub fn max_particles(mut self, n: usize) -> Self {
+        self.max_particles = Some(n);
+        self
+    }
+
+    /// Set the seconds `dt` simulated by each fixed step.
+    pub fn timestep(mut self, dt: f32) -> Self {
+        self.timestep = Some(dt);
+        self
+    }
+
+    /// Set the seed of the system's random spawn decisions.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set whether the system plays backwards when its time decreases.
+    pub fn rewind(mut self, enabled: bool) -> Self {
+        self.rewind = Some(enabled);
+        self
+    }
+
+    /// Set the Culling `c` of the system.
+    pub fn culling(mut self, c: Culling) -> Self {
+        self.culling = Some(c);
+        self
+    }
+
+    /// Set the level of detail `lod` of the system.
+    pub fn lod(mut self, lod: Lod) -> Self {
+        self.lod = Some(lod);
+        self
+    }
+
+    /// Place the system in screen space, running from `start` to `end`.
+    pub fn screen_space(mut self, start: ScreenPoint, end: ScreenPoint) -> Self {
+        self.screen = Some((start, end));
+        self
+    }
+
+    /// Set the forces of `field`.
+    pub fn forces(mut self, field: ForceField) -> Self {
+        self.forces = Some(field);
+        self
+    }
+
+    /// Set the `colliders`.
+    pub fn colliders(mut self, colliders: Colliders) -> Self {
+        self.colliders = Some(colliders);
+        self
+    }
+
+    /// Set the period `p` in seconds.
+    pub fn period(mut self, p: f32) -> Self {
+        self.period = Some(p);
+        self
+    }
+
+    /// Set the decay `d` in seconds.
+    pub fn decay(mut self, d: f32) -> Self {
+        self.decay = Some(d);
+        self
+    }
+
+    /// Set the rates track `r`, replacing densities.
+    pub fn rates(mut self, r: &[f32]) -> Self {
+        self.rates = Some(r.into());
+        self
+    }
+
+    /// Set the bursts `b` of particles spawned at once.
+    pub fn bursts(mut self, b: &[(f32, usize)]) -> Self {
+        self.bursts = Some(b.into());
+        self
+    }
+
+    /// Set the decays track `d`.
+    pub fn decays(mut self, d: &[f32]) -> Self {
+        self.decays = Some(d.into());
+        self
+    }
+
+    /// Set the fade-in `f` in seconds.
+    pub fn fade_in(mut self, f: f32) -> Self {
+        self.fade_in = Some(f);
+        self
+    }
+
+    /// Set the fade curve `curve`.
+    pub fn fade_curve(mut self, curve: FadeCurve) -> Self {
+        self.fade_curve = Some(curve);
+        self
+    }
+
+    /// Set the warm-up `w` in seconds.
+    pub fn warmup(mut self, w: f32) -> Self {
+        self.warmup = Some(w);
+        self
+    }
+
+    /// Have `build()` start the system in LoopMode `mode`, so that it
+    /// is returned running and ready for `run()`.
+    pub fn start_with_mode(mut self, mode: LoopMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    // check that no two settings overriding each other were both given
+    fn check_conflicts(&self) -> Result<(), LinearPLError> {
+        let pairs = [
+            (
+                ("locations", self.locations.is_some()),
+                ("location_keys", self.location_keys.is_some()),
+            ),
+            (
+                ("densities", self.densities.is_some()),
+                ("density_keys", self.density_keys.is_some()),
+            ),
+            (
+                ("colors", self.colors.is_some()),
+                ("color_keys", self.color_keys.is_some()),
+            ),
+            (
+                ("sizes", self.sizes.is_some()),
+                ("size_keys", self.size_keys.is_some()),
+            ),
+            (
+                ("densities", self.densities.is_some()),
+                ("rates", self.rates.is_some()),
+            ),
+            (
+                ("density_keys", self.density_keys.is_some()),
+                ("rates", self.rates.is_some()),
+            ),
+            (
+                ("spread", self.spread.is_some()),
+                ("spreads", self.spreads.is_some()),
+            ),
+            (
+                ("decay", self.decay.is_some()),
+                ("decays", self.decays.is_some()),
+            ),
+        ];
+        for (a, b) in pairs {
+            check_exclusive(a, b)?;
+        }
+        Ok(())
+    }
+
+    /// Validate all settings and return the configured LinearParticles.
+    /// Settings overriding each other, such as `decay` and `decays`, or
+    /// `densities` and `rates`, are rejected with a `ConflictingSettings`
+    /// error when both are given.
+    pub fn build(self) -> Result<LinearParticles, LinearPLError> {
+        self.check_conflicts()?;
+        let mut lp = LinearParticles::new(self.start_location, self.end_location);
+        lp.planar = self.planar;
+        if let Some(v) = self.validation {
+            lp = lp.with_validation(v);
+        }
+        if let Some(l) = self.locations {
+            lp = lp.with_locations(&l)?;
+        }
+        if let Some(d) = self.densities {
+            lp = lp.with_densities(&d)?;
+        }
+        if let Some(c) = self.colors {
+            lp = lp.with_colors(&c)?;
+        }
+        if let Some(s) = self.sizes {
+            lp = lp.with_sizes(&s)?;
+        }
+        if let Some(k) = self.location_keys {
+            lp = lp.with_location_keys(&k)?;
+        }
+        if let Some(k) = self.density_keys {
+            lp = lp.with_density_keys(&k)?;
+        }
+        if let Some(k) = self.color_keys {
+            lp = lp.with_color_keys(&k)?;
+        }
+        if let Some(k) = self.size_keys {
+            lp = lp.with_size_keys(&k)?;
+        }
+        for (kind, easing) in self.easings {
+            lp = lp.with_easing(kind, easing);
+        }
+        for (kind, easings) in self.segment_easings {
+            lp = lp.with_segment_easings(kind, &easings);
+        }
+        for (kind, mode) in self.track_modes {
+            lp = lp.with_track_mode(kind, mode);
+        }
+        for (kind, edge) in self.edges {
+            lp = lp.with_edge(kind, edge);
+        }
+        for kind in self.seamless {
+            lp = lp.with_seamless(kind, true);
+        }
+        for (kind, noise) in self.noises {
+            lp = lp.with_noise(kind, noise)?;
+        }
+        if let Some(c) = self.size_curve {
+            lp = lp.with_size_curve(&c)?;
+        }
+        if let Some(shape) = self.shape {
+            lp = lp.with_shape(shape);
+        }
+        if let Some(mode) = self.blend {
+            lp = lp.with_blend_mode(mode);
+        }
+        if let Some(mode) = self.render_mode {
+            lp = lp.with_render_mode(mode);
+        }
+        if let Some((width, alpha)) = self.glow {
+            lp = lp.with_glow(width, alpha)?;
+        }
+        if let Some((v, spread)) = self.velocity {
+            lp = lp.with_velocity(v, spread)?;
+        }
+        if let Some(d) = self.directions {
+            lp = lp.with_directions(&d)?;
+        }
+        if let Some(radius) = self.spread {
+            lp = lp.with_spread(radius)?;
+        }
+        if let Some(s) = self.spreads {
+            lp = lp.with_spreads(&s)?;
+        }
+        if let Some(g) = self.gravity {
+            lp = lp.with_gravity(g);
+        }
+        if let Some(k) = self.drag {
+            lp = lp.with_drag(k)?;
+        }
+        if let Some((min, max)) = self.mass {
+            lp = lp.with_mass(min, max)?;
+        }
+        if let Some(s) = self.stretch {
+            lp = lp.with_stretch(s)?;
+        }
+        if let Some((s, spread)) = self.spin {
+            lp = lp.with_spin(s, spread)?;
+        }
+        if let Some(n) = self.trail {
+            lp = lp.with_trail(n);
+        }
+        if let Some(n) = self.max_particles {
+            lp = lp.with_max_particles(n);
+        }
+        if let Some(dt) = self.timestep {
+            lp = lp.with_timestep(dt)?;
+        }
+        if let Some(seed) = self.seed {
+            lp = lp.with_seed(seed);
+        }
+        if let Some(enabled) = self.rewind {
+            lp = lp.with_rewind(enabled);
+        }
+        if let Some(c) = self.culling {
+            lp = lp.with_culling(c);
+        }
+        if let Some(l) = self.lod {
+            lp = lp.with_lod(l)?;
+        }
+        if let Some((start, end)) = self.screen {
+            lp = lp.with_screen_space(start, end);
+        }
+        if let Some(field) = self.forces {
+            lp = lp.with_forces(field);
+        }
+        if let Some(colliders) = self.colliders {
+            lp = lp.with_colliders(colliders);
+        }
+        if let Some(p) = self.period {
+            lp = lp.with_period(p)?;
+        }
+        if let Some(d) = self.decay {
+            lp = lp.with_decay(d)?;
+        }
+        if let Some(d) = self.decays {
+            lp = lp.with_decays(&d)?;
+        }
+        if let Some(r) = self.rates {
+            lp = lp.with_rates(&r)?;
+        }
+        if let Some(b) = self.bursts {
+            lp = lp.with_bursts(&b)?;
+        }
+        if let Some(f) = self.fade_in {
+            lp = lp.with_fade_in(f)?;
+        }
+        if let Some(curve) = self.fade_curve {
+            lp = lp.with_fade_curve(curve);
+        }
+        if let Some(w) = self.warmup {
+            lp = lp.with_warmup(w)?;
+        }
+        if let Some(mode) = self.mode {
+            lp.start_with_mode(mode)?;
+        }
+        Ok(lp)
+    }
+}
+
+#[test]
+fn linear_particles_builder_test() {
+    let lp = LinearParticlesBuilder::new(Vec3::ZERO, Vec3::ONE)
+        .densities(&[0.5, 2.])
+        .build();
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidDensity(2.)));
+
+    let lp = LinearParticlesBuilder::new(Vec3::ZERO, Vec3::ONE)
+        .period(2.)
+        .start_with_mode(LoopMode::Loop)
+        .build()
+        .unwrap();
+    assert!(lp.is_looping());
 
-use macroquad::color::Color;
-use macroquad::math::Vec3;
-use macroquad::prelude::get_fps;
-use rand::rngs::ThreadRng;
-use rand::{rng, Rng};
-use std::slice::{Iter, IterMut};
-use std::time::Instant;
+    let lp = LinearParticlesBuilder::new(Vec3::ZERO, Vec3::ONE)
+        .decay(1.)
+        .decays(&[0.5, 1.])
+        .build();
+    assert_eq!(
+        lp.err(),
+        Some(LinearPLError::ConflictingSettings("decay", "decays"))
+    );
+    let lp = LinearParticlesBuilder::new(Vec3::ZERO, Vec3::ONE)
+        .rates(&[10.])
+        .densities(&[0.5])
+        .spreads(&[1.])
+        .spread(1.)
+        .build();
+    assert_eq!(
+        lp.err(),
+        Some(LinearPLError::ConflictingSettings("densities", "rates"))
+    );
 
-use crate::particle::Particle;
-use crate::particle_sys::ParticleSys;
-use crate::util::{
-    check_colors, check_decay, check_densities, check_locations, check_period, map_color_value,
-    map_float_value, map_location,
-};
+    let lp = LinearParticlesBuilder::new(Vec3::ZERO, Vec3::ONE)
+        .sizes(&[0., -0.5])
+        .build();
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidSize(-0.5)));
+
+    let lp = LinearParticlesBuilder::new(Vec3::ZERO, Vec3::ONE)
+        .velocity(Vec3::Y, -1.)
+        .build();
+    assert_eq!(lp.err(), Some(LinearPLError::InvalidSpread(-1.)));
+}
 
 // ***************************************
-// LinearParticles
+// LinearGrp
 // ***************************************
 
-/// LinearParticle system. User should be in charge of setting
-/// appropriate `locations`, `densities`, and `colors`
-/// such that their values are interpolated over the defined `period`
-/// in seconds. `decay` refers to the amount of time the particles
-/// generated stay visible.
+/// Group of LinearParticles objects with a synced period and
+/// start time. This is similar to `linearpl::groups::SyncGrp` but
+/// only allowed to contain LinearParticles objects.
 #[derive(Debug, Clone)]
-pub struct LinearParticles {
-    particles: Vec<Particle>,
-    start_location: Vec3,
-    end_location: Vec3,
-    locations: Vec<f32>,
-    densities: Vec<f32>,
-    colors: Vec<Color>,
-    period: f32,
-    decay: f32,
-    initialized: bool,
-    looping: bool,
+pub struct LinearGrp {
+    pub period: f32,
+    linear_particles: Vec<LinearParticles>,
+    states: Vec<ChildState>,
+    tags: Vec<String>,
     active: bool,
-    start_time: Instant,
-    rand_generator: ThreadRng,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    timer: SysTimer,
+    transform: Option<Mat4>,
+    tints: Vec<Color>,
+    modulation: Modulation,
+    modulated: Factors,
+    last_time: f32,
+    shared: bool,
+    pool: Vec<Particle>,
+    // index of the part each particle of the pool was emitted by
+    pool_parts: Vec<usize>,
+    forces: ForceField,
+    colliders: Colliders,
+    size_curve: Vec<f32>,
+    blend: BlendMode,
+    render_mode: RenderMode,
+    renderers: Renderers,
+    on_update: Option<UpdateCallback>,
+    clock: f32,
+    hooks: Hooks,
+    peak_count: usize,
 }
 
-impl LinearParticles {
-    /// Create a new LinearParticles struct with a starting location of
-    /// `start_loc` and an ending location of `end_loc`.
-    pub fn new(start_loc: Vec3, end_loc: Vec3) -> Self {
-        LinearParticles {
-            start_location: start_loc,
-            end_location: end_loc,
-            particles: Vec::new(),
-            locations: vec![0., 1.],
-            densities: vec![1.],
-            colors: vec![Color::new(1., 1., 1., 1.)],
-            period: 1.,
-            decay: 0.09,
-            initialized: false,
-            looping: false,
+impl LinearGrp {
+    /// Create a new group of LinearParticles objects.
+    pub fn new(period: f32, linparts: &[LinearParticles]) -> Self {
+        LinearGrp {
+            period,
+            linear_particles: linparts.into(),
+            states: vec![ChildState::Playing; linparts.len()],
+            tags: vec![String::new(); linparts.len()],
+            timer: SysTimer::new(),
             active: false,
-            start_time: Instant::now(),
-            rand_generator: rng(),
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            transform: None,
+            tints: Vec::new(),
+            modulation: Modulation::default(),
+            modulated: Factors::default(),
+            last_time: 0.,
+            shared: false,
+            pool: Vec::new(),
+            pool_parts: Vec::new(),
+            forces: ForceField::new(),
+            colliders: Colliders::new(),
+            size_curve: vec![1.],
+            blend: BlendMode::Alpha,
+            render_mode: RenderMode::Immediate,
+            renderers: Renderers::default(),
+            on_update: None,
+            clock: 0.,
+            hooks: Hooks::default(),
+            peak_count: 0,
         }
     }
 
-    // used in density calculations
-    fn should_generate(&mut self, chance: f32) -> bool {
-        chance > self.rand_generator.random_range(0.0..1.0)
+    /// Returns self with contained LinearParticles `linparts`.
+    pub fn with_systems(mut self, linparts: &[LinearParticles]) -> Self {
+        self.linear_particles = linparts.into();
+        self.states = vec![ChildState::Playing; linparts.len()];
+        self.tags = vec![String::new(); linparts.len()];
+        self
     }
 
-    /// Return self (consuming it) with decay `d`.
-    pub fn with_decay(mut self, d: f32) -> Result<Self, String> {
-        check_decay(d)?;
-        self.decay = d;
+    /// Return self with the tags `tags`, one per part, used to change
+    /// the state of parts by name with `set_tag_state()`.
+    pub fn with_tags(mut self, tags: &[&str]) -> Result<Self, LinearPLError> {
+        check_tags(tags, self.linear_particles.len())?;
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
         Ok(self)
     }
 
-    /// Return self (consuming it) with locations `l`.
-    pub fn with_locations(mut self, l: &[f32]) -> Result<Self, String> {
-        check_locations(l)?;
-        self.locations = l.into();
-        Ok(self)
+    /// Return the playback state of the part at `index`, if any.
+    pub fn child_state(&self, index: usize) -> Option<ChildState> {
+        self.states.get(index).cloned()
     }
 
-    /// Return self (consuming it) with densities `d`.
-    pub fn with_densities(mut self, d: &[f32]) -> Result<Self, String> {
-        check_densities(d)?;
-        self.densities = d.into();
-        Ok(self)
+    /// Set the playback state of the part at `index` to `state`, muting
+    /// or pausing it while the rest of the LinearGrp keeps running.
+    pub fn set_child_state(
+        &mut self,
+        index: usize,
+        state: ChildState,
+    ) -> Result<(), LinearPLError> {
+        let len = self.states.len();
+        let s = self
+            .states
+            .get_mut(index)
+            .ok_or(LinearPLError::IndexOutOfBounds {
+                context: "LinearGrp set_child_state",
+                index,
+                len,
+            })?;
+        *s = state;
+        Ok(())
     }
 
-    /// Return self (consuming it) with colors `c`.
-    pub fn with_colors(mut self, c: &[Color]) -> Result<Self, String> {
-        check_colors(c)?;
-        self.colors = c.into();
-        Ok(self)
+    /// Set the playback state of every part tagged `tag` to `state`,
+    /// returning how many parts were changed.
+    pub fn set_tag_state(&mut self, tag: &str, state: ChildState) -> usize {
+        let mut count = 0;
+        for (s, t) in self.states.iter_mut().zip(self.tags.iter()) {
+            if t == tag {
+                *s = state;
+                count += 1;
+            }
+        }
+        count
     }
 
-    /// Return self (consuming it) with start-location `sl`, ending location `el.
-    pub fn with_start_end(mut self, sl: Vec3, el: Vec3) -> Result<Self, String> {
-        self.start_location = sl;
-        self.end_location = el;
-        Ok(self)
+    /// Return self with the shared particle buffer set to `shared`. When
+    /// set, the particles spawned by every LinearParticles in the group
+    /// are kept in one buffer owned by the LinearGrp and drawn in a
+    /// single pass with the group's blend mode, forces and colliders.
+    /// Starting the group, or pushing a part onto it while running,
+    /// fails with a `SharedBuffer` error if a part has a transform, a
+    /// blend mode other than the group's, screen-space placement,
+    /// culling, a particle cap or rewind, none of which can be applied
+    /// to the shared buffer. Likewise a part's own forces, colliders,
+    /// size curve or update callback must be those set on the group,
+    /// as the shared buffer ages every particle with the group's.
+    pub fn with_shared_buffer(mut self, shared: bool) -> Self {
+        self.shared = shared;
+        self
     }
 
-    /// Return clone of self with decay `d`.
-    pub fn clone_with_decay(&self, d: f32) -> Result<Self, String> {
-        self.clone().with_decay(d)
+    /// Set the forces of `field` on every LinearParticles in the group,
+    /// and on the shared particle buffer when one is used.
+    pub fn set_forces(&mut self, field: ForceField) {
+        for lp in self.linear_particles.iter_mut() {
+            lp.set_forces(field.clone());
+        }
+        self.forces = field;
     }
 
-    /// Return clone self with locations `l`.
-    pub fn clone_with_locations(&self, l: &[f32]) -> Result<Self, String> {
-        self.clone().with_locations(l)
+    /// Return self (consuming it) with the forces of `field`.
+    pub fn with_forces(mut self, field: ForceField) -> Self {
+        self.set_forces(field);
+        self
     }
 
-    /// Return clone self with densities `d`.
-    pub fn clone_with_densities(&self, d: &[f32]) -> Result<Self, String> {
-        self.clone().with_densities(d)
+    /// Set the BlendMode `mode` on every LinearParticles in the group, and
+    /// on the shared particle buffer when one is used.
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        for lp in self.linear_particles.iter_mut() {
+            lp.set_blend_mode(mode);
+        }
+        self.blend = mode;
     }
 
-    /// Return clone self with colors `c`.
-    pub fn clone_with_colors(&self, c: &[Color]) -> Result<Self, String> {
-        self.clone().with_colors(c)
+    /// Return self (consuming it) drawn with BlendMode `mode`.
+    pub fn with_blend_mode(mut self, mode: BlendMode) -> Self {
+        self.set_blend_mode(mode);
+        self
     }
 
-    /// Return clone self with start-location `sl`, ending location `el`.
-    pub fn clone_with_start_end(&self, sl: Vec3, el: Vec3) -> Result<Self, String> {
-        self.clone().with_start_end(sl, el)
+    /// Set the RenderMode `mode` on every LinearParticles in the group, and
+    /// on the shared particle buffer when one is used, drawing the lines
+    /// of the whole group together when batched.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        for lp in self.linear_particles.iter_mut() {
+            lp.set_render_mode(mode);
+        }
+        self.render_mode = mode;
     }
 
-    /// Reverse the LinearParticles `locations`, `sizes`, `densities`, `colors`,
-    /// `start_location`, `end_location`, such that the presets defined for each
-    /// would create a reverse of the original graphic generated. This function
-    /// does not reset the elapsed time of the object.
-    pub fn reversed(mut self) {
-        std::mem::swap(&mut self.start_location, &mut self.end_location);
-        self.locations.reverse();
-        self.densities.reverse();
-        self.colors.reverse();
+    /// Return self (consuming it) with its lines drawn by `mode`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        self.set_render_mode(mode);
+        self
     }
-}
 
-// ***************************************
-// Impl's for LinearParticles
+    /// Set `colliders` on every LinearParticles in the group, and on the
+    /// shared particle buffer when one is used.
+    pub fn set_colliders(&mut self, colliders: Colliders) {
+        for lp in self.linear_particles.iter_mut() {
+            lp.set_colliders(colliders.clone());
+        }
+        self.colliders = colliders;
+    }
 
-impl ParticleSys for LinearParticles {
-    type T = Particle;
+    /// Return self (consuming it) colliding with `colliders`.
+    pub fn with_colliders(mut self, colliders: Colliders) -> Self {
+        self.set_colliders(colliders);
+        self
+    }
 
-    fn is_active(&self) -> bool {
-        self.active
+    /// Set the size curve `c` on every LinearParticles in the group, and
+    /// on the shared particle buffer when one is used.
+    pub fn set_size_curve(&mut self, c: &[f32]) -> Result<(), LinearPLError> {
+        check_size_curve(c)?;
+        for lp in self.linear_particles.iter_mut() {
+            lp.size_curve = c.into();
+        }
+        self.size_curve = c.into();
+        Ok(())
     }
 
-    fn is_looping(&self) -> bool {
-        self.active && self.looping
+    /// Return self (consuming it) with size curve `c`.
+    pub fn with_size_curve(mut self, c: &[f32]) -> Result<Self, LinearPLError> {
+        self.set_size_curve(c)?;
+        Ok(self)
     }
 
-    fn is_initialized(&mut self) -> bool {
-        self.initialized
+    /// Return self (consuming it) with callback `f` invoked on each live
+    /// Particle of every LinearParticles in the group, and of the shared
+    /// particle buffer when one is used, as `LinearParticles::with_update()`.
+    pub fn with_update(mut self, f: impl FnMut(&mut Particle, f32, f32) + 'static) -> Self {
+        let f = UpdateCallback::new(f);
+        for lp in self.linear_particles.iter_mut() {
+            lp.on_update = Some(f.clone());
+        }
+        self.on_update = Some(f);
+        self
     }
 
-    fn reset_time(&mut self) {
-        self.start_time = Instant::now();
+    // seconds the shared buffer's monotonic clock moves by to reach
+    // `current_time`. The group's time starts over from 0 each loop,
+    // ping-pong cycles included, so particles keep decaying across it.
+    fn clock_step(&self, current_time: f32) -> f32 {
+        match current_time >= self.last_time {
+            true => current_time - self.last_time,
+            false => current_time,
+        }
     }
 
-    fn elapsed_time(&mut self) -> Option<f32> {
-        Some(self.start_time.elapsed().as_secs_f32())
+    // advance the shared buffer's monotonic clock to `current_time`
+    fn advance_clock(&mut self, current_time: f32) {
+        self.clock += self.clock_step(current_time);
+        self.last_time = current_time;
     }
 
-    fn setup(&mut self, should_loop: bool, p: Option<f32>) -> Result<(), String> {
-        self.period = match p {
-            Some(p) => {
-                check_period(p)?;
-                p
+    // age the shared buffer to `current_time` without emitting, in steps
+    // no longer than the shortest timestep of the parts
+    fn drain_pool(&mut self, current_time: f32) {
+        let dt = self.clock_step(current_time);
+        self.last_time = current_time;
+        let timestep = self
+            .linear_particles
+            .iter()
+            .map(|ps| ps.timestep)
+            .reduce(f32::min)
+            .unwrap_or(DEFAULT_TIMESTEP);
+        let steps = ((dt / timestep).ceil() as usize).clamp(1, MAX_STEPS);
+        for _ in 0..steps {
+            self.clock += dt / steps as f32;
+            self.age_pool();
+        }
+    }
+
+    // age the particles of the shared buffer, dropping those that have
+    // decayed along with the parts they were emitted by
+    fn age_pool(&mut self) {
+        age_in_place(
+            &mut self.pool,
+            self.clock,
+            &self.forces,
+            &self.colliders,
+            &self.size_curve,
+            self.on_update.as_ref(),
+        );
+        let (parts, mut read, mut kept) = (&mut self.pool_parts, 0, 0);
+        self.pool.retain(|p| {
+            let keep = !p.is_finished();
+            if keep {
+                parts[kept] = parts[read];
+                kept += 1;
             }
-            None => self.period,
+            read += 1;
+            keep
+        });
+        parts.truncate(kept);
+    }
+
+    // check that `ps`, the part at `index`, has none of the settings the
+    // shared buffer can't draw it with
+    fn check_shared(&self, index: usize, ps: &LinearParticles) -> Result<(), LinearPLError> {
+        let setting = if ps.transform.is_some() {
+            "transform"
+        } else if ps.blend != self.blend {
+            "blend mode"
+        } else if ps.screen.is_some() {
+            "screen-space placement"
+        } else if ps.culling.is_some() {
+            "culling"
+        } else if ps.max_particles.is_some() {
+            "particle cap"
+        } else if ps.rewind {
+            "rewind"
+        } else if !(ps.forces.is_empty() || ps.forces.shares(&self.forces)) {
+            "forces"
+        } else if !(ps.colliders.is_empty() || ps.colliders == self.colliders) {
+            "colliders"
+        } else if ps.size_curve != [1.] && ps.size_curve != self.size_curve {
+            "size curve"
+        } else if ps
+            .on_update
+            .as_ref()
+            .is_some_and(|f| !self.on_update.as_ref().is_some_and(|g| f.ptr_eq(g)))
+        {
+            "update callback"
+        } else {
+            return Ok(());
         };
+        Err(LinearPLError::SharedBuffer { index, setting })
+    }
 
-        self.particles.clear();
-        self.looping = should_loop;
-        self.active = true;
-        self.initialized = true;
-        self.reset_time();
-        Ok(())
+    // draw the particles of the shared buffer emitted by parts that
+    // aren't muted
+    fn draw_pool(&mut self) {
+        if HEADLESS {
+            return;
+        }
+        let drawn = drawn_from_pool(&mut self.pool, &self.pool_parts, &self.states);
+        begin_blend(self.blend);
+        self.renderers.draw(self.render_mode, self.blend, drawn);
+        end_blend(self.blend);
     }
 
-    fn tear_down(&mut self) {
-        self.active = false;
-        self.initialized = false;
+    /// Add `ps` to the group. If the LinearGrp is running, `ps` is set up
+    /// with the group's period and mode and joins on the next frame.
+    pub fn push(&mut self, mut ps: LinearParticles) -> Result<(), LinearPLError> {
+        if self.active && self.shared {
+            self.check_shared(self.linear_particles.len(), &ps)?;
+        }
+        if self.active {
+            ps.setup(self.mode, Some(self.period))?;
+        }
+        self.linear_particles.push(ps);
+        self.states.push(ChildState::Playing);
+        self.tags.push(String::new());
+        Ok(())
     }
 
-    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, String> {
-        let current_time = match time {
-            Some(v) => v,
-            None => self.start_time.elapsed().as_secs_f32(),
-        };
+    /// Remove and return the system at `index`, stopping it first.
+    pub fn remove(&mut self, index: usize) -> Result<LinearParticles, LinearPLError> {
+        let len = self.linear_particles.len();
+        if index >= len {
+            return Err(LinearPLError::IndexOutOfBounds {
+                context: "LinearGrp remove",
+                index,
+                len,
+            });
+        }
+        let mut ps = self.linear_particles.remove(index);
+        self.states.remove(index);
+        self.tags.remove(index);
+        // particles left in the pool by the part are drawn until they decay
+        for i in self.pool_parts.iter_mut() {
+            *i = match (*i).cmp(&index) {
+                Ordering::Less => *i,
+                Ordering::Equal => usize::MAX,
+                Ordering::Greater => *i - 1,
+            };
+        }
+        ps.tear_down();
+        Ok(ps)
+    }
 
-        if current_time <= self.period {
-            let gen_flag = map_float_value(&self.densities, current_time, self.period)?;
-            if self.should_generate(gen_flag) {
-                let nft = 4.0 / get_fps() as f32;
-                let p = Particle::new_line(
-                    map_location(
-                        &self.locations,
-                        self.start_location,
-                        self.end_location,
-                        current_time,
-                        self.period,
-                    )?,
-                    map_location(
-                        &self.locations,
-                        self.start_location,
-                        self.end_location,
-                        current_time + nft,
-                        self.period,
-                    )?,
-                    map_color_value(&self.colors, current_time, self.period)?,
-                    self.decay,
-                    true,
-                )?;
-                self.particles.push(p);
+    /// Keep only the systems for which `f` returns `true`, stopping
+    /// the others.
+    pub fn retain(&mut self, mut f: impl FnMut(&LinearParticles) -> bool) {
+        let keep: Vec<bool> = self.linear_particles.iter().map(&mut f).collect();
+        for (ps, k) in self.linear_particles.iter_mut().zip(keep.iter()) {
+            if !k {
+                ps.tear_down();
             }
         }
+        let mut kept = keep.iter();
+        self.linear_particles
+            .retain(|_| *kept.next().unwrap_or(&true));
+        let mut kept = keep.iter();
+        self.states.retain(|_| *kept.next().unwrap_or(&true));
+        let mut kept = keep.iter();
+        self.tags.retain(|_| *kept.next().unwrap_or(&true));
+        // renumber the parts of the pool, drawing those of removed parts
+        // until they decay
+        let mut next = 0;
+        let renumbered: Vec<usize> = keep
+            .iter()
+            .map(|k| match k {
+                true => {
+                    next += 1;
+                    next - 1
+                }
+                false => usize::MAX,
+            })
+            .collect();
+        for i in self.pool_parts.iter_mut() {
+            *i = renumbered.get(*i).copied().unwrap_or(usize::MAX);
+        }
+    }
 
-        self.particles.retain_mut(|p| !(*p).draw());
-        Ok(self.start_time.elapsed().as_secs_f32() <= self.period)
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the LinearGrp finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
     }
 
-    fn iter(&self) -> Option<Iter<'_, Self::T>> {
-        Some(self.particles.iter())
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping LinearGrp wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
     }
 
-    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
-        Some(self.particles.iter_mut())
+    /// Set the transform `m` applied to everything drawn by the parts of
+    /// the LinearGrp, moving, rotating or scaling them together.
+    pub fn set_transform(&mut self, m: Mat4) {
+        self.transform = Some(m);
     }
 
-    fn with_period(mut self, p: f32) -> Result<Self, String> {
-        check_period(p)?;
-        self.period = p;
+    /// Return self (consuming it) with the draw time transform `m`.
+    pub fn with_transform(mut self, m: Mat4) -> Self {
+        self.set_transform(m);
+        self
+    }
+
+    /// Set the translation of the draw time transform to `t`, keeping
+    /// its rotation and scale.
+    pub fn set_translation(&mut self, t: Vec3) {
+        let (s, r, _) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    /// Set the rotation of the draw time transform to `r`, keeping its
+    /// translation and scale.
+    pub fn set_rotation(&mut self, r: Quat) {
+        let (s, _, t) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    /// Set the scale of the draw time transform to `s`, keeping its
+    /// translation and rotation.
+    pub fn set_scale(&mut self, s: Vec3) {
+        let (_, r, t) = self.transform_parts();
+        self.transform = Some(Mat4::from_scale_rotation_translation(s, r, t));
+    }
+
+    // scale, rotation and translation of the current transform
+    fn transform_parts(&self) -> (Vec3, Quat, Vec3) {
+        self.transform
+            .unwrap_or(Mat4::IDENTITY)
+            .to_scale_rotation_translation()
+    }
+
+    /// Set the tint track `tints` combined with the color of every
+    /// particle drawn by the parts of the LinearGrp, linearly interpolated
+    /// over its period.
+    pub fn set_tints(&mut self, tints: &[Color]) -> Result<(), LinearPLError> {
+        check_colors(tints)?;
+        self.tints = tints.into();
+        Ok(())
+    }
+
+    /// Return self (consuming it) with the tint track `tints`.
+    pub fn with_tints(mut self, tints: &[Color]) -> Result<Self, LinearPLError> {
+        self.set_tints(tints)?;
         Ok(self)
     }
-}
 
-impl Default for LinearParticles {
-    fn default() -> Self {
-        LinearParticles::new(Vec3::new(0., 0., 0.), Vec3::new(0., 0., 0.))
+    /// Return self (consuming it) with the constant tint `tint`.
+    pub fn with_tint(self, tint: Color) -> Result<Self, LinearPLError> {
+        self.with_tints(&[tint])
     }
-}
 
-// ***************************************
-// LinearGrp
-// ***************************************
+    /// Return self (consuming it) with the constant alpha multiplier
+    /// `alpha`, fading the parts without changing their colors.
+    pub fn with_alpha(self, alpha: f32) -> Result<Self, LinearPLError> {
+        self.with_tint(Color::new(1., 1., 1., alpha))
+    }
 
-/// Group of LinearParticles objects with a synced period and
-/// start time. This is similar to `linearpl::groups::SyncGrp` but
-/// only allowed to contain LinearParticles objects.
-#[derive(Debug, Clone)]
-pub struct LinearGrp {
-    pub period: f32,
-    linear_particles: Vec<LinearParticles>,
-    active: bool,
-    looping: bool,
-    initialized: bool,
-    start_time: Instant,
-}
+    /// Scale `target` of every system within the LinearGrp by the signal
+    /// returned by `f`, sampled once each frame. See
+    /// `linearpl::modulation`.
+    pub fn set_modulation(&mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) {
+        self.modulation.set(target, SignalCallback::new(f));
+    }
 
-impl LinearGrp {
-    /// Create a new group of LinearParticles objects.
-    pub fn new(period: f32, linparts: &[LinearParticles]) -> Self {
-        LinearGrp {
-            period,
-            linear_particles: linparts.into(),
-            start_time: Instant::now(),
-            active: false,
-            looping: false,
-            initialized: false,
+    /// Return self (consuming it) with `target` scaled by the signal
+    /// returned by `f`.
+    pub fn with_modulation(mut self, target: ModTarget, f: impl FnMut() -> f32 + 'static) -> Self {
+        self.set_modulation(target, f);
+        self
+    }
+
+    // tint to combine with the parts' colors at `current_time`, if any
+    fn tint_at(&self, current_time: f32) -> Result<Option<Color>, LinearPLError> {
+        if self.tints.is_empty() {
+            return Ok(None);
         }
+        let (r, g, b, a) = map_color_value(
+            &self.tints,
+            current_time,
+            self.period,
+            &[Easing::Linear],
+            TrackMode::Linear,
+        )?;
+        Ok(Some(Color::new(r, g, b, a)))
     }
 
-    /// Returns self with contained LinearParticles `linparts`.
-    pub fn with_systems(mut self, linparts: &[LinearParticles]) -> Self {
-        self.linear_particles = linparts.into();
-        self
+    // update the parts as `update_frame()` does, within the modulation
+    // of the group
+    fn update_parts(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let child_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        if self.shared {
+            self.advance_clock(current_time);
+            // muted parts emit as usual, their particles skipped when
+            // the pool is drawn
+            let parts = self.linear_particles.iter_mut().zip(self.states.iter());
+            for (i, (ps, _)) in parts.enumerate().filter(|(_, (_, s))| s.is_simulated()) {
+                let start = self.pool.len();
+                ps.emit(Some(child_time), &mut self.pool)?;
+                // particles are stamped with the part's clock at the step
+                // they spawned in, carried over to the group's clock with
+                // the age each has reached by the end of the frame
+                for p in self.pool[start..].iter_mut() {
+                    p.set_time(ps.clock);
+                    let age = p.age();
+                    p.set_time(self.clock - age);
+                    p.reset();
+                }
+                self.pool_parts.resize(self.pool.len(), i);
+            }
+            self.age_pool();
+        } else {
+            self.last_time = current_time;
+            for (ps, _) in self
+                .linear_particles
+                .iter_mut()
+                .zip(self.states.iter())
+                .filter(|(_, s)| s.is_simulated())
+            {
+                #[cfg(feature = "parallel")]
+                ps.defer_aging();
+                ps.update_frame(Some(child_time))?;
+            }
+            #[cfg(feature = "parallel")]
+            age_parts(&mut self.linear_particles);
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
     }
 }
 
@@ -297,7 +4376,7 @@ impl ParticleSys for LinearGrp {
     }
 
     fn is_looping(&self) -> bool {
-        self.active && self.looping
+        self.active && self.mode.repeats_after(self.cycle)
     }
 
     fn is_initialized(&mut self) -> bool {
@@ -305,14 +4384,26 @@ impl ParticleSys for LinearGrp {
     }
 
     fn reset_time(&mut self) {
-        self.start_time = Instant::now();
+        self.timer.reset();
     }
 
     fn elapsed_time(&mut self) -> Option<f32> {
-        Some(self.start_time.elapsed().as_secs_f32())
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
     }
 
-    fn setup(&mut self, should_loop: bool, p: Option<f32>) -> Result<(), String> {
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
         self.period = match p {
             Some(p) => {
                 check_period(p)?;
@@ -320,14 +4411,26 @@ impl ParticleSys for LinearGrp {
             }
             None => self.period,
         };
+        if self.shared {
+            for (i, ps) in self.linear_particles.iter().enumerate() {
+                self.check_shared(i, ps)?;
+            }
+        }
 
         for ps in self.linear_particles.iter_mut() {
-            ps.setup(should_loop, Some(self.period))?;
+            ps.setup(mode, Some(self.period))?;
         }
+        self.pool.clear();
+        self.pool_parts.clear();
+        self.clock = 0.;
+        self.last_time = 0.;
+        self.peak_count = 0;
 
-        self.looping = should_loop;
+        self.mode = mode;
+        self.cycle = 0;
         self.active = true;
         self.initialized = true;
+        self.hooks.reset();
         self.reset_time();
         Ok(())
     }
@@ -341,17 +4444,12 @@ impl ParticleSys for LinearGrp {
         self.initialized = false;
     }
 
-    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, String> {
-        let current_time = match time {
-            None => Some(self.start_time.elapsed().as_secs_f32()),
-            v => v,
-        };
-
-        for ps in self.linear_particles.iter_mut() {
-            ps.next_frame(current_time)?;
-        }
-
-        Ok(current_time <= Some(self.period))
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        self.modulated = self.modulation.sample();
+        push_modulation(self.modulated);
+        let updated = self.update_parts(time);
+        pop_modulation();
+        updated
     }
 
     fn iter(&self) -> Option<Iter<'_, Self::T>> {
@@ -362,10 +4460,97 @@ impl ParticleSys for LinearGrp {
         Some(self.linear_particles.iter_mut())
     }
 
-    fn with_period(mut self, p: f32) -> Result<Self, String> {
+    fn particle_count(&self) -> usize {
+        let owned: usize = self
+            .linear_particles
+            .iter()
+            .map(|ps| ps.particle_count())
+            .sum();
+        owned + self.pool.len()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.linear_particles
+            .iter()
+            .map(|ps| ps.spawned_total())
+            .sum()
+    }
+
+    fn for_each_particle(&self, f: &mut dyn FnMut(&Particle)) {
+        for ps in self.linear_particles.iter() {
+            ps.for_each_particle(f);
+        }
+        self.pool.iter().for_each(f);
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        begin_draw(self.transform, None);
+        for ps in self.linear_particles.iter() {
+            ps.draw_debug(gizmos);
+        }
+        end_draw(self.transform, None);
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
         check_period(p)?;
         self.period = p;
-        Ok(self)
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    fn handle_loop(&mut self) {
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+
+        if self.shared {
+            self.drain_pool(current_time);
+            return Ok(!self.pool.is_empty());
+        }
+
+        self.last_time = current_time;
+        self.linear_particles
+            .iter_mut()
+            .zip(self.states.iter())
+            .filter(|(_, s)| s.is_simulated())
+            .try_fold(false, |remaining, (ps, _)| {
+                Ok(ps.drain_frame(Some(current_time))? || remaining)
+            })
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        let tint = self.modulated.tinted(self.tint_at(self.last_time)?);
+        begin_draw(self.transform, tint);
+        self.draw_pool();
+        let drawn = self
+            .linear_particles
+            .iter_mut()
+            .zip(self.states.iter())
+            .filter(|(_, s)| s.is_drawn())
+            .try_for_each(|(ps, _)| ps.redraw_frame());
+        end_draw(self.transform, tint);
+        drawn
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
     }
 }
 
@@ -374,3 +4559,120 @@ impl Default for LinearGrp {
         LinearGrp::new(1.0, &[])
     }
 }
+
+#[test]
+fn linear_grp_shared_mute_test() {
+    let lp = LinearParticles::builder(Vec3::ZERO, Vec3::X)
+        .densities(&[0.])
+        .bursts(&[(0., 4)])
+        .build()
+        .unwrap();
+    let mut grp = LinearGrp::new(1., &[lp.clone(), lp.clone(), lp]).with_shared_buffer(true);
+    grp.set_child_state(1, ChildState::Muted).unwrap();
+    grp.set_child_state(2, ChildState::Paused).unwrap();
+    grp.start().unwrap();
+    grp.update_frame(Some(0.1)).unwrap();
+    assert_eq!(grp.particle_count(), 8);
+    let drawn =
+        |grp: &mut LinearGrp| drawn_from_pool(&mut grp.pool, &grp.pool_parts, &grp.states).count();
+    assert_eq!(drawn(&mut grp), 4);
+
+    grp.remove(0).unwrap();
+    assert_eq!(
+        grp.pool_parts,
+        [usize::MAX; 4]
+            .iter()
+            .chain(&[0; 4])
+            .copied()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(drawn(&mut grp), 4);
+    grp.set_child_state(0, ChildState::Playing).unwrap();
+    assert_eq!(drawn(&mut grp), 8);
+}
+
+#[test]
+fn linear_grp_shared_settings_test() {
+    let lp = LinearParticles::default;
+    let mut grp =
+        LinearGrp::new(1., &[lp(), lp().with_transform(Mat4::IDENTITY)]).with_shared_buffer(true);
+    assert_eq!(
+        grp.start(),
+        Err(LinearPLError::SharedBuffer {
+            index: 1,
+            setting: "transform"
+        })
+    );
+
+    // blend modes set through the group match its own
+    let mut grp = LinearGrp::new(1., &[lp(), lp().with_blend_mode(BlendMode::Additive)])
+        .with_shared_buffer(true);
+    assert!(grp.start().is_err());
+    grp.set_blend_mode(BlendMode::Additive);
+    grp.start().unwrap();
+    assert_eq!(
+        grp.push(
+            lp().with_blend_mode(BlendMode::Additive)
+                .with_culling(Culling::new())
+        ),
+        Err(LinearPLError::SharedBuffer {
+            index: 2,
+            setting: "culling"
+        })
+    );
+    assert_eq!(grp.linear_particles.len(), 2);
+
+    // the shared buffer neither caps nor rewinds its particles, and ages
+    // them with the group's forces rather than a part's own
+    let capped = lp().with_max_particles(10);
+    let grp = LinearGrp::new(1., &[lp(), capped]).with_shared_buffer(true);
+    assert_eq!(
+        grp.clone().start(),
+        Err(LinearPLError::SharedBuffer {
+            index: 1,
+            setting: "particle cap"
+        })
+    );
+    let wind = ForceField::new().with(crate::forces::Wind(Vec3::X));
+    let mut grp = LinearGrp::new(1., &[lp().with_forces(wind.clone())]).with_shared_buffer(true);
+    assert!(grp.start().is_err());
+    grp.set_forces(wind);
+    grp.start().unwrap();
+}
+
+#[test]
+fn linear_grp_shared_birth_test() {
+    let lp = LinearParticles::default()
+        .with_timestep(0.125)
+        .unwrap()
+        .with_rates(&[8.])
+        .unwrap()
+        .with_decay(10.)
+        .unwrap();
+    let mut grp = LinearGrp::new(1., &[lp]).with_shared_buffer(true);
+    grp.start_at(0.).unwrap();
+    grp.set_time(0.5);
+    grp.update().unwrap();
+
+    // particles spawned over the frame keep the ages of their own steps
+    let ages: Vec<f32> = grp.pool.iter().map(|p| p.age()).collect();
+    assert_eq!(ages, vec![0.375, 0.25, 0.125, 0.]);
+}
+
+#[test]
+fn linear_grp_shared_clock_test() {
+    let mut grp = LinearGrp::new(1., &[LinearParticles::default()]).with_shared_buffer(true);
+    grp.start_loop().unwrap();
+    grp.advance_clock(0.75);
+    grp.advance_clock(0.25);
+    assert_eq!(grp.clock, 1.);
+    assert_eq!(grp.particle_count(), 0);
+
+    // the group's time starts over each ping-pong cycle as well
+    grp.start_with_mode(LoopMode::PingPong).unwrap();
+    grp.advance_clock(0.75);
+    grp.advance_clock(0.25);
+    assert_eq!(grp.clock, 1.);
+    grp.drain_frame(Some(0.5)).unwrap();
+    assert!((grp.clock - 1.25).abs() < 1e-5);
+}
@@ -0,0 +1,193 @@
+//! # Load
+//!
+//! Reading effects from text documents. A document holds a single
+//! `SysConfig`, either a system or a group nesting further systems and
+//! groups, and loading it returns the ready-to-run object it describes:
+//!
+//! ```ignore
+//! let mut effect = linearpl::load::from_ron("effects/seq.ron")?;
+//!
+//! loop {
+//!     effect.run()?;
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! RON documents are read with the `ron` feature, and JSON documents,
+//! following the same layout, with the `json` feature. In RON, optional
+//! settings of a system can be written without `Some(..)`; in JSON, a
+//! config is an object keyed by its variant:
+//!
+//! ```json
+//! { "Group": { "kind": "Seq", "period": 2.0, "parts": [
+//!     { "Particles": { "end": [1.0, 0.0, 0.0], "decay": 0.5 } }
+//! ] } }
+//! ```
+//!
+//! Settings that are rejected, whether while parsing or validating, are
+//! reported as an `InvalidField` error naming the path to the setting
+//! within the document, such as `parts[1].densities`.
+
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "ron")]
+use ron::extensions::Extensions;
+#[cfg(feature = "ron")]
+use ron::Options;
+
+use crate::config::SysConfig;
+use crate::error::LinearPLError;
+use crate::particle_sys::ParticleSysDyn;
+
+// contents of the effect file at `path`
+fn read(path: &Path) -> Result<String, LinearPLError> {
+    fs::read_to_string(path).map_err(|e| LinearPLError::io(path, e))
+}
+
+/// Read the effect file at `path` and return the system it describes,
+/// reading it as RON or JSON by its `.ron` or `.json` extension.
+pub fn from_file(path: impl AsRef<Path>) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+    let path = path.as_ref();
+    match path.extension().and_then(|e| e.to_str()) {
+        #[cfg(feature = "ron")]
+        Some("ron") => from_ron(path),
+        #[cfg(feature = "json")]
+        Some("json") => from_json(path),
+        _ => Err(LinearPLError::Parse(format!(
+            "'{}' is not a supported effect file",
+            path.display()
+        ))),
+    }
+}
+
+/// Read the RON document at `path` and return the system it describes.
+/// Returns an error if the file can't be read, isn't a valid `SysConfig`
+/// or describes an invalid system.
+#[cfg(feature = "ron")]
+pub fn from_ron(path: impl AsRef<Path>) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+    from_ron_str(&read(path.as_ref())?)
+}
+
+/// Return the system described by the RON document `doc`.
+#[cfg(feature = "ron")]
+pub fn from_ron_str(doc: &str) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+    config_from_ron_str(doc)?.build()
+}
+
+/// Return the `SysConfig` held by the RON document `doc`, without
+/// building it.
+#[cfg(feature = "ron")]
+pub fn config_from_ron_str(doc: &str) -> Result<SysConfig, LinearPLError> {
+    Options::default()
+        .with_default_extension(Extensions::IMPLICIT_SOME)
+        .from_str(doc)
+        .map_err(|e| LinearPLError::Parse(e.to_string()))
+}
+
+/// Read the JSON document at `path` and return the system it describes.
+/// Returns an error if the file can't be read, isn't a valid `SysConfig`
+/// or describes an invalid system.
+#[cfg(feature = "json")]
+pub fn from_json(path: impl AsRef<Path>) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+    from_json_str(&read(path.as_ref())?)
+}
+
+/// Return the system described by the JSON document `doc`.
+#[cfg(feature = "json")]
+pub fn from_json_str(doc: &str) -> Result<Box<dyn ParticleSysDyn>, LinearPLError> {
+    config_from_json_str(doc)?.build()
+}
+
+/// Return the `SysConfig` held by the JSON document `doc`, without
+/// building it. Values of the wrong type are reported as an
+/// `InvalidField` error naming where they were found.
+#[cfg(feature = "json")]
+pub fn config_from_json_str(doc: &str) -> Result<SysConfig, LinearPLError> {
+    let de = &mut serde_json::Deserializer::from_str(doc);
+    serde_path_to_error::deserialize(de).map_err(|e| {
+        let field = json_field(e.path());
+        let parse = LinearPLError::Parse(e.into_inner().to_string());
+        match field.is_empty() {
+            true => parse,
+            false => parse.in_field(&field),
+        }
+    })
+}
+
+// path to a JSON value in the field names of configs, leaving out the
+// variant keys a config is nested under
+#[cfg(feature = "json")]
+fn json_field(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+    let mut field = String::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => field += &format!("[{}]", index),
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                if key != "Particles" && key != "Group" {
+                    if !field.is_empty() {
+                        field.push('.');
+                    }
+                    field += key;
+                }
+            }
+            Segment::Unknown => {}
+        }
+    }
+    field
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn from_ron_test() {
+    let effect = from_ron(concat!(env!("CARGO_MANIFEST_DIR"), "/effects/seq.ron")).unwrap();
+    let info = effect.describe();
+    assert_eq!(info.name, "SeqGrp");
+    assert_eq!(info.children.len(), 8);
+    assert!(info.looping);
+
+    let nested = config_from_ron_str(
+        "Group((kind: Stagger(offset: 0.5), parts: [
+            Particles((end: (1., 0., 0.), decay: 0.5, colors: [(1., 0., 0., 1.)])),
+            Group((parts: [Particles(())])),
+        ]))",
+    )
+    .unwrap();
+    assert!(nested.build().is_ok());
+
+    let e = from_ron_str("Particles((densities: [2.]))").err().unwrap();
+    assert_eq!(e.root(), &LinearPLError::InvalidDensity(2.));
+    assert!(matches!(
+        from_ron_str("Particles((decay: \"long\"))"),
+        Err(LinearPLError::Parse(_))
+    ));
+    assert!(matches!(
+        from_ron("effects/missing.ron"),
+        Err(LinearPLError::Io { .. })
+    ));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn from_json_test() {
+    let effect = from_json_str(
+        r#"{ "Group": { "kind": { "Stagger": { "offset": 0.5 } }, "mode": "Loop", "parts": [
+            { "Particles": { "end": [1.0, 0.0, 0.0], "colors": [[1.0, 0.0, 0.0, 1.0]] } },
+            { "Group": { "kind": "Seq", "parts": [{ "Particles": {} }] } }
+        ] } }"#,
+    )
+    .unwrap();
+    assert_eq!(effect.describe().children.len(), 2);
+
+    let invalid = |doc| from_json_str(doc).err().unwrap();
+    let e = invalid(
+        r#"{ "Group": { "parts": [{ "Particles": {} }, { "Group": { "parts": [{ "Particles": { "colors": [] } }] } }] } }"#,
+    );
+    assert_eq!(e.root(), &LinearPLError::EmptyTrack("colors"));
+    assert!(e.to_string().starts_with("parts[1].parts[0].colors: "));
+
+    let e = invalid(r#"{ "Group": { "parts": [{ "Particles": { "decay": "long" } }] } }"#);
+    assert!(matches!(e.root(), LinearPLError::Parse(_)));
+    assert!(e.to_string().starts_with("parts[0].decay: "));
+}
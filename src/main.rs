@@ -9,6 +9,7 @@
 
 use macroquad::prelude::*;
 
+use linearpl::error::LinearPLError;
 use linearpl::groups::{SeqGrp, SyncGrp};
 use linearpl::linear_particles::LinearParticles;
 use linearpl::particle::Particle;
@@ -17,7 +18,7 @@ use linearpl::particle_sys::ParticleSys;
 const CAM_SPEED: f32 = 0.8;
 
 #[macroquad::main("LinearPL Example")]
-async fn main() -> Result<(), String> {
+async fn main() -> Result<(), LinearPLError> {
     let up = vec3(0., 1., 0.);
 
     let mut hrot: f32 = 1.57;
@@ -48,7 +49,7 @@ async fn main() -> Result<(), String> {
 
     // some linear particle systems
     let lin_part_h: LinearParticles =
-        LinearParticles::new((-1., offset, 3.).into(), (1., offset, 3.).into())
+        LinearParticles::new(vec3(-1., offset, 3.), vec3(1., offset, 3.))
             .with_decay(1.4)?
             .with_locations(&[0., 0., 1., 1.])?
             .with_colors(&[
@@ -60,7 +61,7 @@ async fn main() -> Result<(), String> {
                 Color::new(0., 0., 1., 0.),
             ])?;
     let lin_part_v: LinearParticles =
-        LinearParticles::new((-1., offset, 3.).into(), (-1., 2. + offset, 3.).into())
+        LinearParticles::new(vec3(-1., offset, 3.), vec3(-1., 2. + offset, 3.))
             .with_decay(2.0)?
             .with_locations(&[1., 0., 1.])?
             .with_colors(&[PINK, PURPLE, RED, VIOLET])?;
@@ -86,13 +87,15 @@ async fn main() -> Result<(), String> {
         ],
     );
 
-    let lil_lin_part = LinearParticles::new(
-        (-0.75, 0.25 + offset, 3.25).into(),
-        (-0.75, 1.75 + offset, 3.25).into(),
+    // settings can also be collected with a builder and validated once
+    let lil_lin_part = LinearParticles::builder(
+        vec3(-0.75, 0.25 + offset, 3.25),
+        vec3(-0.75, 1.75 + offset, 3.25),
     )
-    .with_decay(0.05)?
-    .with_locations(&[1., 1., 0.5, 0., 0.])?
-    .with_colors(&[SKYBLUE, GREEN])?;
+    .decay(0.05)
+    .locations(&[1., 1., 0.5, 0., 0.])
+    .colors(&[SKYBLUE, GREEN])
+    .build()?;
 
     let mut linear_seq = SeqGrp::new(
         4.,
@@ -100,38 +103,38 @@ async fn main() -> Result<(), String> {
             lil_lin_part
                 .clone_with_colors(&[GREEN, SKYBLUE])?
                 .with_start_end(
-                    (-0.75, 1.75 + offset, 3.25).into(),
-                    (0.75, 0.25 + offset, 4.75).into(),
+                    vec3(-0.75, 1.75 + offset, 3.25),
+                    vec3(0.75, 0.25 + offset, 4.75),
                 )?,
             lil_lin_part.clone_with_start_end(
-                (0.75, 0.25 + offset, 4.75).into(),
-                (0.75, 1.75 + offset, 4.75).into(),
+                vec3(0.75, 0.25 + offset, 4.75),
+                vec3(0.75, 1.75 + offset, 4.75),
             )?,
             lil_lin_part
                 .clone_with_colors(&[GREEN, SKYBLUE])?
                 .with_start_end(
-                    (0.75, 1.75 + offset, 4.75).into(),
-                    (0.75, 0.25 + offset, 3.25).into(),
+                    vec3(0.75, 1.75 + offset, 4.75),
+                    vec3(0.75, 0.25 + offset, 3.25),
                 )?,
             lil_lin_part.clone_with_start_end(
-                (0.75, 0.25 + offset, 3.25).into(),
-                (0.75, 1.75 + offset, 3.25).into(),
+                vec3(0.75, 0.25 + offset, 3.25),
+                vec3(0.75, 1.75 + offset, 3.25),
             )?,
             lil_lin_part
                 .clone_with_colors(&[GREEN, SKYBLUE])?
                 .with_start_end(
-                    (0.75, 1.75 + offset, 3.25).into(),
-                    (-0.75, 0.25 + offset, 4.75).into(),
+                    vec3(0.75, 1.75 + offset, 3.25),
+                    vec3(-0.75, 0.25 + offset, 4.75),
                 )?,
             lil_lin_part.clone_with_start_end(
-                (-0.75, 0.25 + offset, 4.75).into(),
-                (-0.75, 1.75 + offset, 4.75).into(),
+                vec3(-0.75, 0.25 + offset, 4.75),
+                vec3(-0.75, 1.75 + offset, 4.75),
             )?,
             lil_lin_part
                 .clone_with_colors(&[GREEN, SKYBLUE])?
                 .with_start_end(
-                    (-0.75, 1.75 + offset, 4.75).into(),
-                    (-0.75, 0.25 + offset, 3.25).into(),
+                    vec3(-0.75, 1.75 + offset, 4.75),
+                    vec3(-0.75, 0.25 + offset, 3.25),
                 )?,
             lil_lin_part,
         ],
@@ -139,6 +142,7 @@ async fn main() -> Result<(), String> {
 
     let base_grid_line = LinearParticles::default()
         .with_decay(0.8)?
+        .with_warmup(1.)?
         .with_densities(&[0., 1., 0., 1., 0.])?
         .with_locations(&[0., 1., 0.])?
         .with_colors(&[VIOLET, RED, MAROON, MAGENTA])?;
@@ -152,12 +156,12 @@ async fn main() -> Result<(), String> {
 
     for i in (-(size * (1. / res)) as i32 + 1)..((size * (1. / res)) as i32) {
         grid_lines_x.push(base_grid_line.clone_with_start_end(
-            (size, (i as f32 * res) + grid_offset, depth).into(),
-            (-size, (i as f32 * res) + grid_offset, depth).into(),
+            vec3(size, (i as f32 * res) + grid_offset, depth),
+            vec3(-size, (i as f32 * res) + grid_offset, depth),
         )?);
         grid_lines_x_rev.push(base_grid_line.clone_with_start_end(
-            (-size, (i as f32 * res) + grid_offset, depth).into(),
-            (size, (i as f32 * res) + grid_offset, depth).into(),
+            vec3(-size, (i as f32 * res) + grid_offset, depth),
+            vec3(size, (i as f32 * res) + grid_offset, depth),
         )?);
     }
     let mut grid = SyncGrp::new(
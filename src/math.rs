@@ -0,0 +1,112 @@
+//! # Math
+//!
+//! Vector, matrix and color types used throughout LinearPL. Vectors and
+//! matrices are glam's, the same types macroquad re-exports, and `Color`
+//! is macroquad's own when the `macroquad` feature is enabled, so values
+//! pass between the two without conversion.
+//!
+//! Without the `macroquad` feature, `Color` is a plain struct with the
+//! same fields and constructors, letting the simulation core build and
+//! run its tests without macroquad at all.
+//!
+//! Emitters take their locations and vectors as any `Position`, so the
+//! same systems serve 2D and 3D scenes. Particles are simulated in 3D,
+//! with `Vec2`s lying in the `z = 0` plane:
+//!
+//! ```ignore
+//! let sparks = LinearParticles::builder(vec2(-1., 0.), vec2(1., 0.))
+//!     .velocity(vec2(0., 2.), 0.5)
+//!     .gravity(vec2(0., -9.8))
+//!     .build()?;
+//! ```
+
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Sub};
+
+pub use glam::{Mat3, Mat4, Quat, Vec2, Vec3, Vec4, Vec4Swizzles};
+
+/// Point or vector of a 2D or 3D scene.
+pub trait Position:
+    Copy + PartialEq + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+    /// Origin, or the vector of no length.
+    const ZERO: Self;
+
+    /// Number of components, `2` or `3`.
+    const DIMENSIONS: usize;
+
+    /// Return the point `t` of the way from `self` to `rhs`.
+    fn lerp(self, rhs: Self, t: f32) -> Self;
+
+    /// Return the point in 3D, in the `z = 0` plane for 2D points.
+    fn to_vec3(self) -> Vec3;
+
+    /// Return the point nearest to `v`, dropping `z` for 2D points.
+    fn from_vec3(v: Vec3) -> Self;
+}
+
+impl Position for Vec2 {
+    const ZERO: Self = Vec2::ZERO;
+    const DIMENSIONS: usize = 2;
+
+    fn lerp(self, rhs: Self, t: f32) -> Self {
+        Vec2::lerp(self, rhs, t)
+    }
+
+    fn to_vec3(self) -> Vec3 {
+        self.extend(0.)
+    }
+
+    fn from_vec3(v: Vec3) -> Self {
+        v.truncate()
+    }
+}
+
+impl Position for Vec3 {
+    const ZERO: Self = Vec3::ZERO;
+    const DIMENSIONS: usize = 3;
+
+    fn lerp(self, rhs: Self, t: f32) -> Self {
+        Vec3::lerp(self, rhs, t)
+    }
+
+    fn to_vec3(self) -> Vec3 {
+        self
+    }
+
+    fn from_vec3(v: Vec3) -> Self {
+        v
+    }
+}
+
+#[cfg(feature = "macroquad")]
+pub use macroquad::color::Color;
+
+/// Color with red, green, blue and alpha components from `0` to `1`.
+#[cfg(not(feature = "macroquad"))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+#[cfg(not(feature = "macroquad"))]
+impl Color {
+    /// Create a new Color from its `r`, `g`, `b` and `a` components.
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color { r, g, b, a }
+    }
+}
+
+#[cfg(not(feature = "macroquad"))]
+impl From<[f32; 4]> for Color {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Color::new(r, g, b, a)
+    }
+}
+
+/// Opaque white, the color particles are drawn in unless given others.
+pub const WHITE: Color = Color::new(1., 1., 1., 1.);
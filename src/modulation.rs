@@ -0,0 +1,134 @@
+//! # Modulation
+//!
+//! Real-time signals driving effects, such as the amplitude of the
+//! music playing or one band of its spectrum. A closure given to
+//! `with_modulation()` is called once each frame, and the value it
+//! returns scales the density, size or brightness of a system, or of
+//! every system within a group:
+//!
+//! ```ignore
+//! let level = Rc::new(Cell::new(0.));
+//! let bass = level.clone();
+//! let mut lp = LinearParticles::new(start, end)
+//!     .with_modulation(ModTarget::Density, move || bass.get())
+//!     .with_modulation(ModTarget::Brightness, move || 0.5 + level.get());
+//!
+//! loop {
+//!     level.set(analyser.amplitude());
+//!     lp.run()?;
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! Density scales the chance of spawning, rates and bursts, and size
+//! the thickness particles spawn with, while brightness scales the color
+//! of every live particle as it is drawn, following the signal without
+//! waiting for new particles to spawn. Values below `0` are taken as
+//! `0`, and the modulation of a group multiplies that of its parts.
+
+use crate::callback::SignalCallback;
+use crate::math::{Color, WHITE};
+
+/// Setting of a system a signal scales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModTarget {
+    Density,
+    Size,
+    Brightness,
+}
+
+// signals scaling each setting of a system or group
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Modulation {
+    density: Option<SignalCallback>,
+    size: Option<SignalCallback>,
+    brightness: Option<SignalCallback>,
+}
+
+impl Modulation {
+    // scale `target` by the signal sampled by `f`, in place of any
+    // signal scaling it before
+    pub(crate) fn set(&mut self, target: ModTarget, f: SignalCallback) {
+        let source = match target {
+            ModTarget::Density => &mut self.density,
+            ModTarget::Size => &mut self.size,
+            ModTarget::Brightness => &mut self.brightness,
+        };
+        *source = Some(f);
+    }
+
+    // sample each signal for the current frame
+    pub(crate) fn sample(&self) -> Factors {
+        let sample = |f: &Option<SignalCallback>| f.as_ref().map_or(1., |f| f.call().max(0.));
+        Factors {
+            density: sample(&self.density),
+            size: sample(&self.size),
+            brightness: sample(&self.brightness),
+        }
+    }
+}
+
+// values of the signals of a frame, scaling each setting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Factors {
+    pub density: f32,
+    pub size: f32,
+    pub brightness: f32,
+}
+
+impl Default for Factors {
+    fn default() -> Self {
+        Factors {
+            density: 1.,
+            size: 1.,
+            brightness: 1.,
+        }
+    }
+}
+
+impl Factors {
+    // factors of a system within a group modulated by `outer`
+    pub(crate) fn within(self, outer: Factors) -> Factors {
+        Factors {
+            density: self.density * outer.density,
+            size: self.size * outer.size,
+            brightness: self.brightness * outer.brightness,
+        }
+    }
+
+    // `tint` darkened or brightened by the brightness, if either
+    // changes the color of particles
+    pub(crate) fn tinted(&self, tint: Option<Color>) -> Option<Color> {
+        if self.brightness == 1. {
+            return tint;
+        }
+        let t = tint.unwrap_or(WHITE);
+        let b = self.brightness;
+        Some(Color::new(t.r * b, t.g * b, t.b * b, t.a))
+    }
+}
+
+#[test]
+fn modulation_test() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let level = Rc::new(Cell::new(0.5));
+    let l = level.clone();
+    let mut m = Modulation::default();
+    assert_eq!(m.sample(), Factors::default());
+    m.set(ModTarget::Density, SignalCallback::new(move || l.get()));
+    m.set(ModTarget::Brightness, SignalCallback::new(|| 2.));
+    let f = m.sample();
+    assert_eq!((f.density, f.size, f.brightness), (0.5, 1., 2.));
+    level.set(-1.);
+    assert_eq!(m.sample().density, 0.);
+
+    let f = f.within(f);
+    assert_eq!((f.density, f.brightness), (0.25, 4.));
+    assert_eq!(Factors::default().tinted(None), None);
+    assert_eq!(
+        f.tinted(Some(Color::new(0.5, 0.25, 0., 0.5))),
+        Some(Color::new(2., 1., 0., 0.5))
+    );
+}
@@ -8,14 +8,76 @@
 //! ParticleSys trait such that it generates visible particles
 //! itself, you should use the `Particles` struct defined in this module.
 
-use macroquad::color::Color;
-use macroquad::math::Vec3;
-use macroquad::prelude::draw_line_3d;
+use std::f32::consts::TAU;
 use std::slice::{Iter, IterMut};
-use std::time::Instant;
 
-use crate::particle_sys::ParticleSys;
-use crate::util::{check_period, map_color_decay};
+use crate::error::LinearPLError;
+use crate::forces::ForceField;
+use crate::math::{Color, Quat, Vec3};
+use crate::particle_sys::{LoopMode, ParticleSys};
+#[cfg(feature = "macroquad")]
+use crate::render::Immediate;
+use crate::render::ParticleRenderer;
+use crate::time::Timer;
+#[cfg(feature = "macroquad")]
+use crate::util::HEADLESS;
+use crate::util::{apply_tint, check_period, map_color_fade};
+
+/// Primitive a `Particle` is drawn as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParticleShape {
+    /// Line from the location to the ending location, as thick
+    /// as the Particle's thickness.
+    #[default]
+    Line,
+    /// Cube centered on the location.
+    Cube,
+    /// Sphere centered on the location.
+    Sphere,
+    /// Square centered on the location, always facing the camera.
+    Billboard,
+    /// Fixed size dot at the location, regardless of thickness.
+    Point,
+}
+
+/// How particles are combined with what is drawn beneath them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Particles cover what is beneath them according to their alpha.
+    #[default]
+    Alpha,
+    /// Particles add their color, scaled by their alpha, to what is
+    /// beneath them, brightening overlaps as energy and fire effects do.
+    Additive,
+}
+
+/// Curve shaping how a `Particle` fades in and out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FadeCurve {
+    /// Opacity changes at a constant rate.
+    #[default]
+    Linear,
+    /// Opacity changes slowly while faint, quickly while bright.
+    EaseIn,
+    /// Opacity changes quickly while faint, slowly while bright.
+    EaseOut,
+    /// Opacity changes slowly at both ends of the fade.
+    Smooth,
+}
+
+impl FadeCurve {
+    /// Return the opacity of the curve at `x`, where `0` is invisible
+    /// and `1` fully visible.
+    pub fn apply(&self, x: f32) -> f32 {
+        let x = x.clamp(0., 1.);
+        match self {
+            FadeCurve::Linear => x,
+            FadeCurve::EaseIn => x * x,
+            FadeCurve::EaseOut => 1. - (1. - x) * (1. - x),
+            FadeCurve::Smooth => x * x * (3. - 2. * x),
+        }
+    }
+}
 
 /// Single Particle struct. Contains the `location` and `color`.
 /// Because `macroquad` does not support 3 dimensional points
@@ -34,9 +96,38 @@ pub struct Particle {
     color: Color,
     length: f32,
     sloped: bool,
-    start_time: Instant,
+    fade_in: f32,
+    fade_curve: FadeCurve,
+    thickness: f32,
+    scale: f32,
+    glow: f32,
+    glow_alpha: f32,
+    shape: ParticleShape,
+    velocity: Vec3,
+    acceleration: Vec3,
+    drag: f32,
+    mass: f32,
+    stretch: f32,
+    spin: f32,
+    spin_axis: Vec3,
+    angle: f32,
+    trail: [Vec3; MAX_TRAIL],
+    trail_len: usize,
+    trail_count: usize,
+    trail_head: usize,
+    age: f32,
+    timer: Timer,
 }
 
+// number of offset lines drawn around the center line of a thick particle
+const THICK_LINES: usize = 8;
+
+/// Most previous positions a Particle can keep for its trail.
+pub const MAX_TRAIL: usize = 16;
+
+// world size of a particle drawn as `ParticleShape::Point`
+const POINT_SIZE: f32 = 0.01;
+
 impl Particle {
     /// Instantiate a new Particle at `(x, y, z)` location
     /// with `(r, g, b, a)` color and `s` size, length `l`.
@@ -48,7 +139,7 @@ impl Particle {
         size: f32,
         length: f32,
         sloped: bool,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, LinearPLError> {
         let l = Vec3::new(x, y, z);
         let el = l + Vec3::splat(size);
         check_period(length)?;
@@ -58,7 +149,27 @@ impl Particle {
             color: Color::new(r, g, b, a),
             length,
             sloped,
-            start_time: Instant::now(),
+            fade_in: 0.,
+            fade_curve: FadeCurve::Linear,
+            thickness: 0.,
+            scale: 1.,
+            glow: 0.,
+            glow_alpha: 0.,
+            shape: ParticleShape::Line,
+            velocity: Vec3::ZERO,
+            acceleration: Vec3::ZERO,
+            drag: 0.,
+            mass: 1.,
+            stretch: 0.,
+            spin: 0.,
+            spin_axis: Vec3::Y,
+            angle: 0.,
+            trail: [Vec3::ZERO; MAX_TRAIL],
+            trail_len: 0,
+            trail_count: 0,
+            trail_head: 0,
+            age: 0.,
+            timer: Timer::new(),
         })
     }
 
@@ -72,7 +183,7 @@ impl Particle {
         (r, g, b, a): (f32, f32, f32, f32),
         length: f32,
         sloped: bool,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, LinearPLError> {
         check_period(length)?;
         Ok(Particle {
             location: Vec3::new(x, y, z),
@@ -80,7 +191,27 @@ impl Particle {
             color: Color::new(r, g, b, a),
             length,
             sloped,
-            start_time: Instant::now(),
+            fade_in: 0.,
+            fade_curve: FadeCurve::Linear,
+            thickness: 0.,
+            scale: 1.,
+            glow: 0.,
+            glow_alpha: 0.,
+            shape: ParticleShape::Line,
+            velocity: Vec3::ZERO,
+            acceleration: Vec3::ZERO,
+            drag: 0.,
+            mass: 1.,
+            stretch: 0.,
+            spin: 0.,
+            spin_axis: Vec3::Y,
+            angle: 0.,
+            trail: [Vec3::ZERO; MAX_TRAIL],
+            trail_len: 0,
+            trail_count: 0,
+            trail_head: 0,
+            age: 0.,
+            timer: Timer::new(),
         })
     }
 
@@ -102,6 +233,369 @@ impl Particle {
         self
     }
 
+    /// Move the Particle, along with its ending location, by `offset`.
+    #[inline]
+    pub fn translate(&mut self, offset: Vec3) {
+        self.location += offset;
+        self.end_location += offset;
+    }
+
+    /// Return the location of the Particle.
+    #[inline]
+    pub fn location(&self) -> Vec3 {
+        self.location
+    }
+
+    /// Return the ending location of the Particle.
+    #[inline]
+    pub fn end_location(&self) -> Vec3 {
+        self.end_location
+    }
+
+    /// Return the color of the Particle.
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Return the color of the Particle as it is currently drawn, faded
+    /// in and out over its life.
+    pub fn faded_color(&self) -> Color {
+        map_color_fade(self.color, self.fade(self.age()))
+    }
+
+    /// Return the seconds the Particle takes to fade in.
+    #[inline]
+    pub fn fade_in(&self) -> f32 {
+        self.fade_in
+    }
+
+    /// Return self (consuming it) fading in over `f` seconds.
+    pub fn with_fade_in(mut self, f: f32) -> Self {
+        self.set_fade_in(f);
+        self
+    }
+
+    /// Set the particle to fade in over the first `f` seconds of its
+    /// life rather than appearing at full opacity.
+    #[inline]
+    pub fn set_fade_in(&mut self, f: f32) {
+        self.fade_in = f.max(0.);
+    }
+
+    /// Return the curve the Particle fades in and out along.
+    #[inline]
+    pub fn fade_curve(&self) -> FadeCurve {
+        self.fade_curve
+    }
+
+    /// Return self (consuming it) fading along `curve`.
+    pub fn with_fade_curve(mut self, curve: FadeCurve) -> Self {
+        self.set_fade_curve(curve);
+        self
+    }
+
+    /// Set the curve the particle fades in and out along to `curve`.
+    #[inline]
+    pub fn set_fade_curve(&mut self, curve: FadeCurve) {
+        self.fade_curve = curve;
+    }
+
+    /// Return the thickness of the Particle.
+    #[inline]
+    pub fn thickness(&self) -> f32 {
+        self.thickness
+    }
+
+    /// Return self (consuming it) with thickness `t`.
+    pub fn with_thickness(mut self, t: f32) -> Self {
+        self.set_thickness(t);
+        self
+    }
+
+    /// Set the thickness of the particle to `t` in world units. A
+    /// thickness of `0` draws a hairline, anything above draws a bundle
+    /// of lines spread `t` wide around the center line.
+    #[inline]
+    pub fn set_thickness(&mut self, t: f32) {
+        self.thickness = t.max(0.);
+    }
+
+    /// Return the scale the Particle is drawn at.
+    #[inline]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Set the scale the particle is drawn at to `s`, multiplying its
+    /// thickness, or the size of its shape, without changing either.
+    #[inline]
+    pub fn set_scale(&mut self, s: f32) {
+        self.scale = s.max(0.);
+    }
+
+    /// Return the width and opacity of the Particle's glow.
+    #[inline]
+    pub fn glow(&self) -> (f32, f32) {
+        (self.glow, self.glow_alpha)
+    }
+
+    /// Return self (consuming it) with a glow `width` wide at opacity `alpha`.
+    pub fn with_glow(mut self, width: f32, alpha: f32) -> Self {
+        self.set_glow(width, alpha);
+        self
+    }
+
+    /// Draw the particle over a halo `width` wider than itself, at `alpha`
+    /// times its opacity, faking a soft glowing edge. A width of `0`
+    /// draws no halo.
+    #[inline]
+    pub fn set_glow(&mut self, width: f32, alpha: f32) {
+        self.glow = width.max(0.);
+        self.glow_alpha = alpha.clamp(0., 1.);
+    }
+
+    /// Return the shape the Particle is drawn as.
+    #[inline]
+    pub fn shape(&self) -> ParticleShape {
+        self.shape
+    }
+
+    /// Return self (consuming it) drawn as `shape`.
+    pub fn with_shape(mut self, shape: ParticleShape) -> Self {
+        self.set_shape(shape);
+        self
+    }
+
+    /// Set the shape the particle is drawn as to `shape`. Shapes other
+    /// than `ParticleShape::Line` are sized by the thickness, or by the
+    /// distance to the ending location when the thickness is `0`.
+    #[inline]
+    pub fn set_shape(&mut self, shape: ParticleShape) {
+        self.shape = shape;
+    }
+
+    /// Return the velocity of the Particle in units per second.
+    #[inline]
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    /// Return self (consuming it) with velocity `v`.
+    pub fn with_velocity(mut self, v: Vec3) -> Self {
+        self.set_velocity(v);
+        self
+    }
+
+    /// Set the velocity of the particle to `v` units per second.
+    #[inline]
+    pub fn set_velocity(&mut self, v: Vec3) {
+        self.velocity = v;
+    }
+
+    /// Return the acceleration of the Particle in units per second squared.
+    #[inline]
+    pub fn acceleration(&self) -> Vec3 {
+        self.acceleration
+    }
+
+    /// Return self (consuming it) with acceleration `a`.
+    pub fn with_acceleration(mut self, a: Vec3) -> Self {
+        self.set_acceleration(a);
+        self
+    }
+
+    /// Set the acceleration of the particle to `a` units per second
+    /// squared, such as gravity.
+    #[inline]
+    pub fn set_acceleration(&mut self, a: Vec3) {
+        self.acceleration = a;
+    }
+
+    /// Return the drag coefficient of the Particle.
+    #[inline]
+    pub fn drag(&self) -> f32 {
+        self.drag
+    }
+
+    /// Return self (consuming it) with drag coefficient `k`.
+    pub fn with_drag(mut self, k: f32) -> Self {
+        self.set_drag(k);
+        self
+    }
+
+    /// Set the drag coefficient of the particle to `k`. The velocity
+    /// is damped by a factor of `e^(-k)` every second.
+    #[inline]
+    pub fn set_drag(&mut self, k: f32) {
+        self.drag = k.max(0.);
+    }
+
+    /// Return the mass of the Particle.
+    #[inline]
+    pub fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    /// Return self (consuming it) with mass `m`.
+    pub fn with_mass(mut self, m: f32) -> Self {
+        self.set_mass(m);
+        self
+    }
+
+    /// Set the mass of the particle to `m`, defaulting to `1`. Forces and
+    /// drag are divided by the mass, so heavy particles shrug off wind
+    /// and keep falling while light ones drift. Gravity and other
+    /// accelerations apply equally to every mass.
+    #[inline]
+    pub fn set_mass(&mut self, m: f32) {
+        self.mass = m.max(f32::EPSILON);
+    }
+
+    /// Return the seconds of motion the Particle is stretched over.
+    #[inline]
+    pub fn stretch(&self) -> f32 {
+        self.stretch
+    }
+
+    /// Return self (consuming it) stretched over `s` seconds of motion.
+    pub fn with_stretch(mut self, s: f32) -> Self {
+        self.set_stretch(s);
+        self
+    }
+
+    /// Stretch the particle along its velocity, drawing it from its
+    /// location to where it will be in `s` seconds, so faster particles
+    /// streak further. A stretch of `0` keeps the ending location as is.
+    #[inline]
+    pub fn set_stretch(&mut self, s: f32) {
+        self.stretch = s.max(0.);
+        self.apply_stretch();
+    }
+
+    /// Return the angular velocity of the Particle in radians per second.
+    #[inline]
+    pub fn spin(&self) -> f32 {
+        self.spin
+    }
+
+    /// Return self (consuming it) with angular velocity `s`.
+    pub fn with_spin(mut self, s: f32) -> Self {
+        self.set_spin(s);
+        self
+    }
+
+    /// Set the angular velocity of the particle to `s` radians per second.
+    /// Cubes turn around the spin axis and billboards turn in place,
+    /// while lines, spheres and points are unaffected.
+    #[inline]
+    pub fn set_spin(&mut self, s: f32) {
+        self.spin = s;
+    }
+
+    /// Return self (consuming it) spinning around `axis`.
+    pub fn with_spin_axis(mut self, axis: Vec3) -> Self {
+        self.set_spin_axis(axis);
+        self
+    }
+
+    /// Set the axis cubes spin around to `axis`.
+    #[inline]
+    pub fn set_spin_axis(&mut self, axis: Vec3) {
+        self.spin_axis = axis.try_normalize().unwrap_or(Vec3::Y);
+    }
+
+    /// Return the current rotation of the Particle in radians.
+    #[inline]
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Set the current rotation of the particle to `a` radians.
+    #[inline]
+    pub fn set_angle(&mut self, a: f32) {
+        self.angle = a;
+    }
+
+    /// Return the number of previous positions the Particle keeps.
+    #[inline]
+    pub fn trail(&self) -> usize {
+        self.trail_len
+    }
+
+    /// Return self (consuming it) keeping a trail of `n` positions.
+    pub fn with_trail(mut self, n: usize) -> Self {
+        self.set_trail(n);
+        self
+    }
+
+    /// Keep the last `n` positions of the moving particle, up to
+    /// `MAX_TRAIL`, and draw them as a trail fading out behind it.
+    /// A trail of `0` keeps none. Clears the positions kept so far.
+    pub fn set_trail(&mut self, n: usize) {
+        self.trail_len = n.min(MAX_TRAIL);
+        self.trail_count = 0;
+        self.trail_head = 0;
+    }
+
+    // remember `location` as the newest position of the trail
+    fn record_trail(&mut self, location: Vec3) {
+        if self.trail_len == 0 {
+            return;
+        }
+        self.trail_head = (self.trail_head + 1) % self.trail_len;
+        self.trail[self.trail_head] = location;
+        self.trail_count = (self.trail_count + 1).min(self.trail_len);
+    }
+
+    // the `i`th most recent position of the trail
+    fn trail_at(&self, i: usize) -> Vec3 {
+        self.trail[(self.trail_head + self.trail_len - i) % self.trail_len]
+    }
+
+    /// Move the Particle by its velocity and acceleration over the time
+    /// elapsed since the last update.
+    pub fn update(&mut self) {
+        self.integrate(None);
+    }
+
+    /// Move the Particle as `update()` does, with the forces of `field`
+    /// accelerating it as well in proportion to its mass.
+    pub fn update_with(&mut self, field: &ForceField) {
+        self.integrate(Some(field));
+    }
+
+    // move the Particle to its current age, accelerated by `field` if any
+    fn integrate(&mut self, field: Option<&ForceField>) {
+        let age = self.timer.elapsed();
+        let dt = age - self.age;
+        self.age = age;
+        if dt <= 0. {
+            return;
+        }
+        let forced = field.map_or(Vec3::ZERO, |f| f.acceleration(self));
+        let acceleration = self.acceleration + forced / self.mass;
+        self.velocity += acceleration * dt;
+        if self.drag > 0. {
+            self.velocity *= (-self.drag / self.mass * dt).exp();
+        }
+        self.angle += self.spin * dt;
+        let step = self.velocity * dt;
+        if step != Vec3::ZERO {
+            self.record_trail(self.location);
+        }
+        self.location += step;
+        self.end_location += step;
+        self.apply_stretch();
+    }
+
+    // point the ending location along the velocity when stretched
+    fn apply_stretch(&mut self) {
+        if self.stretch > 0. {
+            self.end_location = self.location + self.velocity * self.stretch;
+        }
+    }
+
     /// Set the location of the particle to `x`, `y`, `z` argument.
     #[inline]
     pub fn set_location(&mut self, x: f32, y: f32, z: f32) {
@@ -116,21 +610,134 @@ impl Particle {
 
     /// Draw the Particle within the macroquad world coords. Returns
     /// `true` if Particle has surpassed its length, else `false`.
+    /// Nothing is drawn with the `headless` feature enabled or the
+    /// `macroquad` feature disabled.
     #[inline]
     pub fn draw(&mut self) -> bool {
-        let current_time = self.start_time.elapsed().as_secs_f32();
-        if self.sloped {
-            let color = map_color_decay(self.color, current_time, self.length);
-            draw_line_3d(self.location, self.end_location, color);
-        } else {
-            draw_line_3d(self.location, self.end_location, self.color);
+        #[cfg(feature = "macroquad")]
+        if !HEADLESS {
+            return self.draw_to(&mut Immediate);
+        }
+        self.is_finished()
+    }
+
+    /// Draw the Particle as `draw()` does, handing each of its
+    /// primitives to `renderer`. Returns `true` if Particle has surpassed
+    /// its length, else `false`.
+    pub fn draw_to(&mut self, renderer: &mut dyn ParticleRenderer) -> bool {
+        let current_time = self.timer.elapsed();
+        let color = apply_tint(self.color);
+        if color.a > 0. {
+            // skipped when fully transparent, such as when muted by a group
+            let color = map_color_fade(color, self.fade(current_time));
+            if self.glow > 0. && self.glow_alpha > 0. {
+                self.draw_shape(map_color_fade(color, self.glow_alpha), self.glow, renderer);
+            }
+            self.draw_shape(color, 0., renderer);
+            self.draw_trail(color, renderer);
         }
         current_time > self.length
     }
 
+    // draw a segment between each position of the trail, fading out
+    // towards the oldest
+    fn draw_trail(&self, color: Color, line: &mut dyn ParticleRenderer) {
+        let mut from = self.location;
+        for i in 0..self.trail_count {
+            let to = self.trail_at(i);
+            let fade = 1. - (i + 1) as f32 / (self.trail_count + 1) as f32;
+            line.draw_segment(from, to, map_color_fade(color, fade));
+            from = to;
+        }
+    }
+
+    // opacity `t` seconds into the particle's life, fading in over
+    // `fade_in` and, when sloped, fading out over its length
+    fn fade(&self, t: f32) -> f32 {
+        let mut fade = 1.;
+        if self.sloped {
+            fade *= self.fade_curve.apply(1. - t / self.length);
+        }
+        if t < self.fade_in {
+            fade *= self.fade_curve.apply(t / self.fade_in);
+        }
+        fade
+    }
+
+    // draw the primitive selected by the particle's shape, `grow` wider
+    // than its size, with `renderer`
+    fn draw_shape(&self, color: Color, grow: f32, renderer: &mut dyn ParticleRenderer) {
+        let size = match self.thickness {
+            t if t > 0. => t,
+            _ => self.location.distance(self.end_location),
+        } * self.scale
+            + grow;
+        match self.shape {
+            ParticleShape::Line => self.draw_line(color, grow, renderer),
+            ParticleShape::Cube if self.angle != 0. => {
+                let rotation = Quat::from_axis_angle(self.spin_axis, self.angle);
+                renderer.draw_cube(self.location, size, rotation, color);
+            }
+            ParticleShape::Cube => renderer.draw_cube(self.location, size, Quat::IDENTITY, color),
+            ParticleShape::Sphere => renderer.draw_sphere(self.location, size / 2., color),
+            ParticleShape::Billboard => {
+                renderer.draw_billboard(self.location, size, self.angle, color)
+            }
+            ParticleShape::Point => {
+                renderer.draw_cube(self.location, POINT_SIZE + grow, Quat::IDENTITY, color)
+            }
+        }
+    }
+
+    // draw the hairline, surrounded by a ring of offset lines when the
+    // particle is thick or grown
+    fn draw_line(&self, color: Color, grow: f32, line: &mut dyn ParticleRenderer) {
+        line.draw_segment(self.location, self.end_location, color);
+        let thickness = self.thickness * self.scale + grow;
+        if thickness <= 0. {
+            return;
+        }
+        let direction = (self.end_location - self.location)
+            .try_normalize()
+            .unwrap_or(Vec3::Y);
+        let (u, v) = direction.any_orthonormal_pair();
+        let radius = thickness / 2.;
+        for i in 0..THICK_LINES {
+            let angle = i as f32 * TAU / THICK_LINES as f32;
+            let offset = (u * angle.cos() + v * angle.sin()) * radius;
+            line.draw_segment(self.location + offset, self.end_location + offset, color);
+        }
+    }
+
+    /// Return the seconds the Particle has been alive for.
+    #[inline]
+    pub fn age(&self) -> f32 {
+        self.timer.elapsed()
+    }
+
+    /// Return the seconds the Particle stays alive for.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// Returns `true` if Particle has surpassed its length, else `false`.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.timer.elapsed() > self.length
+    }
+
+    // finish the Particle early, such as when it hits a collider killing
+    // it, so it is dropped by its system
+    pub(crate) fn kill(&mut self) {
+        self.length = f32::NEG_INFINITY;
+    }
+
     /// Reset the ellapsed time for the Particle object
     pub fn reset(&mut self) {
-        self.start_time = Instant::now();
+        self.timer.reset();
+        self.age = 0.;
+        self.trail_count = 0;
     }
 }
 
@@ -154,18 +761,32 @@ impl ParticleSys for Particle {
     }
 
     fn elapsed_time(&mut self) -> Option<f32> {
-        Some(self.start_time.elapsed().as_secs_f32())
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
     }
 
-    fn setup(&mut self, _should_loop: bool, _p: Option<f32>) -> Result<(), String> {
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn setup(&mut self, _mode: LoopMode, _p: Option<f32>) -> Result<(), LinearPLError> {
         self.reset();
         Ok(())
     }
 
     fn tear_down(&mut self) {}
 
-    fn next_frame(&mut self, _time: Option<f32>) -> Result<bool, String> {
-        Ok(self.draw())
+    fn update_frame(&mut self, _time: Option<f32>) -> Result<bool, LinearPLError> {
+        self.update();
+        Ok(self.is_finished())
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        self.draw();
+        Ok(())
     }
 
     fn iter(&self) -> Option<Iter<'_, Self::T>> {
@@ -176,10 +797,18 @@ impl ParticleSys for Particle {
         None
     }
 
-    fn with_period(mut self, p: f32) -> Result<Self, String> {
+    fn particle_count(&self) -> usize {
+        1
+    }
+
+    fn for_each_particle(&self, f: &mut dyn FnMut(&Particle)) {
+        f(self)
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
         check_period(p)?;
         self.length = p;
-        Ok(self)
+        Ok(())
     }
 }
 
@@ -188,3 +817,86 @@ impl Default for Particle {
         Particle::new((0., 0., 0.), (0., 0., 0., 1.), 0.01, 1., false).unwrap()
     }
 }
+
+#[test]
+fn particle_thickness_test() {
+    let p = Particle::default().with_thickness(0.5);
+    assert_eq!(p.thickness(), 0.5);
+    assert_eq!(Particle::default().with_thickness(-1.).thickness(), 0.);
+    assert_eq!(Particle::default().shape(), ParticleShape::Line);
+    let p = p.with_shape(ParticleShape::Sphere);
+    assert_eq!(p.shape(), ParticleShape::Sphere);
+    assert_eq!(p.with_glow(0.2, 2.).glow(), (0.2, 1.));
+}
+
+#[test]
+fn particle_update_test() {
+    let mut p = Particle::new((0., 0., 0.), (1., 1., 1., 1.), 0., 2., false)
+        .unwrap()
+        .with_velocity(Vec3::X)
+        .with_acceleration(Vec3::new(0., -2., 0.));
+    p.set_time(0.);
+    p.reset();
+    p.set_time(0.5);
+    p.update();
+    assert_eq!(p.velocity(), Vec3::new(1., -1., 0.));
+    assert_eq!(p.location(), Vec3::new(0.5, -0.5, 0.));
+    p.update();
+    assert_eq!(p.location(), Vec3::new(0.5, -0.5, 0.));
+
+    let mut p = Particle::default().with_velocity(Vec3::X).with_drag(2.);
+    p.set_time(0.);
+    p.reset();
+    p.set_time(1.);
+    p.update();
+    assert!((p.velocity().x - (-2f32).exp()).abs() < 1e-6);
+
+    let mut p = Particle::default().with_spin(2.);
+    p.set_time(0.);
+    p.reset();
+    p.set_time(0.25);
+    p.update();
+    assert_eq!(p.angle(), 0.5);
+
+    let field = ForceField::new().with(|_p: &Particle| Vec3::X);
+    let mut p = Particle::default().with_mass(4.);
+    p.set_time(0.);
+    p.reset();
+    p.set_time(1.);
+    p.update_with(&field);
+    assert_eq!(p.velocity(), Vec3::new(0.25, 0., 0.));
+
+    let p = Particle::default()
+        .with_velocity(Vec3::new(0., 2., 0.))
+        .with_stretch(0.5);
+    assert_eq!(p.end_location, Vec3::new(0., 1., 0.));
+}
+
+#[test]
+fn particle_fade_test() {
+    let p = Particle::new((0., 0., 0.), (1., 1., 1., 1.), 0., 2., true)
+        .unwrap()
+        .with_fade_in(0.5);
+    assert_eq!(p.fade(0.25), 0.5 * 0.875);
+    assert_eq!(p.fade(1.), 0.5);
+
+    let p = p.with_fade_curve(FadeCurve::EaseIn);
+    assert_eq!(p.fade(0.25), 0.25 * 0.875 * 0.875);
+    assert_eq!(FadeCurve::Smooth.apply(0.5), 0.5);
+}
+
+#[test]
+fn particle_trail_test() {
+    let mut p = Particle::default().with_velocity(Vec3::X).with_trail(2);
+    p.set_time(0.);
+    p.reset();
+    for t in 1..=3 {
+        p.set_time(t as f32);
+        p.update();
+    }
+    assert_eq!(p.location(), Vec3::new(3., 0., 0.));
+    assert_eq!(p.trail_count, 2);
+    assert_eq!(p.trail_at(0), Vec3::new(2., 0., 0.));
+    assert_eq!(p.trail_at(1), Vec3::new(1., 0., 0.));
+    assert_eq!(Particle::default().with_trail(100).trail(), MAX_TRAIL);
+}
@@ -17,9 +17,215 @@
 //! implementing this trait allows for a struct to reside in Groups of
 //! Particle Systems allowing for more complex animations and patterns
 //! to be used with the traits api.
+//!
+//! Systems are simulated in fixed steps, a sixtieth of a second by default or
+//! `with_timestep()`, taking as many steps each frame as it needs to catch up, so
+//! emission and streak lengths come out the same at any frame rate. A single step
+//! of `dt` seconds can also be taken by hand with `step(dt)`.
+//!
+//! Each `run()` is an `update()` followed by a `draw()`, and any `ParticleSys` can
+//! be driven through the two halves directly. `update()` advances a system to its
+//! clock without drawing anything, so effects keep going behind a pause menu or run
+//! in tests without a window, while `draw()` draws it as of the last update as many
+//! times as needed, such as once per camera.
+//!
+//! Engines with a fixed update loop of their own, or their own time scale, can
+//! step systems in lockstep with `advance(dt)` in place of `update()`, moving their
+//! clocks forward by exactly `dt` seconds instead of reading wall time.
+//!
+//! Systems can also share a `Clock` given to `with_clock()`, which they follow in
+//! place of their own wall time. Pausing it with `pause()` or slowing it down with
+//! `set_scale()` freezes or slows every system following it at once, such as for a
+//! pause menu or slow motion.
+//!
+//! With the `parallel` feature enabled, systems of more than a thousand particles
+//! age them across threads with rayon, leaving only drawing on the main thread.
+//! `SyncGrp` and `LinearGrp` likewise age the particles of all their parts across
+//! threads once they hold a thousand between them. Systems with forces or a
+//! `with_update()` closure, which can't be shared across threads, still age their
+//! particles one by one, as do parts of a group with a particle cap or rewind, as
+//! `ages_in_parallel()` reports.
+//!
+//! To find where a frame goes, `with_perf_counters(true)` has a system time its
+//! updates and draws, read back with `perf_counters()` on any `ParticleSys` along with
+//! the particles spawned and drawn, totalled over the systems of a group. The hot
+//! paths themselves are measured by the criterion benches under `benches/`.
 
+use std::fmt;
 use std::slice::{Iter, IterMut};
 
+use crate::clock::Clock;
+use crate::error::LinearPLError;
+use crate::gizmos::Gizmos;
+#[cfg(feature = "parallel")]
+use crate::linear_particles::AgingJob;
+use crate::particle::Particle;
+
+/// How a ParticleSys repeats once it reaches the end of its period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoopMode {
+    /// Run through the period a single time.
+    #[default]
+    Once,
+    /// Repeat the period until stopped.
+    Loop,
+    /// Repeat the period until stopped, alternating between forward
+    /// and reversed traversal of the tracks each cycle.
+    PingPong,
+    /// Repeat the period `n` times in total.
+    LoopN(u32),
+}
+
+impl LoopMode {
+    /// Return `true` if another cycle follows the 0-based cycle `cycle`.
+    pub fn repeats_after(&self, cycle: u32) -> bool {
+        match self {
+            LoopMode::Once => false,
+            LoopMode::Loop | LoopMode::PingPong => true,
+            LoopMode::LoopN(n) => cycle + 1 < *n,
+        }
+    }
+
+    /// Return `true` if the 0-based cycle `cycle` is traversed in reverse.
+    pub fn is_reversed(&self, cycle: u32) -> bool {
+        *self == LoopMode::PingPong && cycle % 2 == 1
+    }
+}
+
+#[test]
+fn loop_mode_test() {
+    assert!(!LoopMode::Once.repeats_after(0));
+    assert!(LoopMode::Loop.repeats_after(100));
+    assert!(LoopMode::LoopN(3).repeats_after(1));
+    assert!(!LoopMode::LoopN(3).repeats_after(2));
+    assert!(!LoopMode::PingPong.is_reversed(0));
+    assert!(LoopMode::PingPong.is_reversed(1));
+    assert!(!LoopMode::Loop.is_reversed(1));
+}
+
+/// Snapshot of the state of a ParticleSys and any systems nested
+/// within it, returned by `ParticleSys::describe()`. Its `Display`
+/// implementation prints the whole tree, one system per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SysInfo {
+    /// Type name of the system, without module path or generics.
+    pub name: String,
+    /// Whether the system is active.
+    pub active: bool,
+    /// Whether the system is active and looping.
+    pub looping: bool,
+    /// Period of the system in seconds, if it has one.
+    pub period: Option<f32>,
+    /// Seconds elapsed on the system's own clock, if it has one.
+    pub elapsed: Option<f32>,
+    /// Number of live particles, including nested systems.
+    pub particles: usize,
+    /// PerfCounters of the last frame, including nested systems, if
+    /// any of them gather counters.
+    pub perf: Option<PerfCounters>,
+    /// Snapshots of the systems nested within this one.
+    pub children: Vec<SysInfo>,
+}
+
+impl SysInfo {
+    /// Return the snapshot of `sys` known from the `ParticleSys` trait
+    /// alone, describing its nested systems recursively. Implementors
+    /// of `describe()` can fill in `period` and `elapsed` on top of it.
+    pub fn of<P: ParticleSys + ?Sized>(sys: &P) -> Self {
+        let full_name = std::any::type_name::<P>();
+        let name = full_name.split('<').next().unwrap_or(full_name);
+        SysInfo {
+            name: name.rsplit("::").next().unwrap_or(name).to_string(),
+            active: sys.is_active(),
+            looping: sys.is_looping(),
+            period: None,
+            elapsed: None,
+            particles: sys.particle_count(),
+            perf: sys.perf_counters(),
+            children: sys
+                .iter()
+                .map(|it| it.map(|c| c.describe()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    // write this snapshot and its children indented by `depth`
+    fn write_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{}{}", "  ".repeat(depth), self.name)?;
+        let state = match (self.active, self.looping) {
+            (true, true) => "looping",
+            (true, false) => "active",
+            _ => "stopped",
+        };
+        write!(f, " {}", state)?;
+        if let Some(p) = self.period {
+            write!(f, " period={:.2}", p)?;
+        }
+        if let Some(e) = self.elapsed {
+            write!(f, " elapsed={:.2}", e)?;
+        }
+        writeln!(f, " particles={}", self.particles)?;
+        for c in self.children.iter() {
+            c.write_tree(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SysInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+
+/// Timings and counts of the last frame of a ParticleSys, gathered
+/// once enabled such as with `LinearParticles::with_perf_counters(true)`
+/// and returned by `ParticleSys::perf_counters()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PerfCounters {
+    /// Seconds spent spawning and aging particles.
+    pub update_secs: f32,
+    /// Seconds spent drawing particles.
+    pub draw_secs: f32,
+    /// Number of particles spawned.
+    pub spawned: usize,
+    /// Number of live particles drawn.
+    pub particles: usize,
+    /// Number of times the buffer of live particles was reallocated.
+    pub allocations: usize,
+}
+
+impl PerfCounters {
+    /// Return the counters of `self` and `other` added together, such
+    /// as to total up the systems of a group.
+    pub fn combined(self, other: PerfCounters) -> Self {
+        PerfCounters {
+            update_secs: self.update_secs + other.update_secs,
+            draw_secs: self.draw_secs + other.draw_secs,
+            spawned: self.spawned + other.spawned,
+            particles: self.particles + other.particles,
+            allocations: self.allocations + other.allocations,
+        }
+    }
+}
+
+#[test]
+fn perf_counters_test() {
+    let a = PerfCounters {
+        update_secs: 0.5,
+        draw_secs: 0.25,
+        spawned: 2,
+        particles: 10,
+        allocations: 1,
+    };
+    let total = a.combined(a);
+    assert_eq!(total.update_secs, 1.);
+    assert_eq!(total.draw_secs, 0.5);
+    assert_eq!((total.spawned, total.particles), (4, 20));
+    assert_eq!(total.allocations, 2);
+}
+
 /// Defines how to interact with a system of particles within
 /// the LinearPL library.
 pub trait ParticleSys {
@@ -48,14 +254,47 @@ pub trait ParticleSys {
     /// counted by the ParticleSys as `f32`, or None if that's desirable.
     fn elapsed_time(&mut self) -> Option<f32>;
 
+    /// Drive the ParticleSys with an external clock reading of `t` seconds.
+    ///
+    /// Once this has been called the ParticleSys stops reading wall time
+    /// and measures its elapsed time entirely from the values passed in.
+    ///
+    /// Times usually increase between calls. A time earlier than the last
+    /// one is taken as the period starting over: a system emits again
+    /// from the start of its period up to that time, while the particles
+    /// it already has keep aging forward as they do across a loop.
+    /// Systems given `with_rewind(true)` instead rebuild the particles
+    /// that were alive at the earlier time, playing backwards faithfully,
+    /// and a Timeline tears down entries scrubbed back before their start.
+    fn set_time(&mut self, t: f32);
+
+    /// Step the clock of the ParticleSys `dt` seconds forward. As with
+    /// `set_time()`, the ParticleSys stops reading wall time from then on.
+    fn advance_time(&mut self, dt: f32);
+
+    /// Follow the shared `clock` in place of wall time from now on,
+    /// carrying on from the current elapsed time. While following a
+    /// Clock, the ParticleSys ignores `set_time()` and `advance_time()`.
+    /// The default ignores the clock.
+    fn set_clock(&mut self, _clock: &Clock) {}
+
+    /// Returns self (consuming it) following the shared `clock`.
+    fn with_clock(mut self, clock: &Clock) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_clock(clock);
+        self
+    }
+
     /// Set up the ParticleSys such that it is ready to be
-    /// displayed. This function isn't intended to be called by the user
-    /// but by other trait methods.
+    /// displayed, repeating according to `mode`. This function isn't
+    /// intended to be called by the user but by other trait methods.
     ///
     /// The implementor is in charge of making sure that this
     /// operation will result in `is_active()` and `is_initialized()`
     /// calls returning true.
-    fn setup(&mut self, should_loop: bool, p: Option<f32>) -> Result<(), String>;
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError>;
 
     /// Tear down the ParticleSys such that `is_active()` and `is_initialized()`
     /// return false and any other resetting of variables necessary for
@@ -65,10 +304,10 @@ pub trait ParticleSys {
     /// This is equivalent to calling the `stop()` method for this trait.
     fn tear_down(&mut self);
 
-    /// Display the next frame of the ParticleSys Particles with
-    /// elapsed time `time` if `Some(time)`, else the ParticleSys own
-    /// counting mechanism. This function isn't intended to be called by
-    /// the user, but by the trait's `run` method.
+    /// Advance the ParticleSys Particles to elapsed time `time` if
+    /// `Some(time)`, else the ParticleSys own counting mechanism, without
+    /// drawing them. This function isn't intended to be called by the
+    /// user, but by the trait's `update` method.
     ///
     /// # Returns
     ///
@@ -80,7 +319,16 @@ pub trait ParticleSys {
     ///
     /// For these to work, it is best to return Ok(false) when you would
     /// like for the loop to reset, not for when to stop displaying particles.
-    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, String>;
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError>;
+
+    /// Advance the ParticleSys Particles to elapsed time `time` as in
+    /// `update_frame()` and draw them. Returns the same values as
+    /// `update_frame()`.
+    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let generating = self.update_frame(time)?;
+        self.redraw_frame()?;
+        Ok(generating)
+    }
 
     /// Return an Iterator over the Particle Pieces managed by the
     /// ParticleSys.
@@ -90,21 +338,155 @@ pub trait ParticleSys {
     /// the ParticleSys.
     fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>>;
 
-    /// Returns self with period `p`.
-    fn with_period(self, p: f32) -> Result<Self, String>
+    /// Called by `run()` once the ParticleSys finishes its period
+    /// without looping. Implementors holding user callbacks should
+    /// invoke them here; the default does nothing.
+    fn handle_complete(&mut self) {}
+
+    /// Called by `run()` each time a looping ParticleSys wraps back
+    /// around to the start of its period. Implementors should advance
+    /// their cycle count here for `LoopMode::LoopN` and
+    /// `LoopMode::PingPong` to take effect. The default does nothing.
+    fn handle_loop(&mut self) {}
+
+    /// Age the particles already emitted to `time` (or the internal clock
+    /// if `None`) without emitting new ones, leaving them to be drawn by
+    /// `redraw_frame()`. Used to let an outgoing system's particles decay
+    /// naturally after it has finished. Returns `true` while particles
+    /// remain; the default has none to age.
+    fn drain_frame(&mut self, _time: Option<f32>) -> Result<bool, LinearPLError> {
+        Ok(false)
+    }
+
+    /// Draw the particles as of the last `update_frame()` without
+    /// advancing the ParticleSys. This function isn't intended to be
+    /// called by the user, but by the trait's `draw` method. The default
+    /// draws nothing.
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        Ok(())
+    }
+
+    /// Return a snapshot of the state of the ParticleSys and of every
+    /// system nested within it, for debugging deep groups. The default
+    /// describes the nested systems returned by `iter()`.
+    fn describe(&self) -> SysInfo {
+        SysInfo::of(self)
+    }
+
+    /// Return the PerfCounters of the last frame, totalled over every
+    /// system nested within the ParticleSys, or `None` if none of them
+    /// gather counters. The default totals those of the nested systems
+    /// returned by `iter()`.
+    fn perf_counters(&self) -> Option<PerfCounters> {
+        self.iter()?
+            .filter_map(|c| c.perf_counters())
+            .reduce(PerfCounters::combined)
+    }
+
+    /// Return the number of live particles currently managed by the
+    /// ParticleSys, including those of any nested systems.
+    fn particle_count(&self) -> usize {
+        0
+    }
+
+    /// Return the highest `particle_count()` observed since the
+    /// ParticleSys was last set up.
+    fn peak_particle_count(&self) -> usize {
+        self.particle_count()
+    }
+
+    /// Return the total number of particles spawned since the
+    /// ParticleSys was last set up.
+    fn spawned_total(&self) -> usize {
+        0
+    }
+
+    /// Leave aging the particles spawned by the next `update_frame()` to
+    /// the AgingJob returned by `aging_job()`, so a group can age those
+    /// of all its parts across threads with the `parallel` feature.
+    /// Returns `false` if the ParticleSys ages them itself, as the
+    /// default does. This function isn't intended to be called by the
+    /// user, but by groups.
+    #[cfg(feature = "parallel")]
+    fn defer_aging(&mut self) -> bool {
+        false
+    }
+
+    /// Return the aging left to the caller by `defer_aging()` for the
+    /// last `update_frame()`, if any. This function isn't intended to be
+    /// called by the user, but by groups.
+    #[cfg(feature = "parallel")]
+    fn aging_job(&mut self) -> Option<AgingJob<'_>> {
+        None
+    }
+
+    /// Call `f` with each live Particle managed by the ParticleSys,
+    /// including those of any nested systems. The default visits the
+    /// systems returned by `iter()`.
+    fn for_each_particle(&self, f: &mut dyn FnMut(&Particle)) {
+        if let Some(parts) = self.iter() {
+            for p in parts {
+                p.for_each_particle(f);
+            }
+        }
+    }
+
+    /// Draw the `gizmos` of the ParticleSys and of every system nested
+    /// within it, showing where they emit. The default draws those of
+    /// the systems returned by `iter()`.
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        if let Some(parts) = self.iter() {
+            for p in parts {
+                p.draw_debug(gizmos);
+            }
+        }
+    }
+
+    /// Set the period of the ParticleSys to `p` in place. Returns an
+    /// error if `p` is not a valid period.
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError>;
+
+    /// Returns self (consuming it) with period `p`.
+    fn with_period(mut self, p: f32) -> Result<Self, LinearPLError>
     where
-        Self: Sized;
+        Self: Sized,
+    {
+        self.set_period(p)?;
+        Ok(self)
+    }
 
-    /// Set up ParticleSys into its looping active state.
-    fn start_loop(&mut self) -> Result<(), String> {
+    /// Set up ParticleSys into its active state, repeating according
+    /// to `mode`.
+    fn start_with_mode(&mut self, mode: LoopMode) -> Result<(), LinearPLError> {
         self.tear_down();
-        self.setup(true, None)
+        self.setup(mode, None)
+    }
+
+    /// Set up ParticleSys into its looping active state.
+    fn start_loop(&mut self) -> Result<(), LinearPLError> {
+        self.start_with_mode(LoopMode::Loop)
     }
 
     /// Set up ParticleSys into its active state.
-    fn start(&mut self) -> Result<(), String> {
+    fn start(&mut self) -> Result<(), LinearPLError> {
+        self.start_with_mode(LoopMode::Once)
+    }
+
+    /// Set up ParticleSys into its active state with its zero point
+    /// anchored at the external timestamp `origin` (e.g. an audio
+    /// playback position). Use `run_at()` to display frames afterwards.
+    fn start_at(&mut self, origin: f32) -> Result<(), LinearPLError> {
         self.tear_down();
-        self.setup(false, None)
+        self.set_time(origin);
+        self.setup(LoopMode::Once, None)
+    }
+
+    /// Set up ParticleSys into its looping active state with its zero
+    /// point anchored at the external timestamp `origin`.
+    fn start_loop_at(&mut self, origin: f32) -> Result<(), LinearPLError> {
+        self.tear_down();
+        self.set_time(origin);
+        self.setup(LoopMode::Loop, None)
     }
 
     /// Tear down and deactivate ParticleSys object.
@@ -112,25 +494,498 @@ pub trait ParticleSys {
         self.tear_down();
     }
 
-    /// Display the next frame available from the LinearParticle.
+    /// Display the next frame available from the ParticleSys using an
+    /// external clock reading of `t` seconds instead of wall time.
+    ///
+    /// Returns the same values as `run()`.
+    fn run_with_time(&mut self, t: f32) -> Result<bool, LinearPLError> {
+        self.set_time(t);
+        self.run()
+    }
+
+    /// Display the next frame available from the ParticleSys at the
+    /// external timestamp `now`, evaluated relative to the `origin`
+    /// given to `start_at()` or `start_loop_at()`.
+    ///
+    /// Returns the same values as `run()`.
+    fn run_at(&mut self, now: f32) -> Result<bool, LinearPLError> {
+        self.run_with_time(now)
+    }
+
+    /// Display the next frame available from the LinearParticle. This
+    /// is `update()` followed by `draw()`.
     ///
     ///
     /// # Returns:
     ///
     /// - `Ok(true)` if LinearParticle is still 'active' in next frame,
     /// - `Ok(false)` otherwise
-    fn run(&mut self) -> Result<bool, String> {
+    fn run(&mut self) -> Result<bool, LinearPLError> {
+        let active = self.update()?;
+        self.draw()?;
+        Ok(active)
+    }
+
+    /// Advance the ParticleSys to the current reading of its clock
+    /// without drawing anything, such as to keep an effect going while
+    /// a menu hides it or to simulate it in a test without a window.
+    ///
+    /// Returns the same values as `run()`.
+    fn update(&mut self) -> Result<bool, LinearPLError> {
         if !(self.is_active() && self.is_initialized()) {
-            return Err("object has not been setup yet for running".into());
+            return Err(LinearPLError::NotStarted);
         }
         let elapsed = self.elapsed_time();
-        if !self.next_frame(elapsed)? {
+        if !self.update_frame(elapsed)? {
             if self.is_looping() {
                 self.reset_time();
+                self.handle_loop();
+            } else {
+                self.handle_complete();
             }
             Ok(false)
         } else {
             Ok(true)
         }
     }
+
+    /// Step the ParticleSys `dt` seconds forward on its own clock rather
+    /// than by wall time and update it, for engines running their own
+    /// fixed update loop or time scale. Call `draw()` to display the
+    /// result.
+    ///
+    /// Returns the same values as `run()`.
+    fn advance(&mut self, dt: f32) -> Result<bool, LinearPLError> {
+        self.advance_time(dt);
+        self.update()
+    }
+
+    /// Draw the particles of the ParticleSys as of the last `update()`
+    /// without advancing it. This can be called any number of times
+    /// between updates, such as once for each camera of a split screen.
+    fn draw(&mut self) -> Result<(), LinearPLError> {
+        self.redraw_frame()
+    }
+}
+
+/// Object-safe counterpart of `ParticleSys` allowing heterogeneous
+/// collections such as `Vec<Box<dyn ParticleSysDyn>>`.
+///
+/// This is implemented for every `Clone` type implementing `ParticleSys`,
+/// so it never needs to be implemented by hand. `Box<dyn ParticleSysDyn>`
+/// implements `ParticleSys` in turn, so boxed systems can be placed in
+/// any of the library's groups (see `linearpl::groups::MixedGrp`).
+///
+/// As the method names match those of `ParticleSys`, only one of the two
+/// traits should be imported in a given scope.
+pub trait ParticleSysDyn {
+    /// See `ParticleSys::is_active`.
+    fn is_active(&self) -> bool;
+
+    /// See `ParticleSys::is_looping`.
+    fn is_looping(&self) -> bool;
+
+    /// See `ParticleSys::is_initialized`.
+    fn is_initialized(&mut self) -> bool;
+
+    /// See `ParticleSys::reset_time`.
+    fn reset_time(&mut self);
+
+    /// See `ParticleSys::elapsed_time`.
+    fn elapsed_time(&mut self) -> Option<f32>;
+
+    /// See `ParticleSys::set_time`.
+    fn set_time(&mut self, t: f32);
+
+    /// See `ParticleSys::advance_time`.
+    fn advance_time(&mut self, dt: f32);
+
+    /// See `ParticleSys::set_clock`.
+    fn set_clock(&mut self, clock: &Clock);
+
+    /// See `ParticleSys::setup`.
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError>;
+
+    /// See `ParticleSys::tear_down`.
+    fn tear_down(&mut self);
+
+    /// See `ParticleSys::update_frame`.
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::next_frame`.
+    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::handle_complete`.
+    fn handle_complete(&mut self);
+
+    /// See `ParticleSys::handle_loop`.
+    fn handle_loop(&mut self);
+
+    /// See `ParticleSys::drain_frame`.
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::redraw_frame`.
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError>;
+
+    /// See `ParticleSys::describe`.
+    fn describe(&self) -> SysInfo;
+
+    /// See `ParticleSys::perf_counters`.
+    fn perf_counters(&self) -> Option<PerfCounters>;
+
+    /// See `ParticleSys::particle_count`.
+    fn particle_count(&self) -> usize;
+
+    /// See `ParticleSys::peak_particle_count`.
+    fn peak_particle_count(&self) -> usize;
+
+    /// See `ParticleSys::spawned_total`.
+    fn spawned_total(&self) -> usize;
+
+    /// See `ParticleSys::defer_aging`.
+    #[cfg(feature = "parallel")]
+    fn defer_aging(&mut self) -> bool;
+
+    /// See `ParticleSys::aging_job`.
+    #[cfg(feature = "parallel")]
+    fn aging_job(&mut self) -> Option<AgingJob<'_>>;
+
+    /// See `ParticleSys::for_each_particle`.
+    fn for_each_particle(&self, f: &mut dyn FnMut(&Particle));
+
+    /// See `ParticleSys::draw_debug`.
+    fn draw_debug(&self, gizmos: &Gizmos);
+
+    /// See `ParticleSys::set_period`.
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError>;
+
+    /// See `ParticleSys::start_with_mode`.
+    fn start_with_mode(&mut self, mode: LoopMode) -> Result<(), LinearPLError>;
+
+    /// See `ParticleSys::start`.
+    fn start(&mut self) -> Result<(), LinearPLError>;
+
+    /// See `ParticleSys::start_loop`.
+    fn start_loop(&mut self) -> Result<(), LinearPLError>;
+
+    /// See `ParticleSys::stop`.
+    fn stop(&mut self);
+
+    /// See `ParticleSys::run`.
+    fn run(&mut self) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::run_with_time`.
+    fn run_with_time(&mut self, t: f32) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::update`.
+    fn update(&mut self) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::advance`.
+    fn advance(&mut self, dt: f32) -> Result<bool, LinearPLError>;
+
+    /// See `ParticleSys::draw`.
+    fn draw(&mut self) -> Result<(), LinearPLError>;
+
+    /// Return a boxed clone of self.
+    fn clone_box(&self) -> Box<dyn ParticleSysDyn>;
+}
+
+impl<P: ParticleSys + Clone + 'static> ParticleSysDyn for P {
+    fn is_active(&self) -> bool {
+        ParticleSys::is_active(self)
+    }
+
+    fn is_looping(&self) -> bool {
+        ParticleSys::is_looping(self)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        ParticleSys::is_initialized(self)
+    }
+
+    fn reset_time(&mut self) {
+        ParticleSys::reset_time(self)
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        ParticleSys::elapsed_time(self)
+    }
+
+    fn set_time(&mut self, t: f32) {
+        ParticleSys::set_time(self, t)
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        ParticleSys::advance_time(self, dt)
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        ParticleSys::set_clock(self, clock)
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        ParticleSys::setup(self, mode, p)
+    }
+
+    fn tear_down(&mut self) {
+        ParticleSys::tear_down(self)
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        ParticleSys::update_frame(self, time)
+    }
+
+    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        ParticleSys::next_frame(self, time)
+    }
+
+    fn handle_complete(&mut self) {
+        ParticleSys::handle_complete(self)
+    }
+
+    fn handle_loop(&mut self) {
+        ParticleSys::handle_loop(self)
+    }
+
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        ParticleSys::drain_frame(self, time)
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        ParticleSys::redraw_frame(self)
+    }
+
+    fn describe(&self) -> SysInfo {
+        ParticleSys::describe(self)
+    }
+
+    fn perf_counters(&self) -> Option<PerfCounters> {
+        ParticleSys::perf_counters(self)
+    }
+
+    fn particle_count(&self) -> usize {
+        ParticleSys::particle_count(self)
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        ParticleSys::peak_particle_count(self)
+    }
+
+    fn spawned_total(&self) -> usize {
+        ParticleSys::spawned_total(self)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn defer_aging(&mut self) -> bool {
+        ParticleSys::defer_aging(self)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn aging_job(&mut self) -> Option<AgingJob<'_>> {
+        ParticleSys::aging_job(self)
+    }
+
+    fn for_each_particle(&self, f: &mut dyn FnMut(&Particle)) {
+        ParticleSys::for_each_particle(self, f)
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        ParticleSys::draw_debug(self, gizmos)
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        ParticleSys::set_period(self, p)
+    }
+
+    fn start_with_mode(&mut self, mode: LoopMode) -> Result<(), LinearPLError> {
+        ParticleSys::start_with_mode(self, mode)
+    }
+
+    fn start(&mut self) -> Result<(), LinearPLError> {
+        ParticleSys::start(self)
+    }
+
+    fn start_loop(&mut self) -> Result<(), LinearPLError> {
+        ParticleSys::start_loop(self)
+    }
+
+    fn stop(&mut self) {
+        ParticleSys::stop(self)
+    }
+
+    fn run(&mut self) -> Result<bool, LinearPLError> {
+        ParticleSys::run(self)
+    }
+
+    fn run_with_time(&mut self, t: f32) -> Result<bool, LinearPLError> {
+        ParticleSys::run_with_time(self, t)
+    }
+
+    fn update(&mut self) -> Result<bool, LinearPLError> {
+        ParticleSys::update(self)
+    }
+
+    fn advance(&mut self, dt: f32) -> Result<bool, LinearPLError> {
+        ParticleSys::advance(self, dt)
+    }
+
+    fn draw(&mut self) -> Result<(), LinearPLError> {
+        ParticleSys::draw(self)
+    }
+
+    fn clone_box(&self) -> Box<dyn ParticleSysDyn> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ParticleSysDyn> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+impl fmt::Debug for dyn ParticleSysDyn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParticleSysDyn")
+            .field("active", &self.is_active())
+            .field("looping", &self.is_looping())
+            .finish()
+    }
+}
+
+impl ParticleSys for Box<dyn ParticleSysDyn> {
+    type T = Box<dyn ParticleSysDyn>;
+
+    fn is_active(&self) -> bool {
+        ParticleSysDyn::is_active(&**self)
+    }
+
+    fn is_looping(&self) -> bool {
+        ParticleSysDyn::is_looping(&**self)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        ParticleSysDyn::is_initialized(&mut **self)
+    }
+
+    fn reset_time(&mut self) {
+        ParticleSysDyn::reset_time(&mut **self)
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        ParticleSysDyn::elapsed_time(&mut **self)
+    }
+
+    fn set_time(&mut self, t: f32) {
+        ParticleSysDyn::set_time(&mut **self, t)
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        ParticleSysDyn::advance_time(&mut **self, dt)
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        ParticleSysDyn::set_clock(&mut **self, clock)
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        ParticleSysDyn::setup(&mut **self, mode, p)
+    }
+
+    fn tear_down(&mut self) {
+        ParticleSysDyn::tear_down(&mut **self)
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        ParticleSysDyn::update_frame(&mut **self, time)
+    }
+
+    fn next_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        ParticleSysDyn::next_frame(&mut **self, time)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        None
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        None
+    }
+
+    fn handle_complete(&mut self) {
+        ParticleSysDyn::handle_complete(&mut **self)
+    }
+
+    fn handle_loop(&mut self) {
+        ParticleSysDyn::handle_loop(&mut **self)
+    }
+
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        ParticleSysDyn::drain_frame(&mut **self, time)
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        ParticleSysDyn::redraw_frame(&mut **self)
+    }
+
+    fn describe(&self) -> SysInfo {
+        ParticleSysDyn::describe(&**self)
+    }
+
+    fn perf_counters(&self) -> Option<PerfCounters> {
+        ParticleSysDyn::perf_counters(&**self)
+    }
+
+    fn particle_count(&self) -> usize {
+        ParticleSysDyn::particle_count(&**self)
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        ParticleSysDyn::peak_particle_count(&**self)
+    }
+
+    fn spawned_total(&self) -> usize {
+        ParticleSysDyn::spawned_total(&**self)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn defer_aging(&mut self) -> bool {
+        ParticleSysDyn::defer_aging(&mut **self)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn aging_job(&mut self) -> Option<AgingJob<'_>> {
+        ParticleSysDyn::aging_job(&mut **self)
+    }
+
+    fn for_each_particle(&self, f: &mut dyn FnMut(&Particle)) {
+        ParticleSysDyn::for_each_particle(&**self, f)
+    }
+
+    fn draw_debug(&self, gizmos: &Gizmos) {
+        ParticleSysDyn::draw_debug(&**self, gizmos)
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        ParticleSysDyn::set_period(&mut **self, p)
+    }
+}
+
+#[test]
+fn particle_sys_dyn_test() {
+    use crate::groups::SyncGrp;
+    use crate::linear_particles::LinearParticles;
+
+    let mut systems: Vec<Box<dyn ParticleSysDyn>> = vec![
+        Box::new(LinearParticles::default()),
+        Box::new(SyncGrp::new(1., &[LinearParticles::default()])),
+    ];
+    for s in systems.iter_mut() {
+        ParticleSysDyn::start_loop(s.as_mut()).unwrap();
+    }
+    assert!(systems
+        .iter()
+        .all(|s| ParticleSysDyn::is_active(s.as_ref()) && ParticleSysDyn::is_looping(s.as_ref())));
+    ParticleSysDyn::stop(systems[0].as_mut());
+    assert!(!ParticleSysDyn::is_active(systems[0].as_ref()));
 }
@@ -0,0 +1,346 @@
+//! # Presets
+//!
+//! Ready-made effects to start from. Each preset returns a configured,
+//! unstarted system placed by a few parameters, which can be adjusted
+//! further with the usual `set_*` and `with_*` methods:
+//!
+//! ```ignore
+//! let mut ring = presets::energy_ring(vec3(0., 1., 0.), 1.5, Vec3::Y, SKYBLUE)?;
+//! let mut dust = presets::ambient_dust(Vec3::ZERO, 4.)?.with_max_particles(300);
+//!
+//! ring.start_loop()?;
+//! dust.start_loop()?;
+//! ```
+//!
+//! Presets sized by a radius or height are tuned for scenes a few units
+//! across, such as the example binary's.
+
+use std::f32::consts::TAU;
+
+use crate::curves::TrackMode;
+use crate::error::LinearPLError;
+use crate::groups::SyncGrp;
+use crate::linear_particles::{LinearParticles, TrackKind};
+use crate::math::{Color, Vec3};
+use crate::particle::{BlendMode, FadeCurve, ParticleShape};
+
+// number of straight lines approximating a ring
+const RING_SEGMENTS: usize = 16;
+
+// number of lines running along a tunnel
+const TUNNEL_LINES: usize = 8;
+
+// `color` with its alpha scaled by `a`
+fn faded(color: Color, a: f32) -> Color {
+    Color::new(color.r, color.g, color.b, color.a * a)
+}
+
+/// Twinkling points scattered along the line from `start` to `end`.
+pub fn sparkle_line(
+    start: Vec3,
+    end: Vec3,
+    color: Color,
+) -> Result<LinearParticles, LinearPLError> {
+    LinearParticles::builder(start, end)
+        .period(2.)
+        .rates(&[40.])
+        .decay(0.6)
+        .spread(0.05)
+        .shape(ParticleShape::Billboard)
+        .sizes(&[0.03])
+        .colors(&[color, faded(color, 0.5)])
+        .blend_mode(BlendMode::Additive)
+        .fade_in(0.2)
+        .fade_curve(FadeCurve::Smooth)
+        .build()
+}
+
+/// Ring of glowing energy `radius` around `center`, facing `normal`.
+pub fn energy_ring(
+    center: Vec3,
+    radius: f32,
+    normal: Vec3,
+    color: Color,
+) -> Result<SyncGrp<LinearParticles>, LinearPLError> {
+    let (u, v) = normal
+        .try_normalize()
+        .unwrap_or(Vec3::Y)
+        .any_orthonormal_pair();
+    let point = |i: usize| {
+        let angle = TAU * i as f32 / RING_SEGMENTS as f32;
+        center + (u * angle.cos() + v * angle.sin()) * radius
+    };
+    let arc = LinearParticles::builder(point(0), point(1))
+        .period(1.)
+        .densities(&[0.8])
+        .decay(0.4)
+        .colors(&[color, faded(color, 0.6)])
+        .sizes(&[0.01])
+        .glow(0.03, 0.3)
+        .blend_mode(BlendMode::Additive)
+        .build()?;
+    let parts = (0..RING_SEGMENTS)
+        .map(|i| arc.clone_with_start_end(point(i), point(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SyncGrp::new(1., &parts))
+}
+
+/// Faint motes of dust drifting within `radius` of `center`.
+pub fn ambient_dust(center: Vec3, radius: f32) -> Result<LinearParticles, LinearPLError> {
+    let offset = Vec3::X * radius;
+    LinearParticles::builder(center - offset, center + offset)
+        .period(8.)
+        .locations(&[0., 1., 0.])
+        .track_mode(TrackKind::Locations, TrackMode::CatmullRom)
+        .rates(&[6.])
+        .decay(6.)
+        .spread(radius)
+        .velocity(Vec3::new(0., 0.02, 0.), 0.03)
+        .shape(ParticleShape::Point)
+        .colors(&[Color::new(0.9, 0.85, 0.7, 0.35)])
+        .fade_in(1.5)
+        .fade_curve(FadeCurve::Smooth)
+        .warmup(6.)
+        .build()
+}
+
+/// Streaks rushing from `start` to `end` along the walls of a tunnel
+/// `radius` wide.
+pub fn warp_tunnel(
+    start: Vec3,
+    end: Vec3,
+    radius: f32,
+    color: Color,
+) -> Result<SyncGrp<LinearParticles>, LinearPLError> {
+    let axis = end - start;
+    let (u, v) = axis
+        .try_normalize()
+        .unwrap_or(Vec3::Z)
+        .any_orthonormal_pair();
+    let streak = LinearParticles::builder(start, end)
+        .period(0.5)
+        .rates(&[30.])
+        .decay(0.35)
+        .velocity(axis * 2., 0.)
+        .stretch(0.05)
+        .spread(radius * 0.1)
+        .colors(&[faded(color, 0.2), color])
+        .sizes(&[0.01])
+        .blend_mode(BlendMode::Additive)
+        .build()?;
+    let parts = (0..TUNNEL_LINES)
+        .map(|i| {
+            let angle = TAU * i as f32 / TUNNEL_LINES as f32;
+            let offset = (u * angle.cos() + v * angle.sin()) * radius;
+            streak.clone_with_start_end(start + offset, end + offset)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SyncGrp::new(0.5, &parts))
+}
+
+/// Burst of tumbling paper confetti thrown up to around `height`
+/// above `origin`.
+pub fn confetti(origin: Vec3, height: f32) -> Result<LinearParticles, LinearPLError> {
+    let speed = (2. * 9.8 * height).sqrt();
+    LinearParticles::builder(origin, origin + Vec3::Y * 0.01)
+        .period(0.5)
+        .rates(&[300., 0.])
+        .decay(3.)
+        .velocity(Vec3::Y * speed, speed * 0.4)
+        .gravity(Vec3::new(0., -9.8, 0.))
+        .drag(1.5)
+        .spin(6., 4.)
+        .shape(ParticleShape::Cube)
+        .sizes(&[0.025])
+        .colors(&[
+            Color::new(0.9, 0.16, 0.22, 1.),
+            Color::new(0.99, 0.98, 0., 1.),
+            Color::new(0., 0.89, 0.19, 1.),
+            Color::new(0., 0.47, 0.95, 1.),
+            Color::new(1., 0.43, 0.76, 1.),
+            Color::new(1., 0.63, 0., 1.),
+        ])
+        .track_mode(TrackKind::Colors, TrackMode::Step)
+        .build()
+}
+
+/// Flames licking upwards from the line between `start` and `end`.
+pub fn fire(start: Vec3, end: Vec3) -> Result<LinearParticles, LinearPLError> {
+    LinearParticles::builder(start, end)
+        .period(0.3)
+        .locations(&[0., 1., 0.])
+        .rates(&[120.])
+        .decay(0.7)
+        .spread(0.05)
+        .velocity(Vec3::Y * 0.8, 0.2)
+        .drag(0.5)
+        .shape(ParticleShape::Billboard)
+        .sizes(&[0.06, 0.03])
+        .colors(&[
+            Color::new(1., 0.9, 0.4, 0.9),
+            Color::new(1., 0.45, 0.1, 0.8),
+            Color::new(0.8, 0.1, 0.05, 0.6),
+        ])
+        .blend_mode(BlendMode::Additive)
+        .fade_curve(FadeCurve::EaseOut)
+        .build()
+}
+
+/// Column of grey smoke rising around `height` above `base`.
+pub fn smoke(base: Vec3, height: f32) -> Result<LinearParticles, LinearPLError> {
+    let decay = 4.;
+    LinearParticles::builder(base, base + Vec3::Y * 0.01)
+        .period(1.)
+        .rates(&[12.])
+        .decay(decay)
+        .spread(0.1)
+        .velocity(Vec3::Y * height / decay, 0.05)
+        .shape(ParticleShape::Sphere)
+        .sizes(&[0.08, 0.12])
+        .colors(&[Color::new(0.45, 0.45, 0.45, 0.4)])
+        .fade_in(0.5)
+        .fade_curve(FadeCurve::Smooth)
+        .warmup(decay)
+        .build()
+}
+
+/// Rain falling `height` from the line between `start` and `end`.
+pub fn rain(start: Vec3, end: Vec3, height: f32) -> Result<LinearParticles, LinearPLError> {
+    let speed = 8.;
+    LinearParticles::builder(start, end)
+        .period(0.25)
+        .locations(&[0., 1.])
+        .rates(&[200.])
+        .decay(height / speed)
+        .spread((end - start).length() * 0.5)
+        .velocity(Vec3::NEG_Y * speed, 0.5)
+        .stretch(0.02)
+        .sizes(&[0.004])
+        .colors(&[Color::new(0.7, 0.8, 1., 0.5)])
+        .warmup(height / speed)
+        .build()
+}
+
+/// Snow drifting down from the line between `start` and `end`.
+pub fn snow(start: Vec3, end: Vec3) -> Result<LinearParticles, LinearPLError> {
+    LinearParticles::builder(start, end)
+        .period(3.)
+        .locations(&[0., 1., 0.])
+        .track_mode(TrackKind::Locations, TrackMode::CatmullRom)
+        .rates(&[25.])
+        .decay(6.)
+        .spread((end - start).length() * 0.5)
+        .velocity(Vec3::new(0., -0.3, 0.), 0.1)
+        .shape(ParticleShape::Billboard)
+        .sizes(&[0.015])
+        .colors(&[Color::new(1., 1., 1., 0.85)])
+        .fade_in(0.5)
+        .warmup(6.)
+        .build()
+}
+
+/// Steady beam of light from `start` to `end`.
+pub fn laser(start: Vec3, end: Vec3, color: Color) -> Result<LinearParticles, LinearPLError> {
+    LinearParticles::builder(start, end)
+        .period(0.1)
+        .densities(&[1.])
+        .decay(0.08)
+        .sizes(&[0.008])
+        .colors(&[color])
+        .glow(0.04, 0.25)
+        .blend_mode(BlendMode::Additive)
+        .build()
+}
+
+/// Jet of droplets arcing up to around `height` above `origin` before
+/// falling back down.
+pub fn fountain(origin: Vec3, height: f32, color: Color) -> Result<LinearParticles, LinearPLError> {
+    let speed = (2. * 9.8 * height).sqrt();
+    LinearParticles::builder(origin, origin + Vec3::Y * 0.01)
+        .period(1.)
+        .rates(&[150.])
+        .decay(2. * speed / 9.8)
+        .velocity(Vec3::Y * speed, speed * 0.15)
+        .gravity(Vec3::new(0., -9.8, 0.))
+        .shape(ParticleShape::Point)
+        .colors(&[color])
+        .fade_curve(FadeCurve::EaseIn)
+        .build()
+}
+
+/// Fireflies wandering within `radius` of `center`, blinking on and off.
+pub fn fireflies(center: Vec3, radius: f32) -> Result<LinearParticles, LinearPLError> {
+    let offset = Vec3::Z * radius;
+    LinearParticles::builder(center - offset, center + offset)
+        .period(5.)
+        .locations(&[0.2, 1., 0., 0.8])
+        .track_mode(TrackKind::Locations, TrackMode::CatmullRom)
+        .rates(&[3.])
+        .decay(2.5)
+        .spread(radius)
+        .velocity(Vec3::ZERO, 0.15)
+        .shape(ParticleShape::Billboard)
+        .sizes(&[0.02])
+        .colors(&[Color::new(0.75, 1., 0.3, 1.)])
+        .glow(0.04, 0.3)
+        .blend_mode(BlendMode::Additive)
+        .fade_in(1.)
+        .fade_curve(FadeCurve::Smooth)
+        .warmup(2.5)
+        .build()
+}
+
+/// Sparks spraying from `origin` in `direction`, such as from a grinder
+/// or a short circuit.
+pub fn sparks(origin: Vec3, direction: Vec3) -> Result<LinearParticles, LinearPLError> {
+    LinearParticles::builder(origin, origin + direction.normalize_or_zero() * 0.01)
+        .period(0.2)
+        .rates(&[180.])
+        .decay(0.5)
+        .velocity(direction * 4., 1.5)
+        .gravity(Vec3::new(0., -9.8, 0.))
+        .stretch(0.03)
+        .sizes(&[0.005])
+        .colors(&[Color::new(1., 0.95, 0.6, 1.), Color::new(1., 0.6, 0.2, 1.)])
+        .blend_mode(BlendMode::Additive)
+        .build()
+}
+
+#[test]
+fn presets_test() {
+    use crate::particle_sys::ParticleSys;
+
+    let (a, b) = (Vec3::ZERO, Vec3::new(1., 0., 0.));
+    let white = Color::new(1., 1., 1., 1.);
+    let mut systems = vec![
+        sparkle_line(a, b, white).unwrap(),
+        ambient_dust(a, 2.).unwrap(),
+        confetti(a, 1.).unwrap(),
+        fire(a, b).unwrap(),
+        smoke(a, 1.).unwrap(),
+        rain(a, b, 2.).unwrap(),
+        snow(a, b).unwrap(),
+        laser(a, b, white).unwrap(),
+        fountain(a, 1., white).unwrap(),
+        fireflies(a, 1.).unwrap(),
+        sparks(a, Vec3::Y).unwrap(),
+    ];
+    for lp in systems.iter_mut() {
+        lp.start_loop().unwrap();
+        for _ in 0..30 {
+            lp.advance(1. / 60.).unwrap();
+        }
+        assert!(lp.particle_count() > 0);
+    }
+
+    let mut ring = energy_ring(a, 1., Vec3::Y, white).unwrap();
+    let mut tunnel = warp_tunnel(a, Vec3::new(0., 0., -5.), 1., white).unwrap();
+    for grp in [&mut ring, &mut tunnel] {
+        grp.start_loop().unwrap();
+        for _ in 0..30 {
+            grp.advance(1. / 60.).unwrap();
+        }
+        assert!(grp.particle_count() > 0);
+    }
+    assert_eq!(ring.describe().children.len(), RING_SEGMENTS);
+}
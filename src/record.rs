@@ -0,0 +1,170 @@
+//! # Record
+//!
+//! Capturing effects as images to share. A `Recorder` grabs the screen
+//! each frame it is given for a set number of seconds and writes it to a
+//! numbered PNG file, ready to be turned into a video or posted as is:
+//!
+//! ```ignore
+//! let mut recorder = Recorder::new("capture", 3.)?;
+//!
+//! loop {
+//!     lp.run()?;
+//!     recorder.capture()?;
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! `capture()` should be called once everything for the frame has been
+//! drawn and before `next_frame()`. With the `gif` feature enabled,
+//! `with_gif()` writes the frames to an animated GIF as well, timed by
+//! the frame time of each. With the `headless` feature enabled nothing is
+//! drawn, so nothing is captured.
+
+use macroquad::texture::{get_screen_data, Image};
+use macroquad::time::get_frame_time;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "gif")]
+use image::codecs::gif::{GifEncoder, Repeat};
+#[cfg(feature = "gif")]
+use std::{fs::File, io::BufWriter};
+
+use crate::error::LinearPLError;
+use crate::util::{check_duration, HEADLESS};
+
+/// Writer of numbered PNG files of the screen, one per frame, for a set
+/// number of seconds.
+pub struct Recorder {
+    dir: PathBuf,
+    duration: f32,
+    elapsed: f32,
+    frame: usize,
+    #[cfg(feature = "gif")]
+    gif: Option<(PathBuf, GifEncoder<BufWriter<File>>)>,
+}
+
+impl Recorder {
+    /// Create a new Recorder writing `seconds` worth of frames into the
+    /// directory `dir`, creating it if needed. Returns an error if
+    /// `seconds` is negative or the directory can't be created.
+    pub fn new(dir: impl AsRef<Path>, seconds: f32) -> Result<Self, LinearPLError> {
+        check_duration(seconds)?;
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| LinearPLError::io(&dir, e))?;
+        Ok(Recorder {
+            dir,
+            duration: seconds,
+            elapsed: 0.,
+            frame: 0,
+            #[cfg(feature = "gif")]
+            gif: None,
+        })
+    }
+
+    /// Return self (consuming it) writing the frames to an animated GIF
+    /// at `path` as well, looping forever. Returns an error if the file
+    /// can't be created.
+    #[cfg(feature = "gif")]
+    pub fn with_gif(mut self, path: impl AsRef<Path>) -> Result<Self, LinearPLError> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path).map_err(|e| LinearPLError::io(&path, e))?;
+        let mut encoder = GifEncoder::new(BufWriter::new(file));
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| LinearPLError::io(&path, e))?;
+        self.gif = Some((path, encoder));
+        Ok(self)
+    }
+
+    /// Return the number of frames written so far.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Return `true` until the set number of seconds has been recorded.
+    pub fn is_recording(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// Write what has been drawn to the screen this frame to the file of
+    /// the next frame, such as `frame_00042.png`. Returns `true` while
+    /// there is more to record.
+    pub fn capture(&mut self) -> Result<bool, LinearPLError> {
+        if HEADLESS || !self.is_recording() {
+            return Ok(false);
+        }
+        self.record_image(&get_screen_data(), get_frame_time())
+    }
+
+    /// Write `image`, bottom row first as returned by `get_screen_data()`
+    /// or a render target's `get_texture_data()`, to the file of the next
+    /// frame as if shown for `dt` seconds. Returns `true` while there is
+    /// more to record.
+    pub fn record_image(&mut self, image: &Image, dt: f32) -> Result<bool, LinearPLError> {
+        if !self.is_recording() {
+            return Ok(false);
+        }
+        let (width, height) = (image.width as u32, image.height as u32);
+        let bytes = flipped_rows(&image.bytes, image.width as usize * 4);
+
+        let path = self.dir.join(format!("frame_{:05}.png", self.frame));
+        image::save_buffer(&path, &bytes, width, height, image::ColorType::Rgba8)
+            .map_err(|e| LinearPLError::io(&path, e))?;
+        #[cfg(feature = "gif")]
+        if let Some((path, encoder)) = self.gif.as_mut() {
+            let buffer = image::RgbaImage::from_raw(width, height, bytes)
+                .ok_or_else(|| LinearPLError::io(path, "image bytes don't hold 4 per pixel"))?;
+            let delay = image::Delay::from_numer_denom_ms((dt * 1000.).round() as u32, 1);
+            encoder
+                .encode_frame(image::Frame::from_parts(buffer, 0, 0, delay))
+                .map_err(|e| LinearPLError::io(path, e))?;
+        }
+
+        self.frame += 1;
+        self.elapsed += dt;
+        // the GIF is finished once its encoder is dropped
+        #[cfg(feature = "gif")]
+        if !self.is_recording() {
+            self.gif = None;
+        }
+        Ok(self.is_recording())
+    }
+}
+
+// `bytes` with its rows of `stride` bytes in reverse order
+fn flipped_rows(bytes: &[u8], stride: usize) -> Vec<u8> {
+    if stride == 0 {
+        return Vec::new();
+    }
+    bytes.rchunks_exact(stride).flatten().copied().collect()
+}
+
+#[test]
+fn recorder_test() {
+    use macroquad::color::Color;
+
+    let dir = std::env::temp_dir().join(format!("linearpl-record-{}", std::process::id()));
+    assert!(Recorder::new(&dir, -1.).is_err());
+    let recorder = Recorder::new(&dir, 1.).unwrap();
+    #[cfg(feature = "gif")]
+    let recorder = recorder.with_gif(dir.join("effect.gif")).unwrap();
+    let mut recorder = recorder;
+
+    // bottom row red, top row blue
+    let mut image = Image::gen_image_color(3, 2, Color::new(1., 0., 0., 1.));
+    image.set_pixel(0, 1, Color::new(0., 0., 1., 1.));
+    assert!(recorder.record_image(&image, 0.5).unwrap());
+    assert!(!recorder.record_image(&image, 0.5).unwrap());
+    assert!(!recorder.record_image(&image, 0.5).unwrap());
+    assert_eq!(recorder.frame(), 2);
+    assert!(!dir.join("frame_00002.png").exists());
+
+    let frame = image::open(dir.join("frame_00001.png")).unwrap().to_rgba8();
+    assert_eq!(frame.dimensions(), (3, 2));
+    assert_eq!(frame.get_pixel(0, 0).0, [0, 0, 255, 255]);
+    assert_eq!(frame.get_pixel(0, 1).0, [255, 0, 0, 255]);
+    #[cfg(feature = "gif")]
+    assert!(fs::metadata(dir.join("effect.gif")).unwrap().len() > 0);
+    fs::remove_dir_all(&dir).unwrap();
+}
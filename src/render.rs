@@ -0,0 +1,628 @@
+//! # Rendering
+//!
+//! Backends drawing particles. Each particle hands the primitives it is
+//! made of to a `ParticleRenderer`: the line segments of its line, trail
+//! and the ring of a thick line, which may be drawn straight away or
+//! gathered to draw together on `flush()`, and its cubes, spheres and
+//! billboards, which are drawn straight away with macroquad unless the
+//! renderer draws them itself.
+//!
+//! Systems pick the renderer they draw with by `RenderMode`:
+//!
+//! ```ignore
+//! let lp = LinearParticles::new(start, end).with_render_mode(RenderMode::Batched);
+//! ```
+//!
+//! With the `instancing` feature enabled, `RenderMode::Instanced` uploads
+//! the segments as instance data drawn by a custom shader, leaving the
+//! placement of every vertex to the GPU.
+//!
+//! A `MockRenderer` draws nothing but records each call it is handed,
+//! so tests can check what a particle or system would draw:
+//!
+//! ```ignore
+//! let mut mock = MockRenderer::new();
+//! lp.draw_to(&mut mock);
+//! assert_eq!(mock.segments().count(), lp.particle_count());
+//! ```
+//!
+//! Without the `macroquad` feature, only the `ParticleRenderer` trait,
+//! `MockRenderer` and `RenderMode` are built. Systems then draw nothing,
+//! but particles can still be handed to a renderer of another library
+//! with `draw_to()`.
+
+#[cfg(feature = "macroquad")]
+use macroquad::camera::Camera3D;
+#[cfg(feature = "macroquad")]
+use macroquad::models::{draw_line_3d, Vertex};
+#[cfg(feature = "macroquad")]
+use macroquad::prelude::{draw_affine_parallelogram, draw_cube, draw_sphere, DrawMode};
+#[cfg(feature = "macroquad")]
+use macroquad::window::get_internal_gl;
+
+use crate::math::{Color, Quat, Vec3};
+#[cfg(feature = "macroquad")]
+use crate::math::{Mat4, Vec2};
+use crate::particle::{BlendMode, Particle};
+#[cfg(feature = "macroquad")]
+use crate::util::{billboard_axes, pop_transform, push_transform, set_camera};
+
+// most line segments drawn by a LineBatch in a single call, well within
+// macroquad's default draw call capacity
+#[cfg(feature = "macroquad")]
+const BATCH_SEGMENTS: usize = 1000;
+
+/// Backend drawing the primitives particles are made of.
+pub trait ParticleRenderer {
+    /// Draw the segment from `from` to `to` in `color`, or gather it to
+    /// be drawn by `flush()`.
+    fn draw_segment(&mut self, from: Vec3, to: Vec3, color: Color);
+
+    /// Draw a cube `size` wide centered on `center`, turned by
+    /// `rotation`, in `color`. The default draws it straight away with
+    /// macroquad, or not at all without the `macroquad` feature.
+    #[cfg_attr(not(feature = "macroquad"), allow(unused_variables))]
+    fn draw_cube(&mut self, center: Vec3, size: f32, rotation: Quat, color: Color) {
+        #[cfg(feature = "macroquad")]
+        if rotation == Quat::IDENTITY {
+            draw_cube(center, Vec3::splat(size), None, color);
+        } else {
+            push_transform(Mat4::from_rotation_translation(rotation, center));
+            draw_cube(Vec3::ZERO, Vec3::splat(size), None, color);
+            pop_transform();
+        }
+    }
+
+    /// Draw a sphere of `radius` centered on `center` in `color`. The
+    /// default draws it straight away with macroquad, or not at all
+    /// without the `macroquad` feature.
+    #[cfg_attr(not(feature = "macroquad"), allow(unused_variables))]
+    fn draw_sphere(&mut self, center: Vec3, radius: f32, color: Color) {
+        #[cfg(feature = "macroquad")]
+        draw_sphere(center, radius, None, color);
+    }
+
+    /// Draw a square `size` wide centered on `center`, facing the camera
+    /// and turned `angle` radians about its center, in `color`. The
+    /// default draws it straight away with macroquad, facing the position
+    /// of the camera given to `register_camera()` if there is one, and
+    /// lying flat against the view of the current camera otherwise. It
+    /// draws nothing without the `macroquad` feature.
+    #[cfg_attr(not(feature = "macroquad"), allow(unused_variables))]
+    fn draw_billboard(&mut self, center: Vec3, size: f32, angle: f32, color: Color) {
+        #[cfg(feature = "macroquad")]
+        {
+            let (right, up) = billboard_axes(center);
+            let (sin, cos) = angle.sin_cos();
+            let (right, up) = (right * cos + up * sin, up * cos - right * sin);
+            let (right, up) = (right * size, up * size);
+            let corner = center - (right + up) / 2.;
+            draw_affine_parallelogram(corner, right, up, None, color);
+        }
+    }
+
+    /// Draw the segments gathered since the last flush.
+    fn flush(&mut self) {}
+}
+
+/// Renderer drawing each segment straight away with macroquad.
+#[cfg(feature = "macroquad")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Immediate;
+
+#[cfg(feature = "macroquad")]
+impl ParticleRenderer for Immediate {
+    fn draw_segment(&mut self, from: Vec3, to: Vec3, color: Color) {
+        draw_line_3d(from, to, color);
+    }
+}
+
+/// Renderer gathering segments into one vertex buffer, drawn in a single
+/// draw call per thousand segments rather than one per segment.
+#[cfg(feature = "macroquad")]
+#[derive(Debug, Clone, Default)]
+pub struct LineBatch {
+    vertices: Vec<Vertex>,
+    indices: Vec<u16>,
+}
+
+#[cfg(feature = "macroquad")]
+impl LineBatch {
+    /// Create a new empty LineBatch.
+    pub fn new() -> Self {
+        LineBatch::default()
+    }
+
+    /// Return the number of segments waiting to be drawn.
+    pub fn len(&self) -> usize {
+        self.indices.len() / 2
+    }
+
+    /// Return `true` if no segments are waiting to be drawn.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+#[cfg(feature = "macroquad")]
+impl ParticleRenderer for LineBatch {
+    fn draw_segment(&mut self, from: Vec3, to: Vec3, color: Color) {
+        if self.len() >= BATCH_SEGMENTS {
+            self.flush();
+        }
+        let i = self.vertices.len() as u16;
+        self.vertices.push(Vertex::new2(from, Vec2::ZERO, color));
+        self.vertices.push(Vertex::new2(to, Vec2::ZERO, color));
+        self.indices.extend([i, i + 1]);
+    }
+
+    fn flush(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+        let gl = unsafe { get_internal_gl().quad_gl };
+        gl.texture(None);
+        gl.draw_mode(DrawMode::Lines);
+        gl.geometry(&self.vertices, &self.indices);
+        self.vertices.clear();
+        self.indices.clear();
+    }
+}
+
+/// Register `camera` as the camera the scene is seen from, turning each
+/// billboard drawn afterwards on this thread to face its position until
+/// `clear_camera()`. Without a registered camera, billboards lie flat
+/// against the view, which stretches them towards the edges of a wide
+/// field of view.
+#[cfg(feature = "macroquad")]
+pub fn register_camera(camera: &Camera3D) {
+    set_camera(Some((camera.position, camera.up)));
+}
+
+/// Forget the camera given to `register_camera()`.
+#[cfg(feature = "macroquad")]
+pub fn clear_camera() {
+    set_camera(None);
+}
+
+/// Call made to a `MockRenderer`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrawCall {
+    Segment {
+        from: Vec3,
+        to: Vec3,
+        color: Color,
+    },
+    Cube {
+        center: Vec3,
+        size: f32,
+        rotation: Quat,
+        color: Color,
+    },
+    Sphere {
+        center: Vec3,
+        radius: f32,
+        color: Color,
+    },
+    Billboard {
+        center: Vec3,
+        size: f32,
+        angle: f32,
+        color: Color,
+    },
+    Flush,
+}
+
+/// Renderer drawing nothing, keeping each call it is handed in order
+/// to be checked by tests.
+#[derive(Debug, Clone, Default)]
+pub struct MockRenderer {
+    calls: Vec<DrawCall>,
+}
+
+impl MockRenderer {
+    /// Create a new MockRenderer with no calls recorded.
+    pub fn new() -> Self {
+        MockRenderer::default()
+    }
+
+    /// Return the calls recorded, in the order they were made.
+    pub fn calls(&self) -> &[DrawCall] {
+        &self.calls
+    }
+
+    /// Return the endpoints and color of each segment recorded.
+    pub fn segments(&self) -> impl Iterator<Item = (Vec3, Vec3, Color)> + '_ {
+        self.calls.iter().filter_map(|c| match *c {
+            DrawCall::Segment { from, to, color } => Some((from, to, color)),
+            _ => None,
+        })
+    }
+
+    /// Forget the calls recorded so far.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+}
+
+impl ParticleRenderer for MockRenderer {
+    fn draw_segment(&mut self, from: Vec3, to: Vec3, color: Color) {
+        self.calls.push(DrawCall::Segment { from, to, color });
+    }
+
+    fn draw_cube(&mut self, center: Vec3, size: f32, rotation: Quat, color: Color) {
+        self.calls.push(DrawCall::Cube {
+            center,
+            size,
+            rotation,
+            color,
+        });
+    }
+
+    fn draw_sphere(&mut self, center: Vec3, radius: f32, color: Color) {
+        self.calls.push(DrawCall::Sphere {
+            center,
+            radius,
+            color,
+        });
+    }
+
+    fn draw_billboard(&mut self, center: Vec3, size: f32, angle: f32, color: Color) {
+        self.calls.push(DrawCall::Billboard {
+            center,
+            size,
+            angle,
+            color,
+        });
+    }
+
+    fn flush(&mut self) {
+        self.calls.push(DrawCall::Flush);
+    }
+}
+
+/// Renderer a system draws the segments of its particles with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// Each segment is drawn on its own as it comes.
+    #[default]
+    Immediate,
+    /// Segments are gathered into a `LineBatch` and drawn together.
+    Batched,
+    /// Segments are uploaded as instance data and drawn by a custom
+    /// shader in a single instanced draw call.
+    #[cfg(feature = "instancing")]
+    Instanced,
+}
+
+// the renderers of a system, kept between frames so their buffers are
+// reused
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Renderers {
+    #[cfg(feature = "macroquad")]
+    immediate: Immediate,
+    #[cfg(feature = "macroquad")]
+    batch: LineBatch,
+    #[cfg(feature = "instancing")]
+    instanced: Instanced,
+}
+
+impl Renderers {
+    // draw `particles` with the renderer of `mode`, blended by `blend`,
+    // drawing nothing without the macroquad feature
+    #[cfg_attr(not(feature = "instancing"), allow(unused_variables))]
+    pub(crate) fn draw<'a>(
+        &mut self,
+        mode: RenderMode,
+        blend: BlendMode,
+        particles: impl IntoIterator<Item = &'a mut Particle>,
+    ) {
+        #[cfg(feature = "macroquad")]
+        let renderer: &mut dyn ParticleRenderer = match mode {
+            RenderMode::Immediate => &mut self.immediate,
+            RenderMode::Batched => &mut self.batch,
+            #[cfg(feature = "instancing")]
+            RenderMode::Instanced => {
+                self.instanced.blend = blend;
+                &mut self.instanced
+            }
+        };
+        #[cfg(feature = "macroquad")]
+        {
+            for p in particles {
+                p.draw_to(renderer);
+            }
+            renderer.flush();
+        }
+    }
+}
+
+#[cfg(feature = "instancing")]
+pub use instancing::Instanced;
+
+#[cfg(feature = "instancing")]
+mod instancing {
+    use macroquad::color::Color;
+    use macroquad::math::{Mat4, Vec3};
+    use macroquad::miniquad::{
+        Bindings, BlendFactor, BlendState, BlendValue, BufferId, BufferLayout, BufferSource,
+        BufferType, BufferUsage, Comparison, Equation, PassAction, Pipeline, PipelineParams,
+        PrimitiveType, RenderingBackend, ShaderMeta, ShaderSource, UniformBlockLayout, UniformDesc,
+        UniformType, UniformsSource, VertexAttribute, VertexFormat, VertexStep,
+    };
+    use macroquad::window::get_internal_gl;
+    use std::cell::RefCell;
+
+    use super::ParticleRenderer;
+    use crate::particle::BlendMode;
+    use crate::util::current_transform;
+
+    const INSTANCE_VERTEX: &str = r#"#version 100
+attribute float end;
+attribute vec3 start_pos;
+attribute vec3 end_pos;
+attribute vec4 color0;
+
+varying lowp vec4 color;
+
+uniform mat4 mvp;
+
+void main() {
+    gl_Position = mvp * vec4(mix(start_pos, end_pos, end), 1);
+    color = color0;
+}"#;
+
+    const INSTANCE_FRAGMENT: &str = r#"#version 100
+varying lowp vec4 color;
+
+void main() {
+    gl_FragColor = color;
+}"#;
+
+    // segment as uploaded to the instance buffer
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    struct Segment {
+        from: [f32; 3],
+        to: [f32; 3],
+        color: [f32; 4],
+    }
+
+    #[repr(C)]
+    struct Uniforms {
+        mvp: Mat4,
+    }
+
+    // GPU resources shared by every Instanced renderer: a single line
+    // from `end` 0 to 1 drawn once per segment of the instance buffer,
+    // with a pipeline per blend mode and depth test
+    struct Gpu {
+        bindings: Bindings,
+        capacity: usize,
+        pipelines: [Pipeline; 4],
+    }
+
+    thread_local! {
+        // GPU resources of instanced rendering, created on first use
+        static GPU: RefCell<Option<Gpu>> = const { RefCell::new(None) };
+    }
+
+    impl Gpu {
+        fn new(ctx: &mut dyn RenderingBackend) -> Option<Self> {
+            let line = ctx.new_buffer(
+                BufferType::VertexBuffer,
+                BufferUsage::Immutable,
+                BufferSource::slice(&[0f32, 1.]),
+            );
+            let index_buffer = ctx.new_buffer(
+                BufferType::IndexBuffer,
+                BufferUsage::Immutable,
+                BufferSource::slice(&[0u16, 1]),
+            );
+            let shader = ctx
+                .new_shader(
+                    ShaderSource::Glsl {
+                        vertex: INSTANCE_VERTEX,
+                        fragment: INSTANCE_FRAGMENT,
+                    },
+                    ShaderMeta {
+                        images: Vec::new(),
+                        uniforms: UniformBlockLayout {
+                            uniforms: vec![UniformDesc::new("mvp", UniformType::Mat4)],
+                        },
+                    },
+                )
+                .ok()?;
+            let layouts = [
+                BufferLayout::default(),
+                BufferLayout {
+                    step_func: VertexStep::PerInstance,
+                    ..Default::default()
+                },
+            ];
+            let attributes = [
+                VertexAttribute::with_buffer("end", VertexFormat::Float1, 0),
+                VertexAttribute::with_buffer("start_pos", VertexFormat::Float3, 1),
+                VertexAttribute::with_buffer("end_pos", VertexFormat::Float3, 1),
+                VertexAttribute::with_buffer("color0", VertexFormat::Float4, 1),
+            ];
+            let mut pipeline = |blend: BlendMode, depth: bool| {
+                let destination = match blend {
+                    BlendMode::Additive => BlendFactor::One,
+                    BlendMode::Alpha => BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+                };
+                let params = PipelineParams {
+                    color_blend: Some(BlendState::new(
+                        Equation::Add,
+                        BlendFactor::Value(BlendValue::SourceAlpha),
+                        destination,
+                    )),
+                    depth_test: match depth {
+                        true => Comparison::LessOrEqual,
+                        false => Comparison::Always,
+                    },
+                    primitive_type: PrimitiveType::Lines,
+                    ..Default::default()
+                };
+                ctx.new_pipeline(&layouts, &attributes, shader, params)
+            };
+            let pipelines = [
+                pipeline(BlendMode::Alpha, false),
+                pipeline(BlendMode::Alpha, true),
+                pipeline(BlendMode::Additive, false),
+                pipeline(BlendMode::Additive, true),
+            ];
+            let instances = new_instance_buffer(ctx, 1);
+            Some(Gpu {
+                bindings: Bindings {
+                    vertex_buffers: vec![line, instances],
+                    index_buffer,
+                    images: Vec::new(),
+                },
+                capacity: 1,
+                pipelines,
+            })
+        }
+
+        // upload `segments`, growing the instance buffer if they no
+        // longer fit
+        fn upload(&mut self, ctx: &mut dyn RenderingBackend, segments: &[Segment]) {
+            if segments.len() > self.capacity {
+                self.capacity = segments.len().next_power_of_two();
+                ctx.delete_buffer(self.bindings.vertex_buffers[1]);
+                self.bindings.vertex_buffers[1] = new_instance_buffer(ctx, self.capacity);
+            }
+            ctx.buffer_update(
+                self.bindings.vertex_buffers[1],
+                BufferSource::slice(segments),
+            );
+        }
+    }
+
+    fn new_instance_buffer(ctx: &mut dyn RenderingBackend, capacity: usize) -> BufferId {
+        ctx.new_buffer(
+            BufferType::VertexBuffer,
+            BufferUsage::Stream,
+            BufferSource::empty::<Segment>(capacity),
+        )
+    }
+
+    /// Renderer uploading segments as instance data, each drawn from one
+    /// shared line by a custom shader, so that every segment of a system
+    /// is drawn in a single instanced draw call. Segments are drawn to the
+    /// current render target after anything macroquad has queued so far.
+    #[derive(Debug, Clone, Default)]
+    pub struct Instanced {
+        segments: Vec<Segment>,
+        pub(crate) blend: BlendMode,
+    }
+
+    impl Instanced {
+        /// Create a new empty Instanced renderer.
+        pub fn new() -> Self {
+            Instanced::default()
+        }
+    }
+
+    impl ParticleRenderer for Instanced {
+        fn draw_segment(&mut self, from: Vec3, to: Vec3, color: Color) {
+            self.segments.push(Segment {
+                from: from.to_array(),
+                to: to.to_array(),
+                color: [color.r, color.g, color.b, color.a],
+            });
+        }
+
+        fn flush(&mut self) {
+            if self.segments.is_empty() {
+                return;
+            }
+            let mut gl = unsafe { get_internal_gl() };
+            // draw what macroquad has queued first, keeping the order
+            gl.flush();
+            let ctx = gl.quad_context;
+            let depth = gl.quad_gl.is_depth_test_enabled();
+            let pass = gl.quad_gl.get_active_render_pass();
+            let mvp = gl.quad_gl.get_projection_matrix() * current_transform();
+            GPU.with(|gpu| {
+                let mut gpu = gpu.borrow_mut();
+                if gpu.is_none() {
+                    *gpu = Gpu::new(ctx);
+                }
+                let Some(gpu) = gpu.as_mut() else {
+                    return;
+                };
+                gpu.upload(ctx, &self.segments);
+                let blend = match self.blend {
+                    BlendMode::Alpha => 0,
+                    BlendMode::Additive => 2,
+                };
+                ctx.begin_pass(pass, PassAction::Nothing);
+                ctx.apply_pipeline(&gpu.pipelines[blend + depth as usize]);
+                ctx.apply_bindings(&gpu.bindings);
+                ctx.apply_uniforms(UniformsSource::table(&Uniforms { mvp }));
+                ctx.draw(0, 2, self.segments.len() as i32);
+                ctx.end_render_pass();
+            });
+            self.segments.clear();
+        }
+    }
+}
+
+#[test]
+fn render_segments_test() {
+    let mut p =
+        Particle::new_line((0., 0., 0.), (1., 0., 0.), (1., 1., 1., 1.), 1., false).unwrap();
+    let mut mock = MockRenderer::new();
+    p.draw_to(&mut mock);
+    let white = Color::new(1., 1., 1., 1.);
+    assert_eq!(
+        mock.segments().collect::<Vec<_>>(),
+        vec![(Vec3::ZERO, Vec3::X, white)]
+    );
+
+    p.set_thickness(0.5);
+    mock.clear();
+    p.draw_to(&mut mock);
+    assert_eq!(mock.segments().count(), 9);
+}
+
+#[test]
+fn mock_renderer_test() {
+    use crate::particle::ParticleShape;
+
+    let mut p =
+        Particle::new_line((0., 0., 0.), (1., 0., 0.), (1., 1., 1., 1.), 1., false).unwrap();
+    let mut mock = MockRenderer::new();
+    for shape in [
+        ParticleShape::Cube,
+        ParticleShape::Sphere,
+        ParticleShape::Billboard,
+        ParticleShape::Point,
+    ] {
+        p.set_shape(shape);
+        p.draw_to(&mut mock);
+    }
+    let calls = mock.calls();
+    assert_eq!(calls.len(), 4);
+    assert!(matches!(calls[0], DrawCall::Cube { size, rotation, .. }
+        if size == 1. && rotation == Quat::IDENTITY));
+    assert!(matches!(calls[1], DrawCall::Sphere { radius, .. } if radius == 0.5));
+    assert!(matches!(calls[2], DrawCall::Billboard { center, size, .. }
+        if center == Vec3::ZERO && size == 1.));
+    assert!(matches!(calls[3], DrawCall::Cube { .. }));
+
+    mock.flush();
+    assert_eq!(mock.calls().last(), Some(&DrawCall::Flush));
+}
+
+#[cfg(feature = "macroquad")]
+#[test]
+fn line_batch_test() {
+    let mut batch = LineBatch::new();
+    batch.draw_segment(Vec3::ZERO, Vec3::X, Color::new(1., 1., 1., 1.));
+    batch.draw_segment(Vec3::X, Vec3::Y, Color::new(1., 1., 1., 1.));
+    assert_eq!(batch.len(), 2);
+    assert_eq!(batch.indices, vec![0, 1, 2, 3]);
+    assert_eq!(batch.vertices[3].position, Vec3::Y);
+}
@@ -0,0 +1,114 @@
+//! # Rng
+//!
+//! Internal random number generator used by the particle systems. A
+//! `SysRng` draws from the thread's entropy unless given a seed, after
+//! which it repeats the same sequence every time the system starts.
+//! On the web, where the thread's entropy isn't available, unseeded
+//! generators are seeded from the clock instead.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+// fresh generator for an unseeded SysRng
+#[cfg(not(target_arch = "wasm32"))]
+fn unseeded() -> StdRng {
+    StdRng::from_rng(&mut rand::rng())
+}
+
+// fresh generator for an unseeded SysRng, seeded from the clock along
+// with a count of those created so far so that generators created in
+// the same instant still differ
+#[cfg(target_arch = "wasm32")]
+fn unseeded() -> StdRng {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static CREATED: AtomicU64 = AtomicU64::new(0);
+    let count = CREATED.fetch_add(1, Ordering::Relaxed);
+    StdRng::seed_from_u64(crate::time::now().to_bits() ^ count.rotate_left(32))
+}
+
+/// Random number generator of a particle system, reproducible once seeded.
+#[derive(Debug)]
+pub struct SysRng {
+    rng: StdRng,
+    seed: Option<u64>,
+}
+
+impl SysRng {
+    /// Create a new SysRng seeded from the thread's entropy.
+    pub fn new() -> Self {
+        SysRng {
+            rng: unseeded(),
+            seed: None,
+        }
+    }
+
+    /// Create a new SysRng producing the sequence of `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        SysRng {
+            rng: StdRng::seed_from_u64(seed),
+            seed: Some(seed),
+        }
+    }
+
+    /// Return the seed of the SysRng, if it has one.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Start the sequence of a seeded SysRng over from the beginning.
+    pub fn restart(&mut self) {
+        if let Some(seed) = self.seed {
+            self.rng = StdRng::seed_from_u64(seed);
+        }
+    }
+}
+
+impl Default for SysRng {
+    fn default() -> Self {
+        SysRng::new()
+    }
+}
+
+// clones of a seeded SysRng carry on with the same sequence, while those
+// of an unseeded one are seeded afresh so cloned systems don't spawn
+// copies of each other's particles
+impl Clone for SysRng {
+    fn clone(&self) -> Self {
+        match self.seed {
+            Some(_) => SysRng {
+                rng: self.rng.clone(),
+                seed: self.seed,
+            },
+            None => SysRng::new(),
+        }
+    }
+}
+
+impl RngCore for SysRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst)
+    }
+}
+
+#[test]
+fn sys_rng_seed_test() {
+    use rand::Rng;
+
+    let mut a = SysRng::seeded(7);
+    let first: Vec<u32> = (0..4).map(|_| a.random()).collect();
+    a.restart();
+    let again: Vec<u32> = (0..4).map(|_| a.random()).collect();
+    assert_eq!(first, again);
+    let mut b = a.clone();
+    assert_eq!(a.random::<u32>(), b.random::<u32>());
+    assert_eq!(b.seed(), Some(7));
+}
@@ -0,0 +1,107 @@
+//! # Screen
+//!
+//! Effects decorating HUD elements, buttons and menu transitions. A
+//! system in screen space works in pixels, with `y` pointing down the
+//! screen as in macroquad's default camera, and draws itself after
+//! switching to that camera whichever camera the rest of the scene uses.
+//!
+//! The ends of its line are `ScreenPoint`s, an offset in pixels from an
+//! `Anchor` on the screen, resolved against the size of the window each
+//! frame so the effect keeps its place as the window is resized:
+//!
+//! ```ignore
+//! let mut sparkle = LinearParticles::default().with_screen_space(
+//!     ScreenPoint::new(Anchor::TopRight, vec2(-220., 40.)),
+//!     ScreenPoint::new(Anchor::TopRight, vec2(-20., 40.)),
+//! );
+//!
+//! loop {
+//!     set_camera(&camera);
+//!     // draw the scene
+//!     sparkle.run()?;
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! Sizes, velocities and gravity of a system in screen space are in
+//! pixels as well, so gravity pulling particles down the screen is
+//! positive in `y`. Without a window, such as in `headless` builds,
+//! points are resolved against macroquad's default 800 by 600 window.
+
+use crate::math::{Vec2, Vec3};
+
+/// Point of the screen a `ScreenPoint` is offset from.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+    /// Percentages of the width and height of the screen, from the
+    /// top left corner.
+    Percent(f32, f32),
+}
+
+impl Anchor {
+    /// Return the share of the width and height of the screen the
+    /// anchor lies at, from `0` to `1`.
+    pub fn fraction(&self) -> Vec2 {
+        match *self {
+            Anchor::TopLeft => Vec2::new(0., 0.),
+            Anchor::Top => Vec2::new(0.5, 0.),
+            Anchor::TopRight => Vec2::new(1., 0.),
+            Anchor::Left => Vec2::new(0., 0.5),
+            Anchor::Center => Vec2::new(0.5, 0.5),
+            Anchor::Right => Vec2::new(1., 0.5),
+            Anchor::BottomLeft => Vec2::new(0., 1.),
+            Anchor::Bottom => Vec2::new(0.5, 1.),
+            Anchor::BottomRight => Vec2::new(1., 1.),
+            Anchor::Percent(x, y) => Vec2::new(x, y) / 100.,
+        }
+    }
+}
+
+/// Point on the screen, `offset` pixels from `anchor`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScreenPoint {
+    pub anchor: Anchor,
+    pub offset: Vec2,
+}
+
+impl ScreenPoint {
+    /// Create a new ScreenPoint `offset` pixels from `anchor`.
+    pub fn new(anchor: Anchor, offset: Vec2) -> Self {
+        ScreenPoint { anchor, offset }
+    }
+
+    /// Create a new ScreenPoint at `anchor`.
+    pub fn at(anchor: Anchor) -> Self {
+        ScreenPoint::new(anchor, Vec2::ZERO)
+    }
+
+    /// Return the location in pixels of the point on a screen of `size`
+    /// pixels.
+    pub fn resolve(&self, size: Vec2) -> Vec3 {
+        (self.anchor.fraction() * size + self.offset).extend(0.)
+    }
+}
+
+#[test]
+fn screen_point_test() {
+    let size = Vec2::new(800., 600.);
+    assert_eq!(
+        ScreenPoint::at(Anchor::Center).resolve(size),
+        Vec3::new(400., 300., 0.)
+    );
+    let corner = ScreenPoint::new(Anchor::BottomRight, Vec2::new(-20., -10.));
+    assert_eq!(corner.resolve(size), Vec3::new(780., 590., 0.));
+    let percent = ScreenPoint::at(Anchor::Percent(25., 50.));
+    assert_eq!(percent.resolve(size), Vec3::new(200., 300., 0.));
+    assert_eq!(percent.resolve(size * 2.), Vec3::new(400., 600., 0.));
+}
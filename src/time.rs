@@ -0,0 +1,181 @@
+//! # Time
+//!
+//! Internal clock used by the particle systems to measure elapsed time.
+//! A `Timer` reads wall time until it is given an external time with
+//! `set_now()` or stepped with `advance()`, after which it is driven
+//! entirely by the caller.
+//!
+//! Wall time is read from `std::time::Instant`, except on the web where
+//! it isn't available and macroquad's `get_time()` is read instead, or
+//! nothing at all without the `macroquad` feature.
+//!
+//! Systems count their time with a `SysTimer`, which can follow a shared
+//! `Clock` in place of the `Timer` it otherwise counts with.
+
+use crate::clock::Clock;
+
+/// Return the seconds elapsed on the wall clock since an arbitrary
+/// starting point, the same for the whole program.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64()
+}
+
+/// Return the seconds elapsed on the wall clock since the program
+/// started.
+#[cfg(all(target_arch = "wasm32", feature = "macroquad"))]
+pub fn now() -> f64 {
+    macroquad::time::get_time()
+}
+
+/// Return `0`, as there is no wall clock to read on the web without
+/// macroquad. Systems must be driven with `advance()` or `run_at()`.
+#[cfg(all(target_arch = "wasm32", not(feature = "macroquad")))]
+pub fn now() -> f64 {
+    0.
+}
+
+/// Elapsed time counter that can be driven by wall time or by
+/// a user-provided clock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timer {
+    start: f64,
+    origin: f32,
+    now: Option<f32>,
+}
+
+impl Timer {
+    /// Create a new Timer reading wall time.
+    pub fn new() -> Self {
+        Timer {
+            start: now(),
+            origin: 0.,
+            now: None,
+        }
+    }
+
+    // seconds of wall time since the start
+    fn wall_elapsed(&self) -> f32 {
+        (now() - self.start) as f32
+    }
+
+    /// Return the seconds elapsed since the last `reset()`.
+    pub fn elapsed(&self) -> f32 {
+        match self.now {
+            Some(now) => now - self.origin,
+            None => self.wall_elapsed(),
+        }
+    }
+
+    /// Restart the elapsed time counter from the current time.
+    pub fn reset(&mut self) {
+        self.start = now();
+        self.origin = self.now.unwrap_or(0.);
+    }
+
+    /// Set the current external time to `t` seconds, switching the
+    /// Timer over from wall time on the first call.
+    pub fn set_now(&mut self, t: f32) {
+        if self.now.is_none() {
+            self.origin = t - self.wall_elapsed();
+        }
+        self.now = Some(t);
+    }
+
+    /// Step the current external time `dt` seconds forward, switching
+    /// the Timer over from wall time on the first call.
+    pub fn advance(&mut self, dt: f32) {
+        let now = self
+            .now
+            .unwrap_or_else(|| self.origin + self.wall_elapsed());
+        self.now = Some(now + dt);
+    }
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Timer::new()
+    }
+}
+
+/// Elapsed time counter of a system, following a shared `Clock` once
+/// given one and counting as a `Timer` otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct SysTimer {
+    timer: Timer,
+    clock: Option<Clock>,
+    // time on the clock at the last `reset()`
+    origin: f32,
+}
+
+impl SysTimer {
+    /// Create a new SysTimer reading wall time.
+    pub fn new() -> Self {
+        SysTimer::default()
+    }
+
+    /// Return the seconds elapsed since the last `reset()`.
+    pub fn elapsed(&self) -> f32 {
+        match &self.clock {
+            Some(clock) => clock.time() - self.origin,
+            None => self.timer.elapsed(),
+        }
+    }
+
+    /// Restart the elapsed time counter from the current time.
+    pub fn reset(&mut self) {
+        self.timer.reset();
+        if let Some(clock) = &self.clock {
+            self.origin = clock.time();
+        }
+    }
+
+    /// See `Timer::set_now`. Has no effect while following a Clock.
+    pub fn set_now(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    /// See `Timer::advance`. Has no effect while following a Clock.
+    pub fn advance(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    /// Follow `clock` from now on, carrying on from the current elapsed
+    /// time.
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.origin = clock.time() - self.elapsed();
+        self.clock = Some(clock);
+    }
+}
+
+#[test]
+fn timer_external_time_test() {
+    let mut timer = Timer::new();
+    timer.set_now(10.);
+    timer.reset();
+    timer.set_now(12.5);
+    assert_eq!(timer.elapsed(), 2.5);
+    timer.reset();
+    assert_eq!(timer.elapsed(), 0.);
+    timer.advance(0.25);
+    timer.advance(0.25);
+    assert_eq!(timer.elapsed(), 0.5);
+}
+
+#[test]
+fn sys_timer_clock_test() {
+    let clock = Clock::new();
+    clock.pause();
+    let mut timer = SysTimer::new();
+    timer.set_now(3.);
+    timer.set_clock(clock.clone());
+    let elapsed = timer.elapsed();
+    clock.advance(1.);
+    assert_eq!(timer.elapsed(), elapsed + 1.);
+    timer.reset();
+    assert_eq!(timer.elapsed(), 0.);
+}
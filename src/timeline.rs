@@ -0,0 +1,400 @@
+//! # Timeline
+//!
+//! Scheduler for particle systems placed at explicit start times
+//! on a shared clock. Where `SyncGrp` and `SeqGrp` only express
+//! systems running together or one after another, a `Timeline`
+//! lets any system start at any point, overlapping freely with
+//! the others.
+
+use std::slice::{Iter, IterMut};
+
+use crate::callback::Hooks;
+use crate::clock::Clock;
+use crate::error::LinearPLError;
+use crate::particle_sys::{LoopMode, ParticleSys, SysInfo};
+use crate::time::SysTimer;
+use crate::util::{check_offsets, check_period};
+
+/// Collection of objects implementing ParticleSys, each starting at
+/// its own time `at` and running for its own `duration` seconds on
+/// the Timeline's clock. The period of the Timeline grows to fit the
+/// latest ending entry, and the whole Timeline can be looped. Entries
+/// whose time runs out go on aging what is left of their particles
+/// until it has decayed, while those scrubbed back before their start
+/// are torn down at once.
+#[derive(Debug, Clone)]
+pub struct Timeline<P: ParticleSys> {
+    period: f32,
+    parts: Vec<P>,
+    starts: Vec<f32>,
+    durations: Vec<f32>,
+    running: Vec<bool>,
+    // entries still aging their particles after their span, with the
+    // drain clock reading at which they would have been at time 0
+    draining: Vec<Option<f32>>,
+    drain_clock: f32,
+    last_time: f32,
+    timer: SysTimer,
+    active: bool,
+    mode: LoopMode,
+    cycle: u32,
+    initialized: bool,
+    hooks: Hooks,
+    peak_count: usize,
+}
+
+impl<P: ParticleSys + std::clone::Clone> Timeline<P> {
+    /// Create a new empty Timeline.
+    pub fn new() -> Self {
+        Timeline {
+            period: 0.,
+            parts: Vec::new(),
+            starts: Vec::new(),
+            durations: Vec::new(),
+            running: Vec::new(),
+            draining: Vec::new(),
+            drain_clock: 0.,
+            last_time: 0.,
+            timer: SysTimer::new(),
+            active: false,
+            mode: LoopMode::Once,
+            cycle: 0,
+            initialized: false,
+            hooks: Hooks::default(),
+            peak_count: 0,
+        }
+    }
+
+    /// Schedule `ps` to start `at` seconds into the Timeline and run
+    /// for `duration` seconds. Extends the period of the Timeline if
+    /// the entry ends after it.
+    pub fn add(&mut self, at: f32, duration: f32, ps: P) -> Result<(), LinearPLError> {
+        check_offsets(&[at], 1)?;
+        check_period(duration)?;
+        self.period = self.period.max(at + duration);
+        self.parts.push(ps);
+        self.starts.push(at);
+        self.durations.push(duration);
+        self.running.push(false);
+        self.draining.push(None);
+        Ok(())
+    }
+
+    /// Return self (consuming it) with `ps` scheduled `at` seconds
+    /// into the Timeline for `duration` seconds.
+    pub fn with_entry(mut self, at: f32, duration: f32, ps: P) -> Result<Self, LinearPLError> {
+        self.add(at, duration, ps)?;
+        Ok(self)
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// once the Timeline finishes its period without looping.
+    pub fn with_on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_complete(f);
+        self
+    }
+
+    /// Return self (consuming it) with callback `f` invoked from `run()`
+    /// each time the looping Timeline wraps back to the start.
+    pub fn with_on_loop(mut self, f: impl FnMut() + 'static) -> Self {
+        self.hooks.set_on_loop(f);
+        self
+    }
+
+    /// Return the period of the Timeline in seconds.
+    pub fn period(&self) -> f32 {
+        self.period
+    }
+
+    // advance the drain clock to `current_time`. Draining entries age on
+    // this monotonic clock so they keep decaying across loop resets of
+    // `current_time`.
+    fn advance_drain_clock(&mut self, current_time: f32) {
+        self.drain_clock += match current_time >= self.last_time {
+            true => current_time - self.last_time,
+            false => current_time,
+        };
+        self.last_time = current_time;
+    }
+}
+
+impl<P> ParticleSys for Timeline<P>
+where
+    P: ParticleSys + std::clone::Clone,
+{
+    type T = P;
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn is_looping(&self) -> bool {
+        self.active && self.mode.repeats_after(self.cycle)
+    }
+
+    fn is_initialized(&mut self) -> bool {
+        self.initialized
+    }
+
+    fn reset_time(&mut self) {
+        self.timer.reset();
+    }
+
+    fn elapsed_time(&mut self) -> Option<f32> {
+        Some(self.timer.elapsed())
+    }
+
+    fn set_time(&mut self, t: f32) {
+        self.timer.set_now(t);
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        self.timer.advance(dt);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.timer.set_clock(clock.clone());
+    }
+
+    fn setup(&mut self, mode: LoopMode, p: Option<f32>) -> Result<(), LinearPLError> {
+        if let Some(p) = p {
+            self.set_period(p)?;
+        }
+
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        self.running = vec![false; self.parts.len()];
+        self.draining = vec![None; self.parts.len()];
+        self.drain_clock = 0.;
+        self.last_time = 0.;
+
+        self.peak_count = 0;
+        self.mode = mode;
+        self.cycle = 0;
+        self.active = true;
+        self.initialized = true;
+        self.hooks.reset();
+        self.reset_time();
+        Ok(())
+    }
+
+    fn tear_down(&mut self) {
+        for ps in self.parts.iter_mut() {
+            ps.tear_down();
+        }
+        self.running = vec![false; self.parts.len()];
+        self.draining = vec![None; self.parts.len()];
+
+        self.active = false;
+        self.initialized = false;
+    }
+
+    fn update_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let line_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        self.advance_drain_clock(current_time);
+
+        // entries are set up as the time enters their span. Those left
+        // the way the Timeline plays drain, while those left the other
+        // way, as when scrubbing back, are torn down.
+        let reversed = self.mode.is_reversed(self.cycle);
+        for (i, ps) in self.parts.iter_mut().enumerate() {
+            let part_time = line_time - self.starts[i];
+            let duration = self.durations[i];
+            if part_time >= 0. && part_time <= duration {
+                if !self.running[i] {
+                    ps.tear_down();
+                    ps.setup(LoopMode::Once, Some(duration))?;
+                    self.running[i] = true;
+                    self.draining[i] = None;
+                }
+                ps.update_frame(Some(part_time))?;
+                continue;
+            }
+            if std::mem::take(&mut self.running[i]) {
+                match (reversed, part_time > duration) {
+                    (false, true) => self.draining[i] = Some(self.drain_clock - duration),
+                    (true, false) => self.draining[i] = Some(self.drain_clock),
+                    _ => ps.tear_down(),
+                }
+            }
+            if let Some(origin) = self.draining[i] {
+                if !ps.drain_frame(Some(self.drain_clock - origin))? {
+                    ps.tear_down();
+                    self.draining[i] = None;
+                }
+            }
+        }
+        self.peak_count = self.peak_count.max(self.particle_count());
+
+        Ok(current_time <= self.period)
+    }
+
+    // running entries are left to age as well, so a Timeline handed off
+    // by a sequence decays along with everything scheduled on it
+    fn drain_frame(&mut self, time: Option<f32>) -> Result<bool, LinearPLError> {
+        let current_time = match time {
+            Some(v) => v,
+            None => self.timer.elapsed(),
+        };
+        let line_time = match self.mode.is_reversed(self.cycle) {
+            true => self.period - current_time,
+            false => current_time,
+        };
+
+        self.advance_drain_clock(current_time);
+        let mut remaining = false;
+        for (i, ps) in self.parts.iter_mut().enumerate() {
+            let drain_time = match self.draining[i] {
+                Some(origin) => self.drain_clock - origin,
+                None if self.running[i] => line_time - self.starts[i],
+                None => continue,
+            };
+            remaining |= ps.drain_frame(Some(drain_time))?;
+        }
+        Ok(remaining)
+    }
+
+    fn iter(&self) -> Option<Iter<'_, Self::T>> {
+        Some(self.parts.iter())
+    }
+
+    fn iter_mut(&mut self) -> Option<IterMut<'_, Self::T>> {
+        Some(self.parts.iter_mut())
+    }
+
+    fn particle_count(&self) -> usize {
+        self.parts.iter().map(|ps| ps.particle_count()).sum()
+    }
+
+    fn peak_particle_count(&self) -> usize {
+        self.peak_count
+    }
+
+    fn spawned_total(&self) -> usize {
+        self.parts.iter().map(|ps| ps.spawned_total()).sum()
+    }
+
+    fn set_period(&mut self, p: f32) -> Result<(), LinearPLError> {
+        check_period(p)?;
+        self.period = p;
+        Ok(())
+    }
+
+    fn handle_complete(&mut self) {
+        self.hooks.complete();
+    }
+
+    // entries still running as the Timeline wraps around drain from
+    // where the cycle left them
+    fn handle_loop(&mut self) {
+        let end = match self.mode.is_reversed(self.cycle) {
+            true => 0.,
+            false => self.period,
+        };
+        for i in 0..self.parts.len() {
+            if std::mem::take(&mut self.running[i]) {
+                let part_time = (end - self.starts[i]).clamp(0., self.durations[i]);
+                self.draining[i] = Some(self.drain_clock - part_time);
+            }
+        }
+        self.cycle += 1;
+        self.hooks.looped();
+    }
+
+    fn redraw_frame(&mut self) -> Result<(), LinearPLError> {
+        for ps in self.parts.iter_mut() {
+            ps.redraw_frame()?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> SysInfo {
+        SysInfo {
+            period: Some(self.period),
+            elapsed: self.active.then(|| self.timer.elapsed()),
+            ..SysInfo::of(self)
+        }
+    }
+}
+
+impl<P: ParticleSys + std::clone::Clone> Default for Timeline<P> {
+    fn default() -> Self {
+        Timeline::new()
+    }
+}
+
+#[test]
+fn timeline_schedule_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let mut timeline = Timeline::new()
+        .with_entry(0., 2., LinearParticles::default())
+        .unwrap()
+        .with_entry(1.5, 2., LinearParticles::default())
+        .unwrap();
+    assert_eq!(timeline.period(), 3.5);
+    assert!(timeline.add(-1., 1., LinearParticles::default()).is_err());
+
+    timeline.start().unwrap();
+    assert_eq!(timeline.running, vec![false, false]);
+}
+
+#[test]
+fn timeline_overlap_test() {
+    use crate::linear_particles::LinearParticles;
+
+    let lp = || LinearParticles::default().with_decay(1.).unwrap();
+    let mut timeline = Timeline::new()
+        .with_entry(0., 1., lp())
+        .unwrap()
+        .with_entry(0.5, 1., lp())
+        .unwrap();
+    timeline.start_loop_at(0.).unwrap();
+
+    timeline.set_time(0.25);
+    assert!(timeline.update().unwrap());
+    assert_eq!(timeline.running, vec![true, false]);
+    timeline.set_time(0.75);
+    timeline.update().unwrap();
+    assert_eq!(timeline.running, vec![true, true]);
+    assert!(timeline.parts.iter().all(|ps| ps.particle_count() > 0));
+
+    // the first entry's particles outlive its span
+    timeline.set_time(1.25);
+    timeline.update().unwrap();
+    assert_eq!(timeline.running, vec![false, true]);
+    assert!(timeline.draining[0].is_some());
+    let spawned = timeline.parts[0].spawned_total();
+    timeline.set_time(1.4);
+    timeline.update().unwrap();
+    assert!(timeline.parts[0].is_active() && timeline.parts[0].particle_count() > 0);
+    assert_eq!(timeline.parts[0].spawned_total(), spawned);
+
+    // the loop wraps around, leaving the second entry to drain as the
+    // first one starts over
+    timeline.set_time(1.6);
+    assert!(!timeline.update().unwrap());
+    timeline.set_time(1.7);
+    timeline.update().unwrap();
+    assert_eq!(timeline.running, vec![true, false]);
+    assert!(timeline.draining[1].is_some());
+    assert!(timeline.parts[1].is_active() && timeline.parts[1].particle_count() > 0);
+
+    // scrubbing back before an entry's start tears it down at once
+    timeline.set_time(2.35);
+    timeline.update().unwrap();
+    timeline.set_time(1.85);
+    timeline.update().unwrap();
+    assert_eq!(timeline.running, vec![true, false]);
+    assert_eq!(timeline.draining[1], None);
+    assert!(!timeline.parts[1].is_active());
+}
@@ -3,189 +3,832 @@
 //! This submodule contains helping functions used by the rest of the
 //! library. It is not publicly accessible.
 
-use macroquad::prelude::{Color, Vec3};
-
-pub fn map_color_decay(orig: Color, current: f32, total: f32) -> Color {
-    Color::new(orig.r, orig.g, orig.b, orig.a * (1.0 - (current / total)))
-}
-
-// find the linearly interpolated value from 'values' given the ratio 'elapsed' / 'total'
-pub fn map_float_value(values: &[f32], elapsed: f32, total: f32) -> Result<f32, String> {
-    let ratio = elapsed / total;
-    let len = values.len() - 1;
-    let vratio = len as f32 * ratio;
-    let low = (vratio.floor()) as usize;
-    let high = (vratio.ceil()) as usize;
-
-    let low = if low > len { len } else { low };
-    let high = if high > len { len } else { high };
-
-    let first_value = match values.get(low) {
-        Some(val) => val,
-        None => {
-            return Err(format!(
-                "map_float_values indexing error: {} of {}",
-                low, len
-            ));
+#[cfg(feature = "macroquad")]
+use macroquad::camera::{pop_camera_state, push_camera_state, set_default_camera};
+#[cfg(feature = "macroquad")]
+use macroquad::material::{
+    gl_use_default_material, gl_use_material, load_material, Material, MaterialParams,
+};
+#[cfg(feature = "macroquad")]
+use macroquad::miniquad::{
+    BlendFactor, BlendState, BlendValue, Equation, PipelineParams, ShaderSource,
+};
+#[cfg(feature = "macroquad")]
+use macroquad::window::{get_internal_gl, screen_height, screen_width};
+#[cfg(feature = "macroquad")]
+use std::cell::Cell;
+use std::cell::RefCell;
+
+use crate::culling::Lod;
+use crate::error::LinearPLError;
+use crate::math::{Color, Mat4, Vec2, Vec3, WHITE};
+use crate::modulation::Factors;
+use crate::particle::BlendMode;
+
+// whether drawing is skipped, with the `headless` feature enabled or
+// without macroquad to draw with
+pub const HEADLESS: bool = cfg!(any(feature = "headless", not(feature = "macroquad")));
+
+// scale the opacity of `orig` by `fade`
+pub fn map_color_fade(orig: Color, fade: f32) -> Color {
+    Color::new(orig.r, orig.g, orig.b, orig.a * fade)
+}
+
+thread_local! {
+    // transforms pushed onto macroquad's model matrix stack, each already
+    // combined with those beneath it, which macroquad does not expose
+    static TRANSFORM_STACK: RefCell<Vec<Mat4>> = const { RefCell::new(Vec::new()) };
+}
+
+// push `m` onto macroquad's model matrix stack, applying it to everything
+// drawn until the matching `pop_transform()`
+pub fn push_transform(m: Mat4) {
+    TRANSFORM_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let top = stack.last().cloned().unwrap_or(Mat4::IDENTITY);
+        stack.push(top * m);
+    });
+    #[cfg(feature = "macroquad")]
+    if !HEADLESS {
+        unsafe { get_internal_gl().quad_gl.push_model_matrix(m) }
+    }
+}
+
+// pop the transform pushed by the last `push_transform()`
+pub fn pop_transform() {
+    TRANSFORM_STACK.with(|stack| stack.borrow_mut().pop());
+    #[cfg(feature = "macroquad")]
+    if !HEADLESS {
+        unsafe { get_internal_gl().quad_gl.pop_model_matrix() }
+    }
+}
+
+// combination of the transforms currently pushed, for drawing outside
+// of macroquad's own batching
+pub fn current_transform() -> Mat4 {
+    TRANSFORM_STACK.with(|stack| stack.borrow().last().cloned().unwrap_or(Mat4::IDENTITY))
+}
+
+// combined view and projection of the current camera
+#[cfg(feature = "macroquad")]
+pub fn view_projection() -> Mat4 {
+    unsafe { get_internal_gl().quad_gl.get_projection_matrix() }
+}
+
+// without macroquad there is no camera, so the world is seen as is
+#[cfg(not(feature = "macroquad"))]
+pub fn view_projection() -> Mat4 {
+    Mat4::IDENTITY
+}
+
+// world space right and up directions of the current camera, used to
+// draw shapes facing it
+#[cfg(feature = "macroquad")]
+pub fn camera_axes() -> (Vec3, Vec3) {
+    let inverse = view_projection().inverse();
+    let right = inverse.transform_vector3(Vec3::X).try_normalize();
+    let up = inverse.transform_vector3(Vec3::Y).try_normalize();
+    (right.unwrap_or(Vec3::X), up.unwrap_or(Vec3::Y))
+}
+
+#[cfg(feature = "macroquad")]
+thread_local! {
+    // position and up direction of the camera registered with
+    // `render::register_camera()`
+    static CAMERA: Cell<Option<(Vec3, Vec3)>> = const { Cell::new(None) };
+    // whether drawing in screen space, where the registered camera
+    // doesn't apply
+    static IN_SCREEN: Cell<bool> = const { Cell::new(false) };
+}
+
+// register the `position` and `up` direction of the camera billboards
+// face, or clear it if `None`
+#[cfg(feature = "macroquad")]
+pub fn set_camera(camera: Option<(Vec3, Vec3)>) {
+    CAMERA.with(|c| c.set(camera));
+}
+
+// right and up directions of a billboard at `center` facing `eye`, with
+// `up` pointing up, or `None` if it can't be told which way to face
+#[cfg(feature = "macroquad")]
+pub fn facing_axes(center: Vec3, eye: Vec3, up: Vec3) -> Option<(Vec3, Vec3)> {
+    let forward = (eye - center).try_normalize()?;
+    let right = up.cross(forward).try_normalize()?;
+    Some((right, forward.cross(right)))
+}
+
+// right and up directions of a billboard at `center`, facing the
+// registered camera if there is one and lying flat against the view of
+// the current camera otherwise. `center` is before the transforms
+// pushed, so the camera is brought into the same space.
+#[cfg(feature = "macroquad")]
+pub fn billboard_axes(center: Vec3) -> (Vec3, Vec3) {
+    let camera = CAMERA
+        .with(Cell::get)
+        .filter(|_| !IN_SCREEN.with(Cell::get));
+    camera
+        .and_then(|(eye, up)| {
+            let inverse = current_transform().inverse();
+            facing_axes(
+                center,
+                inverse.transform_point3(eye),
+                inverse.transform_vector3(up),
+            )
+        })
+        .unwrap_or_else(camera_axes)
+}
+
+// size of the window in pixels, or of macroquad's default window when
+// there is none to measure
+pub fn screen_size() -> Vec2 {
+    #[cfg(feature = "macroquad")]
+    if !HEADLESS {
+        return Vec2::new(screen_width(), screen_height());
+    }
+    Vec2::new(800., 600.)
+}
+
+// view and projection of macroquad's default camera, mapping pixels with
+// `y` pointing down onto the screen
+pub fn screen_projection() -> Mat4 {
+    let size = screen_size();
+    Mat4::orthographic_rh_gl(0., size.x, size.y, 0., -1., 1.)
+}
+
+// switch to macroquad's default camera to draw in screen space, until
+// the matching `end_screen()`
+pub fn begin_screen() {
+    #[cfg(feature = "macroquad")]
+    if !HEADLESS {
+        push_camera_state();
+        set_default_camera();
+        IN_SCREEN.with(|s| s.set(true));
+    }
+}
+
+// return to the camera in use before `begin_screen()`
+pub fn end_screen() {
+    #[cfg(feature = "macroquad")]
+    if !HEADLESS {
+        pop_camera_state();
+        IN_SCREEN.with(|s| s.set(false));
+    }
+}
+
+#[cfg(feature = "macroquad")]
+const BLEND_VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}"#;
+
+#[cfg(feature = "macroquad")]
+const BLEND_FRAGMENT: &str = r#"#version 100
+varying lowp vec4 color;
+varying lowp vec2 uv;
+
+uniform sampler2D Texture;
+
+void main() {
+    gl_FragColor = color * texture2D(Texture, uv);
+}"#;
+
+#[cfg(feature = "macroquad")]
+thread_local! {
+    // material adding particles onto what is beneath them, loaded on
+    // first use
+    static ADDITIVE: RefCell<Option<Material>> = const { RefCell::new(None) };
+}
+
+// load the material adding each fragment's color, scaled by its alpha,
+// onto the color beneath
+#[cfg(feature = "macroquad")]
+fn additive_material() -> Option<Material> {
+    ADDITIVE.with(|m| {
+        if m.borrow().is_none() {
+            let params = MaterialParams {
+                pipeline_params: PipelineParams {
+                    color_blend: Some(BlendState::new(
+                        Equation::Add,
+                        BlendFactor::Value(BlendValue::SourceAlpha),
+                        BlendFactor::One,
+                    )),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let shader = ShaderSource::Glsl {
+                vertex: BLEND_VERTEX,
+                fragment: BLEND_FRAGMENT,
+            };
+            *m.borrow_mut() = load_material(shader, params).ok();
         }
-    };
-
-    if low == high {
-        Ok(*first_value)
-    } else {
-        match values.get(high) {
-            Some(val) => {
-                let vratio_norm = high as f32 - vratio;
-                Ok((first_value * vratio_norm) + (val * (1.0 - vratio_norm)))
-            }
-            None => Err(format!(
-                "map_float_values indexing error: {} of {}",
-                high, len
-            )),
+        m.borrow().clone()
+    })
+}
+
+// switch to the material of `mode` for the particles drawn until the
+// matching `end_blend()`
+#[cfg_attr(not(feature = "macroquad"), allow(unused_variables))]
+pub fn begin_blend(mode: BlendMode) {
+    #[cfg(feature = "macroquad")]
+    if mode == BlendMode::Additive {
+        if let Some(m) = additive_material() {
+            gl_use_material(&m);
         }
     }
 }
 
+// undo `begin_blend()`
+#[cfg_attr(not(feature = "macroquad"), allow(unused_variables))]
+pub fn end_blend(mode: BlendMode) {
+    #[cfg(feature = "macroquad")]
+    if mode == BlendMode::Additive {
+        gl_use_default_material();
+    }
+}
+
+thread_local! {
+    // tints pushed by the groups currently drawing, each already
+    // combined with those beneath it
+    static TINT_STACK: RefCell<Vec<Color>> = const { RefCell::new(Vec::new()) };
+}
+
+// combine `c` with the current tint, applying it to every particle
+// drawn until the matching `pop_tint()`
+pub fn push_tint(c: Color) {
+    let combined = apply_tint(c);
+    TINT_STACK.with(|stack| stack.borrow_mut().push(combined));
+}
+
+// pop the tint pushed by the last `push_tint()`
+pub fn pop_tint() {
+    TINT_STACK.with(|stack| stack.borrow_mut().pop());
+}
+
+// multiply `c` by the current tint
+pub fn apply_tint(c: Color) -> Color {
+    let tint = TINT_STACK.with(|stack| stack.borrow().last().cloned().unwrap_or(WHITE));
+    Color::new(c.r * tint.r, c.g * tint.g, c.b * tint.b, c.a * tint.a)
+}
+
+thread_local! {
+    // modulation of the groups being updated, each already combined with
+    // those of the groups around it
+    static MODULATION_STACK: RefCell<Vec<Factors>> = const { RefCell::new(Vec::new()) };
+}
+
+// push the modulation `f` of a group, scaling the parts updated until
+// the matching `pop_modulation()`
+pub fn push_modulation(f: Factors) {
+    let combined = f.within(current_modulation());
+    MODULATION_STACK.with(|stack| stack.borrow_mut().push(combined));
+}
+
+// pop the modulation pushed by the last `push_modulation()`
+pub fn pop_modulation() {
+    MODULATION_STACK.with(|stack| stack.borrow_mut().pop());
+}
+
+// combination of the modulations of the groups being updated
+pub fn current_modulation() -> Factors {
+    MODULATION_STACK.with(|stack| stack.borrow().last().cloned().unwrap_or_default())
+}
+
+// apply the optional transform and tint of a group before its parts draw
+pub fn begin_draw(transform: Option<Mat4>, tint: Option<Color>) {
+    if let Some(m) = transform {
+        push_transform(m);
+    }
+    if let Some(c) = tint {
+        push_tint(c);
+    }
+}
+
+// undo `begin_draw()` once a group's parts have drawn
+pub fn end_draw(transform: Option<Mat4>, tint: Option<Color>) {
+    if tint.is_some() {
+        pop_tint();
+    }
+    if transform.is_some() {
+        pop_transform();
+    }
+}
+
+#[cfg(feature = "macroquad")]
+#[test]
+fn facing_axes_test() {
+    let (right, up) = facing_axes(Vec3::ZERO, Vec3::Z * 5., Vec3::Y).unwrap();
+    assert!((right - Vec3::X).length() < 1e-6 && (up - Vec3::Y).length() < 1e-6);
+    let (right, up) = facing_axes(Vec3::ZERO, Vec3::X * 2., Vec3::Y).unwrap();
+    assert!((right + Vec3::Z).length() < 1e-6 && (up - Vec3::Y).length() < 1e-6);
+    assert_eq!(facing_axes(Vec3::ZERO, Vec3::Y, Vec3::Y), None);
+    assert_eq!(facing_axes(Vec3::ONE, Vec3::ONE, Vec3::Y), None);
+}
+
 #[test]
-fn map_float_value_test() {
-    let values = vec![0.0, 1.0];
-    assert_eq!(map_float_value(&values, 0.0, 1.0).unwrap_or(-1.0), 0.0);
+fn tint_stack_test() {
+    push_tint(Color::new(1., 0.5, 1., 0.5));
+    push_tint(Color::new(0.5, 1., 1., 0.5));
+    assert_eq!(apply_tint(WHITE), Color::new(0.5, 0.5, 1., 0.25));
+    pop_tint();
+    pop_tint();
+    assert_eq!(apply_tint(WHITE), WHITE);
+}
+
+// `true` if `v` is a finite value of at least `0`, rejecting NaN and
+// infinities that would otherwise poison interpolation
+fn non_negative(v: f32) -> bool {
+    v.is_finite() && v >= 0.
+}
+
+// check that the period of LinearParticles is valid
+pub fn check_period(period: f32) -> Result<(), LinearPLError> {
+    match period {
+        p if non_negative(p) => Ok(()),
+        p => Err(LinearPLError::InvalidPeriod(p)),
+    }
+}
+
+#[test]
+fn test_check_period() {
+    assert_eq!(check_period(-0.5), Err(LinearPLError::InvalidPeriod(-0.5)));
     assert_eq!(
-        map_float_value(&values, 2.0 / 3.0, 1.0).unwrap_or(-1.0),
-        2.0 / 3.0
+        check_period(f32::INFINITY),
+        Err(LinearPLError::InvalidPeriod(f32::INFINITY))
     );
+    assert!(matches!(
+        check_period(f32::NAN),
+        Err(LinearPLError::InvalidPeriod(_))
+    ));
+}
+
+// check that the decay of LinearParticles is valid
+pub fn check_decay(decay: f32) -> Result<(), LinearPLError> {
+    match decay {
+        d if non_negative(d) => Ok(()),
+        d => Err(LinearPLError::InvalidDecay(d)),
+    }
+}
+
+#[test]
+fn test_check_decay() {
+    assert_eq!(check_decay(-0.5), Err(LinearPLError::InvalidDecay(-0.5)));
+}
 
-    let values = vec![1.0, 0.0, 0.5, 0.0];
-    assert_eq!(map_float_value(&values, 0.5, 1.0).unwrap_or(-1.0), 0.25);
+// check that the decays track values are valid
+pub fn check_decays(decays: &[f32]) -> Result<(), LinearPLError> {
+    if decays.is_empty() {
+        return Err(LinearPLError::EmptyTrack("decays"));
+    }
+    decays.iter().try_for_each(|d| check_decay(*d))
 }
 
-// find the linearly interpolated color from 'colors' given the ratio 'elapsed' / 'total'
-pub fn map_color_value(
-    colors: &[Color],
-    elapsed: f32,
-    total: f32,
-) -> Result<(f32, f32, f32, f32), String> {
-    let ratio = elapsed / total;
-    let len = colors.len() - 1;
-    let vratio = len as f32 * ratio;
-    let low = ((len as f32 * ratio).floor()) as usize;
-    let high = ((len as f32 * ratio).ceil()) as usize;
+// check that the spreads track values are valid
+pub fn check_spreads(spreads: &[f32]) -> Result<(), LinearPLError> {
+    if spreads.is_empty() {
+        return Err(LinearPLError::EmptyTrack("spreads"));
+    }
+    spreads.iter().try_for_each(|s| check_spread(*s))
+}
 
-    let low = if low > len { len } else { low };
-    let high = if high > len { len } else { high };
+#[test]
+fn test_check_spreads() {
+    assert_eq!(
+        check_spreads(&Vec::new()),
+        Err(LinearPLError::EmptyTrack("spreads"))
+    );
+    assert_eq!(
+        check_spreads(&[0., -0.5]),
+        Err(LinearPLError::InvalidSpread(-0.5))
+    );
+}
+
+#[test]
+fn test_check_decays() {
+    assert_eq!(
+        check_decays(&Vec::new()),
+        Err(LinearPLError::EmptyTrack("decays"))
+    );
+    assert_eq!(
+        check_decays(&[1., -2.]),
+        Err(LinearPLError::InvalidDecay(-2.))
+    );
+}
 
-    let first_value = match colors.get(low) {
-        Some(val) => val,
-        None => {
-            return Err(format!(
-                "map_color_value indexing error: {} of {}",
-                low, len
-            ));
+// check that the rates track values are valid
+pub fn check_rates(rates: &[f32]) -> Result<(), LinearPLError> {
+    if rates.is_empty() {
+        return Err(LinearPLError::EmptyTrack("rates"));
+    }
+    for r in rates.iter() {
+        if !non_negative(*r) {
+            return Err(LinearPLError::InvalidRate(*r));
         }
-    };
-
-    if low == high {
-        Ok((first_value.r, first_value.g, first_value.b, first_value.a))
-    } else {
-        match colors.get(high) {
-            Some(val) => {
-                let vratio_norm = high as f32 - vratio;
-                Ok((
-                    (first_value.r * vratio_norm) + (val.r * (1.0 - vratio_norm)),
-                    (first_value.g * vratio_norm) + (val.g * (1.0 - vratio_norm)),
-                    (first_value.b * vratio_norm) + (val.b * (1.0 - vratio_norm)),
-                    (first_value.a * vratio_norm) + (val.a * (1.0 - vratio_norm)),
-                ))
-            }
-            None => Err(format!(
-                "map_color_value indexing error: {} of {}",
-                high, len
-            )),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_rates() {
+    assert_eq!(
+        check_rates(&Vec::new()),
+        Err(LinearPLError::EmptyTrack("rates"))
+    );
+    assert_eq!(
+        check_rates(&[5., -1.]),
+        Err(LinearPLError::InvalidRate(-1.))
+    );
+}
+
+// check that the burst times are valid
+pub fn check_bursts(bursts: &[(f32, usize)]) -> Result<(), LinearPLError> {
+    match bursts.iter().find(|(t, _)| !non_negative(*t)) {
+        Some((t, _)) => Err(LinearPLError::InvalidBurst(*t)),
+        None => Ok(()),
+    }
+}
+
+#[test]
+fn test_check_bursts() {
+    assert_eq!(check_bursts(&[(0., 5), (0.5, 2)]), Ok(()));
+    assert_eq!(
+        check_bursts(&[(0.5, 2), (-1., 3)]),
+        Err(LinearPLError::InvalidBurst(-1.))
+    );
+}
+
+// check that the directions track values are valid
+pub fn check_directions(directions: &[Vec3]) -> Result<(), LinearPLError> {
+    if directions.is_empty() {
+        return Err(LinearPLError::EmptyTrack("directions"));
+    }
+    for d in directions.iter() {
+        if let Some(c) = d.to_array().into_iter().find(|c| !c.is_finite()) {
+            return Err(LinearPLError::InvalidDirection(c));
         }
     }
+    Ok(())
 }
 
 #[test]
-fn map_color_value_test() {
-    let values = vec![
-        Color::new(1.0, 1.0, 1.0, 1.0),
-        Color::new(0.0, 0.0, 0.0, 1.0),
-    ];
+fn test_check_directions() {
     assert_eq!(
-        map_color_value(&values, 0.5, 1.0).unwrap(),
-        (0.5, 0.5, 0.5, 1.0)
+        check_directions(&Vec::new()),
+        Err(LinearPLError::EmptyTrack("directions"))
     );
+    assert_eq!(check_directions(&[Vec3::X]), Ok(()));
     assert_eq!(
-        map_color_value(&values, 0.2, 1.0).unwrap(),
-        (0.8, 0.8, 0.8, 1.0)
+        check_directions(&[Vec3::new(0., f32::INFINITY, 0.)]),
+        Err(LinearPLError::InvalidDirection(f32::INFINITY))
     );
 }
 
-// Find the linearly interpolated location from 'start_location' to 'end_location'
-// given the 'locations' values and the ratio 'elapsed' / 'period'
-pub fn map_location(
-    locations: &[f32],
-    start_location: Vec3,
-    end_location: Vec3,
-    elapsed: f32,
-    period: f32,
-) -> Result<(f32, f32, f32), String> {
-    let ratio = map_float_value(locations, elapsed, period)?;
-    let vratio = Vec3::new(ratio, ratio, ratio);
-    let v = (start_location * vratio) + ((Vec3::ONE - vratio) * end_location);
-    Ok(v.into())
+// check that the warm-up duration of LinearParticles is valid
+pub fn check_warmup(warmup: f32) -> Result<(), LinearPLError> {
+    match warmup {
+        w if non_negative(w) => Ok(()),
+        w => Err(LinearPLError::InvalidWarmup(w)),
+    }
 }
 
-// check that the period of LinearParticles is valid
-pub fn check_period(period: f32) -> Result<(), String> {
-    match period {
-        p if p >= 0. => Ok(()),
-        p => Err(format!(
-            "value error: {} period should be positive value",
-            p
-        )),
+#[test]
+fn test_check_warmup() {
+    assert_eq!(check_warmup(-0.5), Err(LinearPLError::InvalidWarmup(-0.5)));
+}
+
+// check that the simulation timestep of LinearParticles is valid
+pub fn check_timestep(dt: f32) -> Result<(), LinearPLError> {
+    match dt {
+        t if t.is_finite() && t > 0. => Ok(()),
+        t => Err(LinearPLError::InvalidTimestep(t)),
     }
 }
 
 #[test]
-fn test_check_period() {
+fn test_check_timestep() {
+    assert_eq!(check_timestep(0.02), Ok(()));
+    assert_eq!(check_timestep(0.), Err(LinearPLError::InvalidTimestep(0.)));
+}
+
+// check that the speed of a Clock is valid
+pub fn check_time_scale(scale: f32) -> Result<(), LinearPLError> {
+    match scale {
+        s if non_negative(s) => Ok(()),
+        s => Err(LinearPLError::InvalidTimeScale(s)),
+    }
+}
+
+#[test]
+fn test_check_time_scale() {
+    assert_eq!(check_time_scale(0.), Ok(()));
     assert_eq!(
-        check_period(-0.5),
-        Err(String::from(
-            "value error: -0.5 period should be positive value"
-        ))
+        check_time_scale(-2.),
+        Err(LinearPLError::InvalidTimeScale(-2.))
     );
 }
 
-// check that the decay of LinearParticles is valid
-pub fn check_decay(decay: f32) -> Result<(), String> {
-    match decay {
-        d if d >= 0. => Ok(()),
-        d => Err(format!("value error: {} decay should be positive value", d)),
+// check that the duration of a recording is valid
+#[cfg(feature = "macroquad")]
+pub fn check_duration(seconds: f32) -> Result<(), LinearPLError> {
+    match seconds {
+        s if non_negative(s) => Ok(()),
+        s => Err(LinearPLError::InvalidDuration(s)),
     }
 }
 
+#[cfg(feature = "macroquad")]
 #[test]
-fn test_check_decay() {
+fn test_check_duration() {
+    assert_eq!(
+        check_duration(-1.),
+        Err(LinearPLError::InvalidDuration(-1.))
+    );
+}
+
+// check that the velocity stretch of LinearParticles is valid
+pub fn check_stretch(stretch: f32) -> Result<(), LinearPLError> {
+    match stretch {
+        s if non_negative(s) => Ok(()),
+        s => Err(LinearPLError::InvalidStretch(s)),
+    }
+}
+
+#[test]
+fn test_check_stretch() {
+    assert_eq!(
+        check_stretch(-0.5),
+        Err(LinearPLError::InvalidStretch(-0.5))
+    );
+}
+
+// check that the mass range of LinearParticles is valid
+pub fn check_mass(min: f32, max: f32) -> Result<(), LinearPLError> {
+    if !(min.is_finite() && min > 0.) {
+        return Err(LinearPLError::InvalidMass(min));
+    }
+    if !(max.is_finite() && max >= min) {
+        return Err(LinearPLError::InvalidMass(max));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_mass() {
+    assert_eq!(check_mass(0., 1.), Err(LinearPLError::InvalidMass(0.)));
+    assert_eq!(check_mass(2., 1.), Err(LinearPLError::InvalidMass(1.)));
+}
+
+// check that the distances and scales of the level of detail are valid
+pub fn check_lod(lod: &Lod) -> Result<(), LinearPLError> {
+    if !non_negative(lod.near) {
+        return Err(LinearPLError::InvalidLod(lod.near));
+    }
+    if !(lod.far.is_finite() && lod.far > lod.near) {
+        return Err(LinearPLError::InvalidLod(lod.far));
+    }
+    match [lod.density, lod.size]
+        .into_iter()
+        .find(|s| !(0. ..=1.).contains(s))
+    {
+        Some(s) => Err(LinearPLError::InvalidLod(s)),
+        None => Ok(()),
+    }
+}
+
+#[test]
+fn test_check_lod() {
+    assert_eq!(check_lod(&Lod::new(10., 50.)), Ok(()));
+    assert_eq!(
+        check_lod(&Lod::new(-1., 50.)),
+        Err(LinearPLError::InvalidLod(-1.))
+    );
+    assert_eq!(
+        check_lod(&Lod::new(10., 10.)),
+        Err(LinearPLError::InvalidLod(10.))
+    );
+    assert_eq!(
+        check_lod(&Lod::new(10., 50.).with_density(1.5)),
+        Err(LinearPLError::InvalidLod(1.5))
+    );
+}
+
+// check that the fade-in duration of LinearParticles is valid
+pub fn check_fade(fade: f32) -> Result<(), LinearPLError> {
+    match fade {
+        f if non_negative(f) => Ok(()),
+        f => Err(LinearPLError::InvalidFade(f)),
+    }
+}
+
+#[test]
+fn test_check_fade() {
+    assert_eq!(check_fade(-0.5), Err(LinearPLError::InvalidFade(-0.5)));
+}
+
+// check that the crossfade duration between group parts is valid
+pub fn check_crossfade(crossfade: f32) -> Result<(), LinearPLError> {
+    match crossfade {
+        c if non_negative(c) => Ok(()),
+        c => Err(LinearPLError::InvalidCrossfade(c)),
+    }
+}
+
+// check that a random spread is valid
+pub fn check_spread(spread: f32) -> Result<(), LinearPLError> {
+    match spread {
+        s if non_negative(s) => Ok(()),
+        s => Err(LinearPLError::InvalidSpread(s)),
+    }
+}
+
+#[test]
+fn test_check_spread() {
+    assert_eq!(check_spread(-0.5), Err(LinearPLError::InvalidSpread(-0.5)));
+}
+
+// check that at most one of two settings overriding each other, each
+// given as its name and whether it is set, is set
+pub fn check_exclusive(
+    (a, a_set): (&'static str, bool),
+    (b, b_set): (&'static str, bool),
+) -> Result<(), LinearPLError> {
+    match a_set && b_set {
+        true => Err(LinearPLError::ConflictingSettings(a, b)),
+        false => Ok(()),
+    }
+}
+
+#[test]
+fn test_check_exclusive() {
+    assert_eq!(
+        check_exclusive(("decay", true), ("decays", true)),
+        Err(LinearPLError::ConflictingSettings("decay", "decays"))
+    );
+    assert_eq!(check_exclusive(("decay", true), ("decays", false)), Ok(()));
+}
+
+// check that the drag coefficient of LinearParticles is valid
+pub fn check_drag(drag: f32) -> Result<(), LinearPLError> {
+    match drag {
+        d if non_negative(d) => Ok(()),
+        d => Err(LinearPLError::InvalidDrag(d)),
+    }
+}
+
+#[test]
+fn test_check_drag() {
+    assert_eq!(check_drag(-0.5), Err(LinearPLError::InvalidDrag(-0.5)));
+}
+
+// check that there is one non-negative start offset per child
+pub fn check_offsets(offsets: &[f32], children: usize) -> Result<(), LinearPLError> {
+    if offsets.len() != children {
+        return Err(LinearPLError::LengthMismatch {
+            context: "offsets",
+            expected: children,
+            found: offsets.len(),
+        });
+    }
+    for o in offsets.iter() {
+        if !non_negative(*o) {
+            return Err(LinearPLError::InvalidOffset(*o));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_offsets() {
+    assert_eq!(
+        check_offsets(&[0., 1.], 3),
+        Err(LinearPLError::LengthMismatch {
+            context: "offsets",
+            expected: 3,
+            found: 2
+        })
+    );
     assert_eq!(
-        check_decay(-0.5),
-        Err(String::from(
-            "value error: -0.5 decay should be positive value"
-        ))
+        check_offsets(&[0., -1.], 2),
+        Err(LinearPLError::InvalidOffset(-1.))
+    );
+}
+
+// check that there is one non-negative sequence weight per child
+pub fn check_weights(weights: &[f32], children: usize) -> Result<(), LinearPLError> {
+    if weights.len() != children {
+        return Err(LinearPLError::LengthMismatch {
+            context: "weights",
+            expected: children,
+            found: weights.len(),
+        });
+    }
+    for w in weights.iter() {
+        if !non_negative(*w) {
+            return Err(LinearPLError::InvalidWeight(*w));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_weights() {
+    assert_eq!(
+        check_weights(&[1., -2.], 2),
+        Err(LinearPLError::InvalidWeight(-2.))
+    );
+}
+
+// check that a part of a sequence plays at least once
+pub fn check_repeats(repeats: u32) -> Result<(), LinearPLError> {
+    match repeats {
+        0 => Err(LinearPLError::InvalidRepeats(0)),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_check_repeats() {
+    assert_eq!(check_repeats(1), Ok(()));
+    assert_eq!(check_repeats(0), Err(LinearPLError::InvalidRepeats(0)));
+}
+
+// check that there is one tag per child
+pub fn check_tags(tags: &[&str], children: usize) -> Result<(), LinearPLError> {
+    if tags.len() != children {
+        return Err(LinearPLError::LengthMismatch {
+            context: "tags",
+            expected: children,
+            found: tags.len(),
+        });
+    }
+    Ok(())
+}
+
+// check that the amplitude and frequency of track noise are valid
+pub fn check_noise(amplitude: f32, frequency: f32) -> Result<(), LinearPLError> {
+    if !non_negative(amplitude) {
+        return Err(LinearPLError::InvalidNoise(amplitude));
+    }
+    if !non_negative(frequency) {
+        return Err(LinearPLError::InvalidNoise(frequency));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_noise() {
+    assert_eq!(
+        check_noise(-0.5, 1.),
+        Err(LinearPLError::InvalidNoise(-0.5))
+    );
+    assert_eq!(check_noise(0.5, -1.), Err(LinearPLError::InvalidNoise(-1.)));
+}
+
+// check that keyframe times are within 0 to 1 and in increasing order
+pub fn check_key_times(times: &[f32]) -> Result<(), LinearPLError> {
+    let mut previous = 0.;
+    for t in times.iter() {
+        if !(previous..=1.).contains(t) {
+            return Err(LinearPLError::InvalidKeyTime(*t));
+        }
+        previous = *t;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_key_times() {
+    assert_eq!(check_key_times(&[0., 0.5, 1.]), Ok(()));
+    assert_eq!(
+        check_key_times(&[0., 0.5, 0.2]),
+        Err(LinearPLError::InvalidKeyTime(0.2))
+    );
+    assert_eq!(
+        check_key_times(&[-0.1]),
+        Err(LinearPLError::InvalidKeyTime(-0.1))
     );
 }
 
 // check that the locations interpolation values are valid
-pub fn check_locations(locations: &[f32]) -> Result<(), String> {
+pub fn check_locations(locations: &[f32]) -> Result<(), LinearPLError> {
     if locations.is_empty() {
-        return Err(String::from("empty: argument 'locations' cannot be empty"));
+        return Err(LinearPLError::EmptyTrack("locations"));
     }
     for l in locations.iter() {
-        if *l > 1. || *l < 0. {
-            return Err(format!(
-                "value error: {} location interpolation should be between 0 and 1 inclusive",
-                *l
-            ));
+        if !(0. ..=1.).contains(l) {
+            return Err(LinearPLError::InvalidLocation(*l));
         };
     }
     Ok(())
@@ -195,27 +838,26 @@ pub fn check_locations(locations: &[f32]) -> Result<(), String> {
 fn test_check_locations() {
     assert_eq!(
         check_locations(&Vec::new()),
-        Err(String::from("empty: argument 'locations' cannot be empty"))
+        Err(LinearPLError::EmptyTrack("locations"))
     );
     assert_eq!(
-        check_locations(&vec!(2.)),
-        Err(String::from(
-            "value error: 2 location interpolation should be between 0 and 1 inclusive"
-        ))
+        check_locations(&[2.]),
+        Err(LinearPLError::InvalidLocation(2.))
     );
+    assert!(matches!(
+        check_locations(&[0.5, f32::NAN]),
+        Err(LinearPLError::InvalidLocation(_))
+    ));
 }
 
 // check that the density chance values are valid
-pub fn check_densities(densities: &[f32]) -> Result<(), String> {
+pub fn check_densities(densities: &[f32]) -> Result<(), LinearPLError> {
     if densities.is_empty() {
-        return Err(String::from("empty: argument 'densities' cannot be empty"));
+        return Err(LinearPLError::EmptyTrack("densities"));
     }
     for d in densities.iter() {
-        if *d > 1. || *d < 0. {
-            return Err(format!(
-                "value error: {} density value should be between 0 and 1 inclusive",
-                *d
-            ));
+        if !(0. ..=1.).contains(d) {
+            return Err(LinearPLError::InvalidDensity(*d));
         };
     }
     Ok(())
@@ -225,20 +867,86 @@ pub fn check_densities(densities: &[f32]) -> Result<(), String> {
 fn test_check_densities() {
     assert_eq!(
         check_densities(&Vec::new()),
-        Err(String::from("empty: argument 'densities' cannot be empty"))
+        Err(LinearPLError::EmptyTrack("densities"))
     );
     assert_eq!(
-        check_densities(&vec!(2.)),
-        Err(String::from(
-            "value error: 2 density value should be between 0 and 1 inclusive"
-        ))
+        check_densities(&[2.]),
+        Err(LinearPLError::InvalidDensity(2.))
     );
+    assert!(matches!(
+        check_densities(&[0.5, f32::NAN]),
+        Err(LinearPLError::InvalidDensity(_))
+    ));
+}
+
+// check that the particle size values are valid
+pub fn check_sizes(sizes: &[f32]) -> Result<(), LinearPLError> {
+    if sizes.is_empty() {
+        return Err(LinearPLError::EmptyTrack("sizes"));
+    }
+    for s in sizes.iter() {
+        if !non_negative(*s) {
+            return Err(LinearPLError::InvalidSize(*s));
+        };
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_sizes() {
+    assert_eq!(
+        check_sizes(&Vec::new()),
+        Err(LinearPLError::EmptyTrack("sizes"))
+    );
+    assert_eq!(check_sizes(&[-1.]), Err(LinearPLError::InvalidSize(-1.)));
+}
+
+// check that the size over lifetime values are valid
+pub fn check_size_curve(curve: &[f32]) -> Result<(), LinearPLError> {
+    if curve.is_empty() {
+        return Err(LinearPLError::EmptyTrack("size_curve"));
+    }
+    for s in curve.iter() {
+        if !non_negative(*s) {
+            return Err(LinearPLError::InvalidSize(*s));
+        };
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_size_curve() {
+    assert_eq!(
+        check_size_curve(&Vec::new()),
+        Err(LinearPLError::EmptyTrack("size_curve"))
+    );
+    assert_eq!(
+        check_size_curve(&[1., -1.]),
+        Err(LinearPLError::InvalidSize(-1.))
+    );
+}
+
+// check that the glow width and opacity are valid
+pub fn check_glow(width: f32, alpha: f32) -> Result<(), LinearPLError> {
+    if !non_negative(width) {
+        return Err(LinearPLError::InvalidSize(width));
+    }
+    if !(0. ..=1.).contains(&alpha) {
+        return Err(LinearPLError::InvalidGlow(alpha));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_glow() {
+    assert_eq!(check_glow(-1., 0.5), Err(LinearPLError::InvalidSize(-1.)));
+    assert_eq!(check_glow(1., 1.5), Err(LinearPLError::InvalidGlow(1.5)));
 }
 
 // check that the color interpolations are valid
-pub fn check_colors(colors: &[Color]) -> Result<(), String> {
+pub fn check_colors(colors: &[Color]) -> Result<(), LinearPLError> {
     if colors.is_empty() {
-        return Err(String::from("empty: argument 'colors' cannot be empty"));
+        return Err(LinearPLError::EmptyTrack("colors"));
     }
     Ok(())
 }
@@ -247,6 +955,6 @@ pub fn check_colors(colors: &[Color]) -> Result<(), String> {
 fn test_check_colors() {
     assert_eq!(
         check_colors(&Vec::new()),
-        Err(String::from("empty: argument 'colors' cannot be empty"))
+        Err(LinearPLError::EmptyTrack("colors"))
     );
 }
@@ -0,0 +1,134 @@
+//! # Watch
+//!
+//! Live tuning of effect files. An `EffectWatcher` loads the system
+//! described by an effect file and reloads it whenever the file changes,
+//! so decays, colors and timings can be adjusted while the program runs:
+//!
+//! ```ignore
+//! let mut watcher = EffectWatcher::new("effects/seq.ron")?;
+//!
+//! loop {
+//!     if let Err(e) = watcher.poll() {
+//!         eprintln!("keeping the last effect: {}", e);
+//!     }
+//!     watcher.system().run()?;
+//!     next_frame().await;
+//! }
+//! ```
+//!
+//! The new system is only swapped in once it has loaded and validated in
+//! full, and carries on from the elapsed time of the one it replaces.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::clock::Clock;
+use crate::config::replace_system;
+use crate::error::LinearPLError;
+use crate::load::from_file;
+use crate::particle_sys::ParticleSysDyn;
+
+/// System loaded from an effect file, reloaded when the file changes.
+pub struct EffectWatcher {
+    path: PathBuf,
+    // modification time and length of the file when last loaded
+    stamp: Option<(SystemTime, u64)>,
+    sys: Box<dyn ParticleSysDyn>,
+    clock: Clock,
+}
+
+impl EffectWatcher {
+    /// Load the effect file at `path` and watch it for changes. Returns
+    /// an error if the file doesn't describe a valid system.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, LinearPLError> {
+        let path = path.as_ref().to_path_buf();
+        let stamp = stamp(&path);
+        let clock = Clock::new();
+        let mut sys = from_file(&path)?;
+        sys.set_clock(&clock);
+        Ok(EffectWatcher {
+            path,
+            stamp,
+            sys,
+            clock,
+        })
+    }
+
+    /// Return self (consuming it) with the system, and every system
+    /// reloaded in its place, following the shared `clock`.
+    pub fn with_clock(mut self, clock: &Clock) -> Self {
+        self.clock = clock.clone();
+        self.sys.set_clock(clock);
+        self
+    }
+
+    /// Return the path of the watched effect file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the Clock the system follows.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Return the currently loaded system.
+    pub fn system(&mut self) -> &mut Box<dyn ParticleSysDyn> {
+        &mut self.sys
+    }
+
+    /// Reload the effect file if it changed since it was last loaded,
+    /// returning `true` if the system was swapped. If the file fails to
+    /// load, the error is returned and the current system kept.
+    pub fn poll(&mut self) -> Result<bool, LinearPLError> {
+        let stamp = stamp(&self.path);
+        if stamp == self.stamp {
+            return Ok(false);
+        }
+        self.stamp = stamp;
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// Load the effect file again and swap the new system in place of
+    /// the current one, carrying on from its elapsed time. A system left
+    /// unstarted by the file is started in the way of the one it replaces.
+    pub fn reload(&mut self) -> Result<(), LinearPLError> {
+        let sys = from_file(&self.path)?;
+        replace_system(&mut self.sys, sys, &self.clock)
+    }
+}
+
+// modification time and length of the file at `path`, if readable
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn effect_watcher_test() {
+    let path = std::env::temp_dir().join(format!("linearpl-watch-{}.ron", std::process::id()));
+    let write =
+        |decay: &str| fs::write(&path, format!("Particles((decay: {}, mode: Loop))", decay));
+    write("0.5").unwrap();
+    let mut watcher = EffectWatcher::new(&path).unwrap();
+    watcher.clock().pause();
+    assert!(!watcher.poll().unwrap());
+
+    watcher.clock().advance(0.75);
+    let elapsed = watcher.system().elapsed_time().unwrap();
+    assert!((elapsed - 0.75).abs() < 0.05);
+    write("1.25").unwrap();
+    assert!(watcher.poll().unwrap());
+    assert!(watcher.system().is_looping());
+    let carried = watcher.system().elapsed_time().unwrap();
+    assert!((carried - elapsed).abs() < 0.05);
+
+    write("-1.00").unwrap();
+    assert!(watcher.poll().is_err());
+    assert!(watcher.system().is_looping());
+    assert!(!watcher.poll().unwrap());
+    fs::remove_file(&path).unwrap();
+}